@@ -1,11 +1,12 @@
 use cw_orch::{interface, prelude::*};
 
 use crate::{
-    execute, instantiate, query, reply, ExecuteMsg, InstantiateMsg, QueryMsg, CONTRACT_NAME,
+    execute, instantiate, migrate, query, reply, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    CONTRACT_NAME,
 };
 
 /// Uploadable trait for bs721_account_minter & use with cw-orchestrator library
-#[interface(InstantiateMsg, ExecuteMsg, QueryMsg, Empty)]
+#[interface(InstantiateMsg, ExecuteMsg, QueryMsg, MigrateMsg)]
 pub struct AccountRegistryMiddleware;
 
 impl<Chain> Uploadable for AccountRegistryMiddleware<Chain> {
@@ -17,6 +18,10 @@ impl<Chain> Uploadable for AccountRegistryMiddleware<Chain> {
     }
     /// Returns a CosmWasm contract wrapper
     fn wrapper() -> Box<dyn MockContract<Empty>> {
-        Box::new(ContractWrapper::new_with_empty(execute, instantiate, query).with_reply(reply))
+        Box::new(
+            ContractWrapper::new_with_empty(execute, instantiate, query)
+                .with_reply(reply)
+                .with_migrate(migrate),
+        )
     }
 }
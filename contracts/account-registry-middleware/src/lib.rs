@@ -1,16 +1,18 @@
-
-use std::fmt::format;
+use abstract_std::objects::gov_type::GovernanceDetails;
 use abstract_std::objects::namespace::Namespace;
+use abstract_std::objects::salt::generate_instantiate_salt;
 use abstract_std::objects::AccountId;
 use abstract_std::registry::{NamespaceResponse, QueryMsg as RegistryQueryMsg};
 use abstract_std::AbstractError;
 use bs721::NftInfoResponse;
 use btsg_account::market::hooks::{AskHookMsg, BidHookMsg, HookAction, SaleHookMsg};
+use btsg_account::market::{ValidationAction, ValidationResponse};
 use btsg_account::Metadata;
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{
-    entry_point, to_json_binary, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Empty, Env,
-    MessageInfo, Reply, Response, StdError, StdResult, SubMsg, WasmMsg,
+    entry_point, from_json, instantiate2_address, to_json_binary, BankMsg, Binary, CosmosMsg, Deps,
+    DepsMut, Empty, Env, MessageInfo, Reply, Response, StdError, StdResult, SubMsg, SubMsgResult,
+    Uint128, WasmMsg,
 };
 use cw_storage_plus::Item;
 use thiserror::Error;
@@ -24,6 +26,18 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 const CONFIG: Item<Config> = Item::new("marketplace");
 
+/// Reason code returned when the account token no longer matches its claimed namespace
+pub const ACCOUNT_TOKEN_CORRUPTED: u32 = 1;
+
+/// Account ids are the ask id offset into the registry's predictable range (first bit set),
+/// which the registry never assigns from its own sequence, so they can't collide with
+/// accounts created through the registry.
+pub const ACCOUNT_ID_OFFSET: u32 = 1 << 31;
+
+/// Reply ids of the submessages sent when an account token is listed
+pub const CREATE_ACCOUNT_REPLY_ID: u64 = 1;
+pub const CLAIM_NAMESPACE_REPLY_ID: u64 = 2;
+
 #[derive(Error, Debug)]
 pub enum ContractError {
     #[error("{0}")]
@@ -40,6 +54,24 @@ pub enum ContractError {
 
     #[error("ReplyErr: {e}")]
     ReplyErr { e: String },
+
+    #[error("Registry is not set")]
+    RegistryNotSet {},
+
+    #[error("Account creation for {token_id} failed: {error}")]
+    AccountCreationFailed { token_id: String, error: String },
+
+    #[error("Namespace claim for {token_id} failed: {error}")]
+    NamespaceClaimFailed { token_id: String, error: String },
+
+    #[error("Ask id {ask_id} is outside the predictable account id range")]
+    AccountIdOutOfRange { ask_id: u32 },
+
+    #[error("Cannot migrate from contract {contract}")]
+    InvalidMigrationContract { contract: String },
+
+    #[error("Cannot migrate from version {from} to {to}")]
+    InvalidMigrationVersion { from: String, to: String },
 }
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -86,6 +118,15 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(Config)]
     Config {},
+    /// Validation hook called by the marketplace before a bid is placed or accepted.
+    /// Serializes the same as `ValidationHookQueryMsg::ValidateBid`.
+    #[returns(ValidationResponse)]
+    ValidateBid {
+        token_id: String,
+        bidder: String,
+        amount: Uint128,
+        action: ValidationAction,
+    },
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -114,7 +155,7 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
@@ -129,15 +170,16 @@ pub fn execute(
             collection,
             owner,
         } => update_config(deps, info, market, registry, collection, owner),
-        ExecuteMsg::AskCreatedHook(a) => p_ask(info, a, &c, HookAction::Create),
-        ExecuteMsg::AskUpdatedHook(a) => p_ask(info, a, &c, HookAction::Update),
-        ExecuteMsg::AskDeletedHook(a) => p_ask(info, a, &c, HookAction::Delete),
+        ExecuteMsg::AskCreatedHook(a) => p_ask(deps.as_ref(), env, info, a, &c, HookAction::Create),
+        ExecuteMsg::AskUpdatedHook(a) => p_ask(deps.as_ref(), env, info, a, &c, HookAction::Update),
+        ExecuteMsg::AskDeletedHook(a) => p_ask(deps.as_ref(), env, info, a, &c, HookAction::Delete),
         ExecuteMsg::BidCreatedHook(b) => p_bid(deps, info, b, c, HookAction::Create),
         ExecuteMsg::BidUpdatedHook(b) => p_bid(deps, info, b, c, HookAction::Update),
         ExecuteMsg::BidDeletedHook(b) => p_bid(deps, info, b, c, HookAction::Delete),
         ExecuteMsg::SaleHook(s) => process_sale_hook(info, s, &c.market),
         ExecuteMsg::RegistryAction(execute_msg) => {
-            route_registry_action(info, c.current_admin, c.registry.unwrap(), &execute_msg)
+            let registry = c.registry.ok_or(ContractError::RegistryNotSet {})?;
+            route_registry_action(info, c.current_admin, registry, &execute_msg)
         }
     }
 }
@@ -146,31 +188,52 @@ pub fn execute(
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => cosmwasm_std::to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::ValidateBid { token_id, .. } => to_json_binary(&validate_bid(deps, token_id)?),
     }
 }
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
-    println!("{:#?}", msg);
-    match msg.result {
-        cosmwasm_std::SubMsgResult::Ok(_) => Ok(Response::default()),
-        cosmwasm_std::SubMsgResult::Err(e) => return Err(ContractError::ReplyErr { e }),
+    // submessages only reply on error, reverting the hook so the marketplace records it for a retry
+    let error = match msg.result {
+        SubMsgResult::Ok(_) => return Ok(Response::default()),
+        SubMsgResult::Err(error) => error,
+    };
+    let token_id: String = from_json(&msg.payload)?;
+    match msg.id {
+        CREATE_ACCOUNT_REPLY_ID => Err(ContractError::AccountCreationFailed { token_id, error }),
+        CLAIM_NAMESPACE_REPLY_ID => Err(ContractError::NamespaceClaimFailed { token_id, error }),
+        _ => Err(ContractError::ReplyErr { e: error }),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
     let then = cw2::get_contract_version(deps.storage)?;
-    if then.version >= CONTRACT_VERSION.to_owned() || then.contract != CONTRACT_NAME.to_owned() {
-        return Err(ContractError::Std(StdError::generic_err(
-            "unable to migrate contract.",
-        )));
+    if then.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidMigrationContract {
+            contract: then.contract,
+        });
+    }
+    if parse_version(&then.version)? >= parse_version(CONTRACT_VERSION)? {
+        return Err(ContractError::InvalidMigrationVersion {
+            from: then.version,
+            to: CONTRACT_VERSION.to_string(),
+        });
     }
     cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     Ok(Response::default())
 }
 
+fn parse_version(version: &str) -> StdResult<semver::Version> {
+    version
+        .parse()
+        .map_err(|err| StdError::parse_err("semver::Version", err))
+}
+
 pub fn p_ask(
+    deps: Deps,
+    env: Env,
     info: MessageInfo,
     hook: AskHookMsg,
     c: &Config,
@@ -182,22 +245,20 @@ pub fn p_ask(
     let r = c
         .registry
         .as_ref()
-        .expect("registry is yet to be registered");
+        .ok_or(ContractError::RegistryNotSet {})?;
 
     // set anmespace for newly minted token on framework.
-    let mut res = Response::default();
-    let mut msgs = match method {
-        HookAction::Create => claim_namespace(&mut res, c, hook)?,
+    let res = Response::default();
+    match method {
+        HookAction::Create => Ok(res.add_submessages(claim_namespace(deps, &env, c, r, hook)?)),
 
         HookAction::Update => {
             // check that namespaces are still in sync with current account owners.
-            vec![]
+            Ok(res)
         }
         // forgoe namespace if account token is burnt (burning is only way ask-hook invokes HookAction::Delete)
-        HookAction::Delete => forgoe_namespace(&hook.ask.token_id, r)?,
-    };
-
-    Ok(res.add_messages(msgs))
+        HookAction::Delete => Ok(res.add_messages(forgoe_namespace(&hook.ask.token_id, r)?)),
+    }
 }
 
 pub fn p_bid(
@@ -213,30 +274,7 @@ pub fn p_bid(
 
     match method {
         HookAction::Create => {
-            let namespace = deps.querier.query_wasm_smart::<NamespaceResponse>(
-                config.registry.unwrap(),
-                &RegistryQueryMsg::Namespace {
-                    namespace: Namespace::new(&hook.bid.token_id)?,
-                },
-            )?;
-
-            match namespace {
-                NamespaceResponse::Claimed(namespace_info) => {
-                    let token: NftInfoResponse<Metadata> = deps.querier.query_wasm_smart(
-                        config.collection,
-                        &bs721::Bs721QueryMsg::NftInfo {
-                            token_id: hook.bid.token_id,
-                        },
-                    )?;
-
-                    if token.token_uri.expect("should have this set")
-                        != namespace_info.account.into_addr().to_string()
-                    {
-                        return Err(ContractError::AccountTokenIsCorrupted {});
-                    };
-                }
-                NamespaceResponse::Unclaimed {} => {}
-            }
+            ensure_account_token_intact(deps.as_ref(), &config, hook.bid.token_id)?;
         }
         // noop
         HookAction::Update => {}
@@ -247,6 +285,52 @@ pub fn p_bid(
     Ok(Response::default())
 }
 
+/// Denies bids on account tokens that no longer match their claimed namespace
+pub fn validate_bid(deps: Deps, token_id: String) -> StdResult<ValidationResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    match ensure_account_token_intact(deps, &config, token_id) {
+        Ok(()) => Ok(ValidationResponse::Allow {}),
+        Err(ContractError::AccountTokenIsCorrupted {}) => Ok(ValidationResponse::Deny {
+            code: ACCOUNT_TOKEN_CORRUPTED,
+            reason: ContractError::AccountTokenIsCorrupted {}.to_string(),
+        }),
+        Err(err) => Err(StdError::generic_err(err.to_string())),
+    }
+}
+
+fn ensure_account_token_intact(
+    deps: Deps,
+    config: &Config,
+    token_id: String,
+) -> Result<(), ContractError> {
+    // nothing to compare against until a registry is set
+    let registry = match &config.registry {
+        Some(registry) => registry,
+        None => return Ok(()),
+    };
+    let namespace = deps.querier.query_wasm_smart::<NamespaceResponse>(
+        registry,
+        &RegistryQueryMsg::Namespace {
+            namespace: Namespace::new(&token_namespace(&token_id)?)?,
+        },
+    )?;
+
+    match namespace {
+        NamespaceResponse::Claimed(namespace_info) => {
+            let token: NftInfoResponse<Metadata> = deps.querier.query_wasm_smart(
+                &config.collection,
+                &bs721::Bs721QueryMsg::NftInfo { token_id },
+            )?;
+
+            if token.token_uri != Some(namespace_info.account.into_addr().to_string()) {
+                return Err(ContractError::AccountTokenIsCorrupted {});
+            };
+        }
+        NamespaceResponse::Unclaimed {} => {}
+    }
+    Ok(())
+}
+
 pub fn process_sale_hook(
     info: MessageInfo,
     hook: SaleHookMsg,
@@ -279,50 +363,87 @@ pub fn route_registry_action(
     }))
 }
 
-fn claim_namespace(res: &mut Response, c: &Config, hook: AskHookMsg) -> StdResult<Vec<CosmosMsg>> {
-    // let namespace = format!(
-    //     "{}:{}",
-    //     hook.ask.token_id.clone(),
-    //     hook.ask.seller.to_string()
-    // );
+/// Local account id of the account created for an ask
+pub fn account_id(ask_id: u32) -> Result<AccountId, ContractError> {
+    ACCOUNT_ID_OFFSET
+        .checked_add(ask_id)
+        .map(AccountId::local)
+        .ok_or(ContractError::AccountIdOutOfRange { ask_id })
+}
+
+/// Creates an Abstract account owned by the account token, with an account id derived from the ask id,
+/// and claims the token id as its namespace. The middleware must own the registry.
+fn claim_namespace(
+    deps: Deps,
+    env: &Env,
+    c: &Config,
+    registry: &String,
+    hook: AskHookMsg,
+) -> Result<Vec<SubMsg>, ContractError> {
+    let token_id = hook.ask.token_id;
+    let account_id = account_id(hook.ask.id)?;
+
+    // accounts are instantiated at the address predicted from their account id, and are their own admin
+    let salt = generate_instantiate_salt(&account_id);
+    let checksum = deps
+        .querier
+        .query_wasm_code_info(c.account_code_id)?
+        .checksum;
+    let creator = deps.api.addr_canonicalize(env.contract.address.as_str())?;
+    let account = instantiate2_address(checksum.as_slice(), &creator, &salt)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    let account = deps.api.addr_humanize(&account)?;
+
+    let create_account = WasmMsg::Instantiate2 {
+        admin: Some(account.to_string()),
+        code_id: c.account_code_id,
+        label: "Bitsong Abstract Account".into(),
+        msg: to_json_binary(&abstract_std::account::InstantiateMsg::<Empty> {
+            code_id: c.account_code_id,
+            owner: Some(GovernanceDetails::NFT {
+                collection_addr: c.collection.clone(),
+                token_id: token_id.clone(),
+            }),
+            account_id: Some(account_id.clone()),
+            authenticator: None,
+            namespace: None,
+            install_modules: vec![],
+            name: Some(token_id.clone()),
+            description: Some("Powered By Bitsong Account Framework".into()),
+            link: None,
+        })?,
+        funds: vec![],
+        salt,
+    };
+    let claim = WasmMsg::Execute {
+        contract_addr: registry.to_string(),
+        msg: to_json_binary(&abstract_std::registry::ExecuteMsg::ClaimNamespace {
+            account_id,
+            namespace: token_namespace(&token_id)?,
+        })?,
+        funds: vec![],
+    };
 
+    let payload = to_json_binary(&token_id)?;
     Ok(vec![
-        // cosmwasm_std::CosmosMsg::Wasm(WasmMsg::Instantiate {
-        //     admin: None,
-        //     code_id: c.account_code_id.clone(),
-        //     msg: to_json_binary(&abstract_std::account::InstantiateMsg::<Empty> {
-        //         code_id: c.account_code_id,
-        //         owner: Some(abstract_std::objects::gov_type::GovernanceDetails::NFT {
-        //             collection_addr: c.collection.clone(),
-        //             token_id: hook.ask.token_id.clone(),
-        //         }),
-        //         account_id: Some(AccountId::local(hook.ask.id)),
-        //         authenticator: None,
-        //         namespace: None,
-        //         install_modules: vec![], // TODO: install USB
-        //         name: Some(hook.ask.token_id.clone()),
-        //         description: Some("Powered By Bitsong Account Framework".into()),
-        //         link: None,
-        //     })?,
-        //     funds: vec![],
-        //     label: "Bitsong Abstract Account".into(),
-        // }),
-        // cosmwasm_std::CosmosMsg::Wasm(WasmMsg::Execute {
-        //     contract_addr: c.registry.to_owned().expect("registry was not set"),
-        //     msg: to_json_binary(&abstract_std::registry::ExecuteMsg::ClaimNamespace {
-        //         account_id: AccountId::local(hook.ask.id),
-        //         namespace: hook.ask.token_id.clone(),
-        //     })?,
-        //     funds: vec![],
-        // }),
+        SubMsg::reply_on_error(create_account, CREATE_ACCOUNT_REPLY_ID)
+            .with_payload(payload.clone()),
+        SubMsg::reply_on_error(claim, CLAIM_NAMESPACE_REPLY_ID).with_payload(payload),
     ])
 }
 
+/// Namespace claimed for an account token. Internationalized token ids are claimed in their
+/// punycode form, since the registry only accepts ASCII namespaces.
+pub fn token_namespace(token_id: &str) -> StdResult<String> {
+    btsg_account::idn::to_punycode(token_id)
+        .ok_or_else(|| StdError::generic_err(format!("{token_id} has no punycode form")))
+}
+
 fn forgoe_namespace(token_id: &String, market: &String) -> StdResult<Vec<CosmosMsg>> {
     Ok(vec![cosmwasm_std::CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: market.to_string(),
         msg: to_json_binary(&abstract_std::registry::ExecuteMsg::ForgoNamespace {
-            namespaces: vec![token_id.into()],
+            namespaces: vec![token_namespace(token_id)?],
         })?,
         funds: vec![],
     })])
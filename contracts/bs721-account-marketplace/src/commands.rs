@@ -1,19 +1,26 @@
 use crate::{
     error::ContractError,
-    hooks::{prepare_ask_hook, prepare_bid_hook, prepare_sale_hook},
+    hooks::{hook_submsg, prepare_ask_hook, prepare_bid_hook, prepare_sale_hook, HookReply},
     state::*,
 };
 
 use bs721::{NftInfoResponse, OwnerOfResponse};
-use bs721_account::{helpers::Bs721Account, msg::ExecuteMsg as Bs721AccountExecuteMsg};
+use bs721_account::{
+    helpers::Bs721Account,
+    msg::{Bs721AccountsQueryMsg, ExecuteMsg as Bs721AccountExecuteMsg},
+};
 use btsg_account::{
-    charge_fees, market::hooks::HookAction, market::*, validate_aa_ownership, Metadata, TokenId,
-    DEFAULT_QUERY_LIMIT, DEPLOYMENT_DAO, MAX_QUERY_LIMIT, NATIVE_DENOM,
+    charge_fees,
+    market::hooks::{HookAction, ValidateBidMsg, ValidationHookQueryMsg},
+    market::*,
+    validate_aa_ownership, Metadata, TokenId, DEFAULT_QUERY_LIMIT, DEPLOYMENT_DAO, MAX_QUERY_LIMIT,
+    NATIVE_DENOM,
 };
 
 use cosmwasm_std::{
-    coin, to_json_binary, Addr, BankMsg, Decimal, Deps, DepsMut, Env, Event, Fraction, MessageInfo,
-    Order, Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
+    coin, to_json_binary, Addr, BankMsg, Coin, Decimal, Deps, DepsMut, Env, Event, Fraction,
+    MessageInfo, Order, Response, StdError, StdResult, Storage, SubMsg, Timestamp, Uint128,
+    WasmMsg,
 };
 use cw_storage_plus::Bound;
 use cw_utils::{must_pay, nonpayable};
@@ -38,7 +45,8 @@ pub fn execute_setup(
     ))
 }
 
-/// A seller may set an Ask on their NFT to list it on Marketplace
+/// A seller may set an Ask on their NFT to list it on Marketplace.
+/// A seller may only list the same token once every `ask_interval` seconds.
 pub fn execute_set_ask(
     deps: DepsMut,
     env: Env,
@@ -53,19 +61,20 @@ pub fn execute_set_ask(
 
     let collection = ACCOUNT_COLLECTION.load(deps.storage)?;
 
-    // check if collection is approved to transfer on behalf of the seller
-    let ops = Bs721Account(collection).all_operators(
-        &deps.querier,
-        seller.to_string(),
-        false,
-        None,
-        None,
-    )?;
-
-    // println!("{:#?}", ops);
-    if ops.is_empty() {
-        return Err(ContractError::NotApproved {});
+    // check if the marketplace is approved to transfer the token, which the collection does at mint
+    Bs721Account(collection)
+        .approval(&deps.querier, token_id, env.contract.address.as_str(), None)
+        .map_err(|_| ContractError::NotApproved {})?;
+
+    let ask_interval = SUDO_PARAMS.load(deps.storage)?.ask_interval;
+    let key = (seller.clone(), ask_key(token_id));
+    if let Some(last_updated) = ask_updated().may_load(deps.storage, key.clone())? {
+        let next_update = last_updated.plus_seconds(ask_interval);
+        if env.block.time < next_update {
+            return Err(ContractError::AskUpdateTooSoon(next_update));
+        }
     }
+    prune_ask_updated(deps.storage, env.block.time, ask_interval)?;
 
     let renewal_time = env.block.time.plus_seconds(31536000u64);
 
@@ -75,6 +84,7 @@ pub fn execute_set_ask(
         seller: seller.clone(),
     };
     store_ask(deps.storage, &ask)?;
+    ask_updated().save(deps.storage, key, &env.block.time)?;
 
     let hook = prepare_ask_hook(deps.storage, &ask, HookAction::Create)?;
 
@@ -112,7 +122,8 @@ pub fn execute_remove_ask(
 
     let key = ask_key(token_id);
     let ask = asks().load(deps.storage, key.clone())?;
-    asks().remove(deps.storage, key)?;
+    asks().remove(deps.storage, key.clone())?;
+    PRIVATE_SALES.remove(deps.storage, &key);
 
     let hook = prepare_ask_hook(deps.storage, &ask, HookAction::Delete)?;
     let event = Event::new("remove-ask").add_attribute("token_id", token_id);
@@ -122,6 +133,9 @@ pub fn execute_remove_ask(
 
 /// When an NFT is transferred, the `ask` has to be updated with the new
 /// seller. Also any existing bids must begin the checked bid removal process to refund bidders.
+/// Unlike listing, ask updates are not rate limited: the ask's seller is the only one who can
+/// accept sales and is paid by them, so the ask must follow the token on every transfer. Rejecting
+/// the update would block transfers and sales, and deferring it would pay a previous holder.
 pub fn execute_update_ask(
     deps: DepsMut,
     info: MessageInfo,
@@ -133,10 +147,14 @@ pub fn execute_update_ask(
         return Err(ContractError::Unauthorized {});
     }
 
+    let key = ask_key(token_id);
+
     // refund any renewal funds and update the seller
-    let mut ask = asks().load(deps.storage, ask_key(token_id))?;
+    let mut ask = asks().load(deps.storage, key.clone())?;
     ask.seller = seller.clone();
-    asks().save(deps.storage, ask_key(token_id), &ask)?;
+    asks().save(deps.storage, key.clone(), &ask)?;
+    // private sales were offered by the previous owner
+    PRIVATE_SALES.remove(deps.storage, &key);
 
     let mut res = Response::new().add_event(
         Event::new("update-ask")
@@ -157,12 +175,15 @@ pub fn execute_update_ask(
 }
 
 /// Places a bid on a account. The bid is escrowed in the contract.
+/// Bidders may opt to skip the cooldown period once their bid is accepted.
 pub fn execute_set_bid(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     token_id: &str,
+    waive_cooldown: bool,
 ) -> Result<Response, ContractError> {
+    ensure_active(deps.storage)?;
     let params = SUDO_PARAMS.load(deps.storage)?;
 
     let ask_key = ask_key(token_id);
@@ -174,11 +195,21 @@ pub fn execute_set_bid(
     }
 
     let bidder = info.sender;
+    run_validation_hooks(
+        deps.as_ref(),
+        token_id,
+        &bidder,
+        bid_price,
+        ValidationAction::SetBid,
+    )?;
     let mut res = Response::new();
     let bid_key = bid_key(token_id, &bidder);
 
     if let Some(existing_bid) = bids().may_load(deps.storage, bid_key.clone())? {
-        bids().remove(deps.storage, bid_key)?;
+        bids().remove(deps.storage, bid_key.clone())?;
+        // counter offers were made against the previous bid amount
+        COUNTER_OFFERS.remove(deps.storage, bid_key);
+        release_escrow(deps.storage, existing_bid.amount)?;
         let refund_bidder = BankMsg::Send {
             to_address: bidder.to_string(),
             amount: vec![coin(existing_bid.amount.u128(), NATIVE_DENOM)],
@@ -186,21 +217,27 @@ pub fn execute_set_bid(
         res = res.add_message(refund_bidder)
     }
 
-    let bid = Bid::new(token_id, bidder.clone(), bid_price, env.block.time);
+    let bid = Bid {
+        waive_cooldown,
+        ..Bid::new(token_id, bidder.clone(), bid_price, env.block.time)
+    };
     store_bid(deps.storage, &bid)?;
+    add_escrow(deps.storage, bid_price)?;
 
     let hook = prepare_bid_hook(deps.storage, &bid.clone(), HookAction::Create)?;
 
     let event = Event::new("set-bid")
         .add_attribute("token_id", token_id)
         .add_attribute("bidder", bidder)
-        .add_attribute("bid_price", bid_price.to_string());
+        .add_attribute("bid_price", bid_price.to_string())
+        .add_attribute("waive_cooldown", waive_cooldown.to_string());
 
     Ok(res.add_event(event).add_submessages(hook))
 }
 
 /// Cancels an accepted bid in cooldown period. Only seller may call.
-/// Requires seller to provide fee, which is split between bidder and developemnt team.
+/// Requires seller to provide the fee of the tier the sale was accepted in, which is split
+/// between bidder and developemnt team.
 pub fn execute_cancel_cooldown(
     deps: DepsMut,
     env: Env,
@@ -214,27 +251,27 @@ pub fn execute_cancel_cooldown(
             if info.sender != p.ask.seller {
                 return Err(ContractError::Unauthorized {});
             };
+            // cannot cancel if cooldown period is over
+            if env.block.time >= p.unlock_time {
+                return Err(ContractError::InvalidDuration {});
+            }
+
             let mut res = Response::default();
-            let params = SUDO_PARAMS.load(deps.storage)?;
-            if params.cooldown_duration != 0 {
+            // the fee of the tier the sale was accepted in, whatever the tiers are now
+            let cooldown_fee = p.cancel_fee.clone().unwrap_or_default();
+            if !cooldown_fee.amount.is_zero() {
                 // must have sent cancel cooldown fee
                 let payment = must_pay(&info, NATIVE_DENOM)?;
 
-                if payment != params.cooldown_fee.amount {
+                if payment != cooldown_fee.amount {
                     return Err(ContractError::IncorrectPayment {
                         got: payment.u128(),
-                        expected: params.cooldown_fee.amount.u128(),
+                        expected: cooldown_fee.amount.u128(),
                     });
                 }
 
-                // cannot cancel if cooldown period is over
-                if env.block.time >= p.unlock_time {
-                    return Err(ContractError::InvalidDuration {});
-                }
-
                 // refund bidder
-                let dev_cut = params
-                    .cooldown_fee
+                let dev_cut = cooldown_fee
                     .amount
                     .u128()
                     .checked_div(2)
@@ -248,7 +285,7 @@ pub fn execute_cancel_cooldown(
                 let seller_share_msg = BankMsg::Send {
                     to_address: p.new_owner.to_string(),
                     amount: vec![coin(
-                        p.amount.u128() + (params.cooldown_fee.amount.u128() - dev_cut),
+                        p.amount.u128() + (cooldown_fee.amount.u128() - dev_cut),
                         NATIVE_DENOM.to_string(),
                     )],
                 };
@@ -256,7 +293,15 @@ pub fn execute_cancel_cooldown(
                     SubMsg::new(seller_share_msg),
                     SubMsg::new(dev_cut_msg),
                 ]);
+            } else {
+                nonpayable(&info)?;
+                // refund bidder
+                res.messages.push(SubMsg::new(BankMsg::Send {
+                    to_address: p.new_owner.to_string(),
+                    amount: vec![coin(p.amount.u128(), NATIVE_DENOM.to_string())],
+                }));
             }
+            release_escrow(deps.storage, p.amount)?;
             COOLDOWN_BID.remove(deps.storage, cd_key);
             Ok(res)
         }
@@ -326,7 +371,9 @@ pub fn execute_remove_bid(
 
     let key = bid_key(token_id, &bidder);
     let bid = bids().load(deps.storage, key.clone())?;
-    bids().remove(deps.storage, key)?;
+    bids().remove(deps.storage, key.clone())?;
+    COUNTER_OFFERS.remove(deps.storage, key);
+    release_escrow(deps.storage, bid.amount)?;
 
     let refund_bidder_msg = BankMsg::Send {
         to_address: bid.bidder.to_string(),
@@ -352,82 +399,18 @@ pub fn execute_finalize_bid(
     env: Env,
     token_id: &str,
 ) -> Result<Response, ContractError> {
-    let collection = ACCOUNT_COLLECTION.load(deps.storage)?;
+    ensure_active(deps.storage)?;
     let cd_key = &ask_key(token_id);
     let pending = COOLDOWN_BID.may_load(deps.storage, cd_key)?;
     let mut res = Response::default();
     match pending {
-        Some(mut p) => {
+        Some(p) => {
             // check if pending bid is ready to be finalized
             if env.block.time < p.unlock_time {
                 return Err(ContractError::InvalidDuration {});
             }
-            // Check if token is approved for transfer
-            let ops = Bs721Account(collection.clone()).approval(
-                &deps.querier,
-                token_id,
-                &env.contract.address.to_string(),
-                None,
-            );
-            if ops.is_err() || ops?.approval.expires.is_expired(&env.block) {
-                // market automatically approves msg for itself
-                res.messages
-                    .push(SubMsg::new(Bs721Account(collection.clone()).call(
-                        Bs721AccountExecuteMsg::ApproveAllViaMarket {
-                            owner: p.ask.seller.to_string(),
-                            expires: None,
-                        },
-                    )?));
-            };
-
-            let token: NftInfoResponse<Metadata> =
-                Bs721Account(collection.clone()).nft_info(&deps.querier, token_id)?;
-
-            // get the associated abstract account we expect to exist
-            if token.extension.account_ownership
-                && validate_aa_ownership(
-                    deps.as_ref(),
-                    &token.token_uri.expect(
-                        "should never have aa support enabled and not have account associated",
-                    ),
-                    token_id,
-                    &collection.clone(),
-                    true,
-                )
-                .is_err()
-            {
-                // abstract account does not use this token for its ownership method.
-                // we refund the bidder by setting their address in the current ask, and still transfer the token to the bidder.
-                // this penalizes the original owner for changing ownership of their account during the cooldown phase.
-                p.ask.seller = p.new_owner.clone();
-            }
-
-            // Transfer funds and NFT
-            finalize_sale(
-                deps.as_ref(),
-                p.ask.clone(),
-                p.amount,
-                p.new_owner.clone(),
-                &mut res,
-            )?;
             COOLDOWN_BID.remove(deps.storage, cd_key);
-
-            let bid_to_remove = bids()
-                .idx
-                .price
-                .sub_prefix(token_id.to_string()) // This matches (token_id, _)
-                .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-                .collect::<Result<Vec<BidKey>, _>>()?;
-
-            checked_bid_removal(deps.storage, bid_to_remove, token_id, &mut res)?;
-            store_ask(
-                deps.storage,
-                &Ask {
-                    token_id: token_id.to_string(),
-                    id: p.ask.id,
-                    seller: p.new_owner.clone(),
-                },
-            )?;
+            settle_sale(deps, &env, p.ask, p.new_owner, p.amount, &mut res)?;
         }
         None => {
             return Err(ContractError::AskNotFound {});
@@ -435,8 +418,66 @@ pub fn execute_finalize_bid(
     }
     Ok(res)
 }
+
+/// Transfers funds and NFT for an accepted bid, refunds any remaining bids,
+/// and updates the ask to the new owner.
+fn settle_sale(
+    deps: DepsMut,
+    env: &Env,
+    mut ask: Ask,
+    new_owner: Addr,
+    amount: Uint128,
+    res: &mut Response,
+) -> Result<(), ContractError> {
+    let collection = ACCOUNT_COLLECTION.load(deps.storage)?;
+    let token_id = ask.token_id.clone();
+
+    // Check if token is approved for transfer
+    let ops = Bs721Account(collection.clone()).approval(
+        &deps.querier,
+        &token_id,
+        &env.contract.address.to_string(),
+        None,
+    );
+    if ops.is_err() || ops?.approval.expires.is_expired(&env.block) {
+        // market automatically approves msg for itself
+        res.messages
+            .push(SubMsg::new(Bs721Account(collection.clone()).call(
+                Bs721AccountExecuteMsg::ApproveAllViaMarket {
+                    owner: ask.seller.to_string(),
+                    expires: None,
+                },
+            )?));
+    };
+
+    if !aa_ownership_intact(deps.as_ref(), &collection, &token_id)? {
+        // abstract account does not use this token for its ownership method.
+        // we refund the bidder by setting their address in the current ask, and still transfer the token to the bidder.
+        // this penalizes the original owner for changing ownership of their account during the cooldown phase.
+        ask.seller = new_owner.clone();
+    }
+
+    // Transfer funds and NFT
+    finalize_sale(deps.as_ref(), ask.clone(), amount, new_owner.clone(), res)?;
+    release_escrow(deps.storage, amount)?;
+    record_sale(deps.storage, env, &ask, amount, &new_owner)?;
+
+    let bid_to_remove = token_bid_keys(deps.storage, &token_id)?;
+    checked_bid_removal(deps.storage, bid_to_remove, &token_id, res)?;
+    PRIVATE_SALES.remove(deps.storage, &ask_key(&token_id));
+    store_ask(
+        deps.storage,
+        &Ask {
+            token_id,
+            id: ask.id,
+            seller: new_owner,
+        },
+    )?;
+    Ok(())
+}
 /// Seller can accept a bid which transfers funds as well as the token.
 /// The bid is removed, then a new ask is created for the same token.
+/// Unless the bidder waived the cooldown, the sale is escrowed until the cooldown period is over.
 pub fn execute_accept_bid(
     deps: DepsMut,
     env: Env,
@@ -444,17 +485,25 @@ pub fn execute_accept_bid(
     token_id: &str,
     bidder: Addr,
 ) -> Result<Response, ContractError> {
+    ensure_active(deps.storage)?;
     // println!("1.0 execute_accept bid ----------------------------");
     nonpayable(&info)?;
     let collection = ACCOUNT_COLLECTION.load(deps.storage)?;
-    let cooldown = SUDO_PARAMS.load(deps.storage)?.cooldown_duration;
     only_owner(deps.as_ref(), &info, &collection, token_id)?;
+    ensure_not_locked(deps.as_ref(), &collection, token_id)?;
 
     let ask_key = ask_key(token_id);
     let bid_key = bid_key(token_id, &bidder);
 
     let ask = asks().load(deps.storage, ask_key.clone())?;
     let bid = bids().load(deps.storage, bid_key.clone())?;
+    run_validation_hooks(
+        deps.as_ref(),
+        token_id,
+        &bidder,
+        bid.amount,
+        ValidationAction::AcceptBid,
+    )?;
 
     // Check if token is approved for transfer
     Bs721Account(collection.clone()).approval(
@@ -465,28 +514,648 @@ pub fn execute_accept_bid(
     )?;
     let mut res = Response::default();
     // Remove accepted bid
-    bids().remove(deps.storage, bid_key)?;
-    let bid_to_remove = bids()
-        .idx
-        .price
-        .sub_prefix(token_id.to_string()) // This matches (token_id, _)
-        .keys(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-        .collect::<Result<Vec<BidKey>, _>>()?;
-    checked_bid_removal(deps.storage, bid_to_remove, token_id, &mut res)?;
-
-    // begin cooldown period
-    let unlock_time = env.block.time.plus_seconds(cooldown);
-    let pending = PendingBid::new(ask.clone(), bidder.clone(), bid.amount, unlock_time);
-    COOLDOWN_BID.save(deps.storage, &ask_key, &pending)?;
+    bids().remove(deps.storage, bid_key.clone())?;
+    COUNTER_OFFERS.remove(deps.storage, bid_key);
+    if bid.waive_cooldown {
+        ensure_no_cooldown(deps.storage, token_id)?;
+        settle_sale(deps, &env, ask, bidder.clone(), bid.amount, &mut res)?;
+    } else {
+        begin_cooldown(
+            deps.storage,
+            &env,
+            ask,
+            bidder.clone(),
+            bid.amount,
+            &mut res,
+        )?;
+    }
 
     Ok(res.add_event(
         Event::new("accept-bid")
             .add_attribute("token_id", token_id)
             .add_attribute("bidder", bidder)
-            .add_attribute("price", bid.amount.to_string()),
+            .add_attribute("price", bid.amount.to_string())
+            .add_attribute("waive_cooldown", bid.waive_cooldown.to_string()),
+    ))
+}
+
+/// Token owner counters an existing bid with a higher price.
+/// Replaces any previous counter offer made on the same bid.
+pub fn execute_counter_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: &str,
+    bidder: Addr,
+    price: Uint128,
+    expires: Timestamp,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let collection = ACCOUNT_COLLECTION.load(deps.storage)?;
+    only_owner(deps.as_ref(), &info, &collection, token_id)?;
+    ensure_no_cooldown(deps.storage, token_id)?;
+
+    let bid_key = bid_key(token_id, &bidder);
+    let bid = bids().load(deps.storage, bid_key.clone())?;
+    if price <= bid.amount {
+        return Err(ContractError::PriceTooSmall(price));
+    }
+    if expires <= env.block.time {
+        return Err(ContractError::InvalidDuration {});
+    }
+
+    let offer = CounterOffer {
+        token_id: token_id.to_string(),
+        seller: info.sender.clone(),
+        bidder: bidder.clone(),
+        price,
+        expires,
+    };
+    COUNTER_OFFERS.save(deps.storage, bid_key, &offer)?;
+
+    Ok(Response::new().add_event(
+        Event::new("counter-offer")
+            .add_attribute("token_id", token_id)
+            .add_attribute("bidder", bidder)
+            .add_attribute("price", price.to_string())
+            .add_attribute("expires", expires.to_string()),
     ))
 }
 
+/// Bidder accepts a counter offer by paying the difference to their existing bid.
+/// The sale then enters the cooldown period like an accepted bid.
+pub fn execute_accept_counter_offer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: &str,
+) -> Result<Response, ContractError> {
+    ensure_active(deps.storage)?;
+    let bidder = info.sender.clone();
+    let bid_key = bid_key(token_id, &bidder);
+    let offer = COUNTER_OFFERS.load(deps.storage, bid_key.clone())?;
+    if env.block.time >= offer.expires {
+        return Err(ContractError::CounterOfferExpired {});
+    }
+
+    // counter offer is only valid while the seller still owns the token
+    let ask = asks().load(deps.storage, ask_key(token_id))?;
+    if ask.seller != offer.seller {
+        return Err(ContractError::Unauthorized {});
+    }
+    ensure_no_cooldown(deps.storage, token_id)?;
+    ensure_not_locked(
+        deps.as_ref(),
+        &ACCOUNT_COLLECTION.load(deps.storage)?,
+        token_id,
+    )?;
+    run_validation_hooks(
+        deps.as_ref(),
+        token_id,
+        &bidder,
+        offer.price,
+        ValidationAction::AcceptBid,
+    )?;
+
+    let bid = bids().load(deps.storage, bid_key.clone())?;
+    let top_up = offer.price.checked_sub(bid.amount)?;
+    let payment = must_pay(&info, NATIVE_DENOM)?;
+    if payment != top_up {
+        return Err(ContractError::IncorrectPayment {
+            got: payment.u128(),
+            expected: top_up.u128(),
+        });
+    }
+    add_escrow(deps.storage, top_up)?;
+
+    bids().remove(deps.storage, bid_key.clone())?;
+    COUNTER_OFFERS.remove(deps.storage, bid_key);
+
+    let mut res = Response::new();
+    let bid = Bid::new(token_id, bidder.clone(), offer.price, env.block.time);
+    res.messages
+        .extend(prepare_bid_hook(deps.storage, &bid, HookAction::Update)?);
+    begin_cooldown(
+        deps.storage,
+        &env,
+        ask,
+        bidder.clone(),
+        offer.price,
+        &mut res,
+    )?;
+
+    Ok(res.add_event(
+        Event::new("accept-counter-offer")
+            .add_attribute("token_id", token_id)
+            .add_attribute("bidder", bidder)
+            .add_attribute("price", offer.price.to_string()),
+    ))
+}
+
+/// Token owner offers their account to a single buyer at a fixed price.
+pub fn execute_private_sale(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: &str,
+    buyer: Addr,
+    price: Uint128,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let collection = ACCOUNT_COLLECTION.load(deps.storage)?;
+    only_owner(deps.as_ref(), &info, &collection, token_id)?;
+    ensure_no_cooldown(deps.storage, token_id)?;
+    asks().load(deps.storage, ask_key(token_id))?;
+
+    if price < SUDO_PARAMS.load(deps.storage)?.min_price {
+        return Err(ContractError::PriceTooSmall(price));
+    }
+    if buyer == info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let sale = PrivateSale {
+        token_id: token_id.to_string(),
+        seller: info.sender,
+        buyer: buyer.clone(),
+        price,
+    };
+    PRIVATE_SALES.save(deps.storage, &ask_key(token_id), &sale)?;
+
+    Ok(Response::new().add_event(
+        Event::new("private-sale")
+            .add_attribute("token_id", token_id)
+            .add_attribute("buyer", buyer)
+            .add_attribute("price", price.to_string()),
+    ))
+}
+
+/// Removes a private sale offer. Only the seller that created it may call.
+pub fn execute_cancel_private_sale(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: &str,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let sale = PRIVATE_SALES.load(deps.storage, &ask_key(token_id))?;
+    if sale.seller != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    PRIVATE_SALES.remove(deps.storage, &ask_key(token_id));
+
+    Ok(Response::new().add_event(
+        Event::new("cancel-private-sale")
+            .add_attribute("token_id", token_id)
+            .add_attribute("buyer", sale.buyer),
+    ))
+}
+
+/// Buyer accepts a private sale by escrowing the sale price.
+/// The sale then enters the cooldown period like an accepted bid.
+pub fn execute_accept_private_sale(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: &str,
+) -> Result<Response, ContractError> {
+    ensure_active(deps.storage)?;
+    let sale = PRIVATE_SALES.load(deps.storage, &ask_key(token_id))?;
+    if sale.buyer != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // private sale is only valid while the seller still owns the token
+    let ask = asks().load(deps.storage, ask_key(token_id))?;
+    if ask.seller != sale.seller {
+        return Err(ContractError::Unauthorized {});
+    }
+    ensure_no_cooldown(deps.storage, token_id)?;
+    ensure_not_locked(
+        deps.as_ref(),
+        &ACCOUNT_COLLECTION.load(deps.storage)?,
+        token_id,
+    )?;
+    run_validation_hooks(
+        deps.as_ref(),
+        token_id,
+        &sale.buyer,
+        sale.price,
+        ValidationAction::AcceptBid,
+    )?;
+
+    let payment = must_pay(&info, NATIVE_DENOM)?;
+    if payment != sale.price {
+        return Err(ContractError::IncorrectPayment {
+            got: payment.u128(),
+            expected: sale.price.u128(),
+        });
+    }
+    PRIVATE_SALES.remove(deps.storage, &ask_key(token_id));
+    add_escrow(deps.storage, sale.price)?;
+
+    // any existing bid from the buyer is refunded along with the others
+    let mut res = Response::new();
+    let bid = Bid::new(token_id, sale.buyer.clone(), sale.price, env.block.time);
+    res.messages
+        .extend(prepare_bid_hook(deps.storage, &bid, HookAction::Create)?);
+    begin_cooldown(
+        deps.storage,
+        &env,
+        ask,
+        sale.buyer.clone(),
+        sale.price,
+        &mut res,
+    )?;
+
+    Ok(res.add_event(
+        Event::new("accept-private-sale")
+            .add_attribute("token_id", token_id)
+            .add_attribute("buyer", sale.buyer)
+            .add_attribute("price", sale.price.to_string()),
+    ))
+}
+
+/// Places a single bid on several accounts listed by the same seller.
+/// The bid is escrowed in the contract.
+pub fn execute_set_bundle_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_ids: Vec<TokenId>,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    ensure_active(deps.storage)?;
+    let params = SUDO_PARAMS.load(deps.storage)?;
+
+    let payment = must_pay(&info, NATIVE_DENOM)?;
+    if payment != amount {
+        return Err(ContractError::IncorrectPayment {
+            got: payment.u128(),
+            expected: amount.u128(),
+        });
+    }
+    if amount < params.min_price {
+        return Err(ContractError::PriceTooSmall(amount));
+    }
+    if token_ids.len() < 2 || token_ids.len() > MAX_BUNDLE_SIZE {
+        return Err(ContractError::InvalidBundle(format!(
+            "must contain between 2 and {} accounts",
+            MAX_BUNDLE_SIZE
+        )));
+    }
+
+    let seller = asks().load(deps.storage, ask_key(&token_ids[0]))?.seller;
+    for (i, token_id) in token_ids.iter().enumerate() {
+        if token_ids[..i].contains(token_id) {
+            return Err(ContractError::InvalidBundle(format!(
+                "duplicate account {}",
+                token_id
+            )));
+        }
+        if asks().load(deps.storage, ask_key(token_id))?.seller != seller {
+            return Err(ContractError::InvalidBundle(
+                "accounts must be listed by the same seller".to_string(),
+            ));
+        }
+    }
+    if seller == info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let bundle = BundleBid {
+        id: increment_bundles(deps.storage)?,
+        token_ids,
+        seller,
+        bidder: info.sender,
+        amount,
+        created_time: env.block.time,
+    };
+    BUNDLE_BIDS.save(deps.storage, bundle.id, &bundle)?;
+    for token_id in &bundle.token_ids {
+        TOKEN_BUNDLES.save(deps.storage, (token_id, bundle.id), &true)?;
+    }
+    add_escrow(deps.storage, amount)?;
+
+    Ok(Response::new().add_event(
+        Event::new("set-bundle-bid")
+            .add_attribute("id", bundle.id.to_string())
+            .add_attribute("token_ids", bundle.token_ids.join(","))
+            .add_attribute("bidder", bundle.bidder)
+            .add_attribute("amount", amount.to_string()),
+    ))
+}
+
+/// Removes a bundle bid and refunds the bidder. Only the bidder may call.
+pub fn execute_remove_bundle_bid(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    let bundle = BUNDLE_BIDS.load(deps.storage, id)?;
+    if bundle.bidder != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    remove_bundle_bid(deps.storage, &bundle);
+    release_escrow(deps.storage, bundle.amount)?;
+
+    let refund_bidder_msg = BankMsg::Send {
+        to_address: bundle.bidder.to_string(),
+        amount: vec![coin(bundle.amount.u128(), NATIVE_DENOM)],
+    };
+
+    Ok(Response::new().add_message(refund_bidder_msg).add_event(
+        Event::new("remove-bundle-bid")
+            .add_attribute("id", id.to_string())
+            .add_attribute("bidder", bundle.bidder),
+    ))
+}
+
+/// Seller accepts a bundle bid. Every account in the bundle has its remaining bids refunded,
+/// and enters a single cooldown period that is finalized for all accounts at once.
+pub fn execute_accept_bundle_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    ensure_active(deps.storage)?;
+    nonpayable(&info)?;
+    let collection = ACCOUNT_COLLECTION.load(deps.storage)?;
+    let bundle = BUNDLE_BIDS.load(deps.storage, id)?;
+    let (cooldown, _) = SUDO_PARAMS.load(deps.storage)?.cooldown_for(bundle.amount);
+    if bundle.seller != info.sender {
+        return Err(ContractError::UnauthorizedOwner {});
+    }
+
+    let mut res = Response::default();
+    for token_id in &bundle.token_ids {
+        only_owner(deps.as_ref(), &info, &collection, token_id)?;
+        // bundle is only valid while the seller still has every account listed
+        if asks().load(deps.storage, ask_key(token_id))?.seller != bundle.seller {
+            return Err(ContractError::Unauthorized {});
+        }
+        ensure_no_cooldown(deps.storage, token_id)?;
+        ensure_not_locked(deps.as_ref(), &collection, token_id)?;
+
+        let bid_to_remove = token_bid_keys(deps.storage, token_id)?;
+        checked_bid_removal(deps.storage, bid_to_remove, token_id, &mut res)?;
+        BUNDLE_COOLDOWN_TOKENS.save(deps.storage, token_id, &id)?;
+    }
+    remove_bundle_bid(deps.storage, &bundle);
+
+    let unlock_time = env.block.time.plus_seconds(cooldown);
+    BUNDLE_COOLDOWN.save(
+        deps.storage,
+        id,
+        &PendingBundle {
+            bundle: bundle.clone(),
+            unlock_time,
+            cooldown,
+        },
+    )?;
+
+    Ok(res.add_event(
+        Event::new("accept-bundle-bid")
+            .add_attribute("id", id.to_string())
+            .add_attribute("bidder", bundle.bidder)
+            .add_attribute("price", bundle.amount.to_string()),
+    ))
+}
+
+/// Finalizes an accepted bundle once its cooldown period is complete.
+/// All transfers are dispatched in the same response, so either every account
+/// in the bundle changes hands or the whole transaction reverts.
+pub fn execute_finalize_bundle_bid(
+    deps: DepsMut,
+    env: Env,
+    id: u64,
+) -> Result<Response, ContractError> {
+    ensure_active(deps.storage)?;
+    let collection = ACCOUNT_COLLECTION.load(deps.storage)?;
+    let pending = BUNDLE_COOLDOWN
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::AskNotFound {})?;
+    if env.block.time < pending.unlock_time {
+        return Err(ContractError::InvalidDuration {});
+    }
+    let bundle = pending.bundle;
+    let mut res = Response::default();
+
+    // market automatically approves msg for itself if any token is not approved
+    let needs_approval = bundle.token_ids.iter().any(|token_id| {
+        Bs721Account(collection.clone())
+            .approval(
+                &deps.querier,
+                token_id,
+                &env.contract.address.to_string(),
+                None,
+            )
+            .map_or(true, |ops| ops.approval.expires.is_expired(&env.block))
+    });
+    if needs_approval {
+        res.messages
+            .push(SubMsg::new(Bs721Account(collection.clone()).call(
+                Bs721AccountExecuteMsg::ApproveAllViaMarket {
+                    owner: bundle.seller.to_string(),
+                    expires: None,
+                },
+            )?));
+    }
+
+    // split the payment evenly, with any remainder going to the first account
+    let count = bundle.token_ids.len() as u128;
+    let share = Uint128::from(bundle.amount.u128() / count);
+    let remainder = Uint128::from(bundle.amount.u128() % count);
+
+    for (i, token_id) in bundle.token_ids.iter().enumerate() {
+        let price = if i == 0 { share + remainder } else { share };
+        let mut ask = asks().load(deps.storage, ask_key(token_id))?;
+        if !aa_ownership_intact(deps.as_ref(), &collection, token_id)? {
+            // same penalty as a single sale: the bidder is refunded this accounts share
+            ask.seller = bundle.bidder.clone();
+        }
+
+        finalize_sale(
+            deps.as_ref(),
+            ask.clone(),
+            price,
+            bundle.bidder.clone(),
+            &mut res,
+        )?;
+        record_sale(deps.storage, &env, &ask, price, &bundle.bidder)?;
+        BUNDLE_COOLDOWN_TOKENS.remove(deps.storage, token_id);
+
+        let bid_to_remove = token_bid_keys(deps.storage, token_id)?;
+        checked_bid_removal(deps.storage, bid_to_remove, token_id, &mut res)?;
+        store_ask(
+            deps.storage,
+            &Ask {
+                token_id: token_id.to_string(),
+                id: ask.id,
+                seller: bundle.bidder.clone(),
+            },
+        )?;
+    }
+    BUNDLE_COOLDOWN.remove(deps.storage, id);
+    release_escrow(deps.storage, bundle.amount)?;
+
+    Ok(res.add_event(
+        Event::new("finalize-bundle-bid")
+            .add_attribute("id", id.to_string())
+            .add_attribute("buyer", bundle.bidder)
+            .add_attribute("price", bundle.amount.to_string()),
+    ))
+}
+
+fn remove_bundle_bid(storage: &mut dyn Storage, bundle: &BundleBid) {
+    BUNDLE_BIDS.remove(storage, bundle.id);
+    for token_id in &bundle.token_ids {
+        TOKEN_BUNDLES.remove(storage, (token_id, bundle.id));
+    }
+}
+
+/// Returns false if a token has abstract-account ownership enabled,
+/// but the associated account no longer uses the token for its ownership,
+/// or no account is associated at all.
+fn aa_ownership_intact(deps: Deps, collection: &Addr, token_id: &str) -> StdResult<bool> {
+    let token: NftInfoResponse<Metadata> =
+        Bs721Account(collection.clone()).nft_info(&deps.querier, token_id)?;
+
+    if !token.extension.account_ownership {
+        return Ok(true);
+    }
+    let Some(account) = token.token_uri else {
+        return Ok(false);
+    };
+    Ok(validate_aa_ownership(deps, &account, token_id, collection, true).is_ok())
+}
+
+/// Keys of all bids placed on a token
+fn token_bid_keys(storage: &dyn Storage, token_id: &str) -> StdResult<Vec<BidKey>> {
+    bids()
+        .idx
+        .price
+        .sub_prefix(token_id.to_string()) // This matches (token_id, _)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<BidKey>>>()
+}
+
+/// Refunds all remaining bids on a token and escrows the sale until the cooldown period is over.
+fn begin_cooldown(
+    storage: &mut dyn Storage,
+    env: &Env,
+    ask: Ask,
+    new_owner: Addr,
+    amount: Uint128,
+    res: &mut Response,
+) -> Result<PendingBid, ContractError> {
+    ensure_no_cooldown(storage, &ask.token_id)?;
+    let (cooldown, cancel_fee) = SUDO_PARAMS.load(storage)?.cooldown_for(amount);
+
+    let bid_to_remove = token_bid_keys(storage, &ask.token_id)?;
+    checked_bid_removal(storage, bid_to_remove, &ask.token_id, res)?;
+
+    let unlock_time = env.block.time.plus_seconds(cooldown);
+    let pending = PendingBid::new(
+        ask.clone(),
+        new_owner,
+        amount,
+        unlock_time,
+        cooldown,
+        Some(cancel_fee),
+    );
+    COOLDOWN_BID.save(storage, &ask_key(&ask.token_id), &pending)?;
+
+    Ok(pending)
+}
+
+/// Removes listing times that no longer rate limit their seller, oldest first
+fn prune_ask_updated(
+    storage: &mut dyn Storage,
+    now: Timestamp,
+    ask_interval: u64,
+) -> StdResult<()> {
+    let expired = ask_updated()
+        .idx
+        .time
+        .range(storage, None, None, Order::Ascending)
+        .take_while(|item| {
+            item.as_ref()
+                .map_or(true, |(_, time)| time.plus_seconds(ask_interval) <= now)
+        })
+        .take(MAX_ASK_UPDATED_PRUNE)
+        .map(|item| item.map(|(key, _)| key))
+        .collect::<StdResult<Vec<_>>>()?;
+    for key in expired {
+        ask_updated().remove(storage, key)?;
+    }
+    Ok(())
+}
+
+/// New bids and sales are halted while the marketplace is paused
+fn ensure_active(storage: &dyn Storage) -> Result<(), ContractError> {
+    match MARKET_STATUS.may_load(storage)?.unwrap_or_default() {
+        MarketStatus::Active => Ok(()),
+        _ => Err(ContractError::MarketPaused {}),
+    }
+}
+
+/// Asks each validation hook whether a bid may be placed or accepted.
+/// The first hook to deny rejects the bid with its reason. A hook whose query fails rejects
+/// the bid too, since skipping it would let anyone bypass its veto. Broken hooks are removed
+/// by the hooks admin.
+fn run_validation_hooks(
+    deps: Deps,
+    token_id: &str,
+    bidder: &Addr,
+    amount: Uint128,
+    action: ValidationAction,
+) -> Result<(), ContractError> {
+    let msg = ValidationHookQueryMsg::ValidateBid(ValidateBidMsg {
+        token_id: token_id.to_string(),
+        bidder: bidder.to_string(),
+        amount,
+        action,
+    });
+    for hook in VALIDATION_HOOKS.query_hooks(deps)?.hooks {
+        let res: ValidationResponse =
+            deps.querier.query_wasm_smart(&hook, &msg).map_err(|err| {
+                ContractError::ValidationHookFailed {
+                    hook: hook.clone(),
+                    error: err.to_string(),
+                }
+            })?;
+        if let ValidationResponse::Deny { code, reason } = res {
+            return Err(ContractError::BidRejected { hook, code, reason });
+        }
+    }
+    Ok(())
+}
+
+/// A token may only have one accepted sale in escrow at a time, single or bundled
+fn ensure_no_cooldown(storage: &dyn Storage, token_id: &str) -> Result<(), ContractError> {
+    if COOLDOWN_BID.has(storage, &ask_key(token_id))
+        || BUNDLE_COOLDOWN_TOKENS.has(storage, &ask_key(token_id))
+    {
+        return Err(ContractError::CooldownInProgress {});
+    }
+    Ok(())
+}
+
+/// Accounts locked by the minter, e.g. after a successful challenge, can't be sold
+fn ensure_not_locked(deps: Deps, collection: &Addr, token_id: &str) -> Result<(), ContractError> {
+    let locked: bool = deps.querier.query_wasm_smart(
+        collection,
+        &Bs721AccountsQueryMsg::Locked {
+            token_id: token_id.to_string(),
+        },
+    )?;
+    if locked {
+        return Err(ContractError::AccountLocked {});
+    }
+    Ok(())
+}
+
 /// Transfers funds and NFT, updates bid
 fn finalize_sale(
     deps: Deps,
@@ -526,6 +1195,30 @@ fn finalize_sale(
     Ok(())
 }
 
+/// Stores a completed sale for price history.
+/// Sales refunded to the buyer due to an abstract account ownership change are not recorded.
+fn record_sale(
+    storage: &mut dyn Storage,
+    env: &Env,
+    ask: &Ask,
+    price: Uint128,
+    buyer: &Addr,
+) -> StdResult<()> {
+    if ask.seller == *buyer {
+        return Ok(());
+    }
+    let sale = Sale {
+        id: increment_sales(storage)?,
+        token_id: ask.token_id.clone(),
+        price,
+        denom: NATIVE_DENOM.to_string(),
+        seller: ask.seller.clone(),
+        buyer: buyer.clone(),
+        time: env.block.time,
+    };
+    sales().save(storage, sale.id, &sale)
+}
+
 // only process up to MAX_REMOVE_BID_LIMIT in initial bid remove.
 // Any 'overflow bids' stored into map, and upon either:
 //     - new bids being set
@@ -552,9 +1245,15 @@ pub fn checked_bid_removal(
     // refund bidders, call bid hooks
     let mut submsgs = Vec::new();
     for key in process {
-        let bid = bids().load(storage, key.clone())?;
+        // bid may have already been removed or refunded since it was queued
+        let bid = match bids().may_load(storage, key.clone())? {
+            Some(bid) => bid,
+            None => continue,
+        };
         submsgs.extend(prepare_bid_hook(storage, &bid, HookAction::Delete)?);
         bids().remove(storage, key.clone())?;
+        COUNTER_OFFERS.remove(storage, key.clone());
+        release_escrow(storage, bid.amount)?;
         submsgs.push(SubMsg::new(BankMsg::Send {
             to_address: bid.bidder.to_string(),
             amount: vec![coin(bid.amount.u128(), NATIVE_DENOM)],
@@ -743,25 +1442,233 @@ pub fn query_bids(
         .collect::<StdResult<Vec<_>>>()
 }
 
+pub fn query_counter_offers(
+    deps: Deps,
+    token_id: TokenId,
+    start_after: Option<Bidder>,
+    limit: Option<u32>,
+) -> StdResult<Vec<CounterOffer>> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+
+    COUNTER_OFFERS
+        .prefix(token_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, o)| o))
+        .collect::<StdResult<Vec<_>>>()
+}
+
+/// Returns the pending sale of a token, including tokens locked in an accepted bundle.
+pub fn query_cooldown(deps: Deps, token_id: TokenId) -> StdResult<Option<PendingBid>> {
+    let key = ask_key(&token_id);
+    if let Some(pending) = COOLDOWN_BID.may_load(deps.storage, &key)? {
+        return Ok(Some(pending));
+    }
+    let id = match BUNDLE_COOLDOWN_TOKENS.may_load(deps.storage, &key)? {
+        Some(id) => id,
+        None => return Ok(None),
+    };
+    let pending = BUNDLE_COOLDOWN.load(deps.storage, id)?;
+    let ask = asks().load(deps.storage, key)?;
+    Ok(Some(PendingBid::new(
+        ask,
+        pending.bundle.bidder,
+        pending.bundle.amount,
+        pending.unlock_time,
+        pending.cooldown,
+        None,
+    )))
+}
+
+pub fn query_bundle_bids_by_token(
+    deps: Deps,
+    token_id: TokenId,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<BundleBid>> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    TOKEN_BUNDLES
+        .prefix(&token_id)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|id| BUNDLE_BIDS.load(deps.storage, id?))
+        .collect::<StdResult<Vec<_>>>()
+}
+
+/// Compares the escrow owed to bidders against the marketplace balance.
+pub fn query_escrow_summary(deps: Deps, env: Env) -> StdResult<EscrowSummaryResponse> {
+    let tracked = ESCROW_TOTAL.may_load(deps.storage)?.unwrap_or_default();
+    let escrowed = sum_escrow(deps.storage)?;
+    let balance = deps
+        .querier
+        .query_balance(env.contract.address, NATIVE_DENOM)?;
+
+    Ok(EscrowSummaryResponse {
+        solvent: balance.amount >= escrowed.max(tracked),
+        consistent: tracked == escrowed,
+        tracked: coin(tracked.u128(), NATIVE_DENOM),
+        escrowed: coin(escrowed.u128(), NATIVE_DENOM),
+        balance,
+    })
+}
+
+/// Sums every escrowed amount in storage: open bids, bundle bids, and accepted sales in cooldown.
+/// Iterates all entries, so only suitable for migrations and the escrow summary query.
+pub fn sum_escrow(storage: &dyn Storage) -> StdResult<Uint128> {
+    let mut total = Uint128::zero();
+    for item in bids().range(storage, None, None, Order::Ascending) {
+        total += item?.1.amount;
+    }
+    for item in COOLDOWN_BID.range(storage, None, None, Order::Ascending) {
+        total += item?.1.amount;
+    }
+    for item in BUNDLE_BIDS.range(storage, None, None, Order::Ascending) {
+        total += item?.1.amount;
+    }
+    for item in BUNDLE_COOLDOWN.range(storage, None, None, Order::Ascending) {
+        total += item?.1.bundle.amount;
+    }
+    Ok(total)
+}
+
+pub fn query_sales_by_token(
+    deps: Deps,
+    token_id: TokenId,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Sale>> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let end = start_after.map(Bound::exclusive);
+
+    sales()
+        .idx
+        .token_id
+        .prefix(token_id)
+        .range(deps.storage, None, end, Order::Descending)
+        .take(limit)
+        .map(|item| item.map(|(_, s)| s))
+        .collect::<StdResult<Vec<_>>>()
+}
+
+pub fn query_recent_sales(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Sale>> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let end = start_after.map(Bound::exclusive);
+
+    sales()
+        .range(deps.storage, None, end, Order::Descending)
+        .take(limit)
+        .map(|item| item.map(|(_, s)| s))
+        .collect::<StdResult<Vec<_>>>()
+}
+
+/// Lowest price among the last `FLOOR_PRICE_SAMPLE` sales of accounts with `length` characters
+pub fn query_floor_price(deps: Deps, length: u32) -> StdResult<Option<Coin>> {
+    let floor = sales()
+        .idx
+        .length
+        .prefix(length)
+        .range(deps.storage, None, None, Order::Descending)
+        .take(FLOOR_PRICE_SAMPLE)
+        .map(|item| item.map(|(_, s)| s.price))
+        .collect::<StdResult<Vec<_>>>()?
+        .into_iter()
+        .min();
+
+    Ok(floor.map(|price| coin(price.u128(), NATIVE_DENOM)))
+}
+
+pub fn query_last_sale_price(deps: Deps, token_id: TokenId) -> StdResult<Option<Coin>> {
+    Ok(query_sales_by_token(deps, token_id, None, Some(1))?
+        .pop()
+        .map(|sale| coin(sale.price.u128(), &sale.denom)))
+}
+
+/// Runs the checks `SetBid` would, without moving funds.
+/// Rejections by the marketplace itself are returned with code `0`.
+pub fn query_can_bid(
+    deps: Deps,
+    token_id: TokenId,
+    bidder: String,
+    amount: Uint128,
+) -> StdResult<ValidationResponse> {
+    let bidder = deps.api.addr_validate(&bidder)?;
+    let check = || -> Result<(), ContractError> {
+        ensure_active(deps.storage)?;
+        asks().load(deps.storage, ask_key(&token_id))?;
+        if amount < SUDO_PARAMS.load(deps.storage)?.min_price {
+            return Err(ContractError::PriceTooSmall(amount));
+        }
+        run_validation_hooks(deps, &token_id, &bidder, amount, ValidationAction::SetBid)
+    };
+
+    Ok(match check() {
+        Ok(()) => ValidationResponse::Allow {},
+        Err(ContractError::BidRejected { code, reason, .. }) => {
+            ValidationResponse::Deny { code, reason }
+        }
+        Err(err) => ValidationResponse::Deny {
+            code: 0,
+            reason: err.to_string(),
+        },
+    })
+}
+
+pub fn query_failed_hooks(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<FailedHook>> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    FAILED_HOOKS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, f)| f))
+        .collect::<StdResult<Vec<_>>>()
+}
+
+pub fn query_private_sale(deps: Deps, token_id: TokenId) -> StdResult<Option<PrivateSale>> {
+    PRIVATE_SALES.may_load(deps.storage, &token_id)
+}
+
+/// Returns the highest valid bid for a token, searching at most `valid_bid_query_limit` bids.
 pub fn query_highest_bid(deps: Deps, token_id: TokenId) -> StdResult<Option<Bid>> {
-    let bid = bids()
+    let limit = SUDO_PARAMS.load(deps.storage)?.valid_bid_query_limit as usize;
+    let seller = asks()
+        .may_load(deps.storage, ask_key(&token_id))?
+        .map(|ask| ask.seller);
+    let pending_refunds = OVERFLOW_BIDS_REMOVE
+        .may_load(deps.storage, token_id.clone())?
+        .unwrap_or_default();
+
+    for item in bids()
         .idx
         .price
+        .sub_prefix(token_id)
         .range(deps.storage, None, None, Order::Descending)
-        .filter_map(|item| {
-            let (key, bid) = item.unwrap();
-            if key.0 == token_id {
-                Some(bid)
-            } else {
-                None
-            }
-        })
-        .take(1)
-        .collect::<Vec<_>>()
-        .first()
-        .cloned();
+        .take(limit)
+    {
+        let (_, bid) = item?;
+        if is_valid_bid(&bid, seller.as_ref(), &pending_refunds) {
+            return Ok(Some(bid));
+        }
+    }
+
+    Ok(None)
+}
 
-    Ok(bid)
+/// A bid no longer has valid escrow if it is queued to be refunded,
+/// or if the bidder has since become the seller of the token.
+fn is_valid_bid(bid: &Bid, seller: Option<&Addr>, pending_refunds: &[BidKey]) -> bool {
+    seller != Some(&bid.bidder) && !pending_refunds.contains(&bid_key(&bid.token_id, &bid.bidder))
 }
 
 pub fn query_bids_sorted_by_price(
@@ -826,12 +1733,18 @@ pub fn sudo_update_params(
         ask_interval,
         cooldown_duration,
         cooldown_cancel_fee,
+        cooldown_tiers,
     } = param_info;
     if let Some(trading_fee_bps) = trading_fee_bps {
         if trading_fee_bps > MAX_FEE_BPS {
             return Err(ContractError::InvalidTradingFeeBps(trading_fee_bps));
         }
     }
+    if let Some(tiers) = &cooldown_tiers {
+        if tiers.windows(2).any(|w| w[0].min_price >= w[1].min_price) {
+            return Err(ContractError::InvalidCooldownTiers {});
+        }
+    }
 
     let mut params = SUDO_PARAMS.load(deps.storage)?;
 
@@ -843,6 +1756,7 @@ pub fn sudo_update_params(
     params.ask_interval = ask_interval.unwrap_or(params.ask_interval);
     params.cooldown_duration = cooldown_duration.unwrap_or(params.cooldown_duration);
     params.cooldown_fee = cooldown_cancel_fee.unwrap_or(params.cooldown_fee);
+    params.cooldown_tiers = cooldown_tiers.unwrap_or(params.cooldown_tiers);
 
     SUDO_PARAMS.save(deps.storage, &params)?;
 
@@ -874,6 +1788,124 @@ pub fn sudo_update_account_collection(
     let event = Event::new("update-account-collection").add_attribute("collection", collection);
     Ok(Response::new().add_event(event))
 }
+pub fn execute_update_status(
+    deps: DepsMut,
+    info: MessageInfo,
+    status: MarketStatus,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    // only hooks admin may invoke
+    if info.sender.to_string() != SUDO_PARAMS.load(deps.storage)?.hooks_admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    update_status(deps, status)
+}
+
+pub fn sudo_update_status(deps: DepsMut, status: MarketStatus) -> Result<Response, ContractError> {
+    update_status(deps, status)
+}
+
+fn update_status(deps: DepsMut, status: MarketStatus) -> Result<Response, ContractError> {
+    MARKET_STATUS.save(deps.storage, &status)?;
+    Ok(Response::new()
+        .add_event(Event::new("update-status").add_attribute("status", format!("{:?}", status))))
+}
+
+/// Releases escrow back to bidders while the marketplace is in emergency mode.
+/// Processes at most `limit` entries per call, in order: open bids, accepted bids in
+/// cooldown, bundle bids, then accepted bundles. No hooks are called.
+pub fn execute_refund_escrow(
+    deps: DepsMut,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    if MARKET_STATUS.may_load(deps.storage)?.unwrap_or_default() != MarketStatus::Emergency {
+        return Err(ContractError::NotInEmergency {});
+    }
+    let limit = limit
+        .unwrap_or(MAX_REMOVE_BID_LIMIT as u32)
+        .min(MAX_QUERY_LIMIT) as usize;
+    let mut refunds: Vec<(Addr, Uint128)> = Vec::new();
+
+    let open_bids = bids()
+        .range(deps.storage, None, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (key, bid) in open_bids {
+        bids().remove(deps.storage, key.clone())?;
+        COUNTER_OFFERS.remove(deps.storage, key);
+        refunds.push((bid.bidder, bid.amount));
+    }
+
+    let pending_bids = COOLDOWN_BID
+        .range(deps.storage, None, None, Order::Ascending)
+        .take(limit - refunds.len())
+        .collect::<StdResult<Vec<_>>>()?;
+    for (token_id, pending) in pending_bids {
+        COOLDOWN_BID.remove(deps.storage, &token_id);
+        refunds.push((pending.new_owner, pending.amount));
+    }
+
+    let bundles = BUNDLE_BIDS
+        .range(deps.storage, None, None, Order::Ascending)
+        .take(limit - refunds.len())
+        .collect::<StdResult<Vec<_>>>()?;
+    for (_, bundle) in bundles {
+        remove_bundle_bid(deps.storage, &bundle);
+        refunds.push((bundle.bidder, bundle.amount));
+    }
+
+    let pending_bundles = BUNDLE_COOLDOWN
+        .range(deps.storage, None, None, Order::Ascending)
+        .take(limit - refunds.len())
+        .collect::<StdResult<Vec<_>>>()?;
+    for (id, pending) in pending_bundles {
+        BUNDLE_COOLDOWN.remove(deps.storage, id);
+        for token_id in &pending.bundle.token_ids {
+            BUNDLE_COOLDOWN_TOKENS.remove(deps.storage, token_id);
+        }
+        refunds.push((pending.bundle.bidder, pending.bundle.amount));
+    }
+
+    let refunded = refunds.len();
+    let total = refunds.iter().map(|(_, amount)| *amount).sum();
+    release_escrow(deps.storage, total)?;
+    let msgs = refunds.into_iter().map(|(bidder, amount)| BankMsg::Send {
+        to_address: bidder.to_string(),
+        amount: vec![coin(amount.u128(), NATIVE_DENOM)],
+    });
+
+    Ok(Response::new().add_messages(msgs).add_event(
+        Event::new("refund-escrow")
+            .add_attribute("refunded", refunded.to_string())
+            .add_attribute("complete", (refunded < limit).to_string()),
+    ))
+}
+
+/// Resends a failed hook call with its original payload.
+/// If it fails again, it is stored under a new id.
+pub fn execute_retry_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    // only hooks admin may invoke
+    if info.sender.to_string() != SUDO_PARAMS.load(deps.storage)?.hooks_admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    let failed = FAILED_HOOKS.load(deps.storage, id)?;
+    FAILED_HOOKS.remove(deps.storage, id);
+
+    let submsg = hook_submsg(&failed.hook, failed.msg, HookReply::from(&failed.kind))?;
+    Ok(Response::new().add_submessage(submsg).add_event(
+        Event::new("retry-hook")
+            .add_attribute("id", id.to_string())
+            .add_attribute("hook", failed.hook),
+    ))
+}
+
 pub fn manage_hooks(
     deps: DepsMut,
     sender: Addr,
@@ -883,6 +1915,79 @@ pub fn manage_hooks(
     if sender.to_string() != SUDO_PARAMS.load(deps.storage)?.hooks_admin {
         return Err(ContractError::UnauthorizedMinter {});
     }
+    if HOOKS_TIMELOCK.delay(deps.storage)? > 0 {
+        return Err(ContractError::TimelockRequired {});
+    }
+    apply_hooks_action(deps, action)
+}
+
+/// Queue a hook change, executable once the admin delay has passed
+pub fn execute_propose_manage_hooks(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: ManageHooksAction,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    // only hooks admin may invoke
+    if info.sender.to_string() != SUDO_PARAMS.load(deps.storage)?.hooks_admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    let queued = HOOKS_TIMELOCK.propose(deps.storage, env.block.time, action)?;
+
+    Ok(Response::new().add_event(
+        Event::new("propose-manage-hooks")
+            .add_attribute("id", queued.id.to_string())
+            .add_attribute("executable_at", queued.executable_at.seconds().to_string()),
+    ))
+}
+
+pub fn execute_queued_manage_hooks(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    // only hooks admin may invoke
+    if info.sender.to_string() != SUDO_PARAMS.load(deps.storage)?.hooks_admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    let queued = HOOKS_TIMELOCK.take(deps.storage, id)?;
+    if !queued.is_ready(&env.block.time) {
+        return Err(ContractError::ActionNotReady {
+            executable_at: queued.executable_at,
+        });
+    }
+
+    let res = apply_hooks_action(deps, queued.action)?;
+    Ok(res.add_event(Event::new("execute-manage-hooks").add_attribute("id", id.to_string())))
+}
+
+pub fn execute_cancel_manage_hooks(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+    // only hooks admin may invoke
+    if info.sender.to_string() != SUDO_PARAMS.load(deps.storage)?.hooks_admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    HOOKS_TIMELOCK.take(deps.storage, id)?;
+
+    Ok(Response::new()
+        .add_event(Event::new("cancel-manage-hooks").add_attribute("id", id.to_string())))
+}
+
+pub fn sudo_update_admin_delay(deps: DepsMut, delay: u64) -> Result<Response, ContractError> {
+    HOOKS_TIMELOCK.set_delay(deps.storage, delay)?;
+
+    Ok(Response::new()
+        .add_event(Event::new("update-admin-delay").add_attribute("delay", delay.to_string())))
+}
+
+fn apply_hooks_action(deps: DepsMut, action: ManageHooksAction) -> Result<Response, ContractError> {
     let mut res = Response::default();
     match action {
         ManageHooksAction::AddSaleHook(hook) => {
@@ -915,6 +2020,16 @@ pub fn manage_hooks(
             res.events
                 .push(Event::new("remove-bid-hook").add_attribute("hook", hook));
         }
+        ManageHooksAction::AddValidationHook(hook) => {
+            VALIDATION_HOOKS.add_hook(deps.storage, deps.api.addr_validate(&hook)?)?;
+            res.events
+                .push(Event::new("add-validation-hook").add_attribute("hook", hook));
+        }
+        ManageHooksAction::RemoveValidationHook(hook) => {
+            VALIDATION_HOOKS.remove_hook(deps.storage, deps.api.addr_validate(&hook)?)?;
+            res.events
+                .push(Event::new("remove-validation-hook").add_attribute("hook", hook));
+        }
     }
     Ok(res)
 }
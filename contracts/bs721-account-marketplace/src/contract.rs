@@ -1,8 +1,10 @@
 use btsg_account::market::MigrateMsg;
 use cosmwasm_std::{
-    to_json_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128
+    to_json_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError,
+    StdResult, Storage, Uint128,
 };
 use cw2::set_contract_version;
+use semver::Version;
 
 use crate::{commands::*, state::*, ContractError};
 use btsg_account::market::{
@@ -32,6 +34,7 @@ pub fn instantiate(
         cooldown_duration: msg.cooldown_timeframe,
         cooldown_fee: msg.cooldown_cancel_fee,
         hooks_admin: msg.hooks_admin.unwrap_or(info.sender.to_string()),
+        cooldown_tiers: vec![],
     };
 
     SUDO_PARAMS.save(deps.storage, &params)?;
@@ -61,7 +64,10 @@ pub fn execute(
         ExecuteMsg::UpdateAsk { token_id, seller } => {
             execute_update_ask(deps, info, &token_id, api.addr_validate(&seller)?)
         }
-        ExecuteMsg::SetBid { token_id } => execute_set_bid(deps, env, info, &token_id),
+        ExecuteMsg::SetBid {
+            token_id,
+            waive_cooldown,
+        } => execute_set_bid(deps, env, info, &token_id, waive_cooldown),
         ExecuteMsg::RemoveBid { token_id } => execute_remove_bid(deps, env, info, &token_id),
         ExecuteMsg::AcceptBid { token_id, bidder } => {
             execute_accept_bid(deps, env, info, &token_id, api.addr_validate(&bidder)?)
@@ -70,16 +76,58 @@ pub fn execute(
         ExecuteMsg::CancelCooldown { token_id } => {
             execute_cancel_cooldown(deps, env, info, &token_id)
         }
+        ExecuteMsg::CounterOffer {
+            token_id,
+            bidder,
+            price,
+            expires,
+        } => execute_counter_offer(
+            deps,
+            env,
+            info,
+            &token_id,
+            api.addr_validate(&bidder)?,
+            price,
+            expires,
+        ),
+        ExecuteMsg::AcceptCounterOffer { token_id } => {
+            execute_accept_counter_offer(deps, env, info, &token_id)
+        }
+        ExecuteMsg::PrivateSale {
+            token_id,
+            buyer,
+            price,
+        } => execute_private_sale(deps, info, &token_id, api.addr_validate(&buyer)?, price),
+        ExecuteMsg::CancelPrivateSale { token_id } => {
+            execute_cancel_private_sale(deps, info, &token_id)
+        }
+        ExecuteMsg::AcceptPrivateSale { token_id } => {
+            execute_accept_private_sale(deps, env, info, &token_id)
+        }
+        ExecuteMsg::SetBundleBid { token_ids, amount } => {
+            execute_set_bundle_bid(deps, env, info, token_ids, amount)
+        }
+        ExecuteMsg::RemoveBundleBid { id } => execute_remove_bundle_bid(deps, info, id),
+        ExecuteMsg::AcceptBundleBid { id } => execute_accept_bundle_bid(deps, env, info, id),
+        ExecuteMsg::FinalizeBundleBid { id } => execute_finalize_bundle_bid(deps, env, id),
+        ExecuteMsg::UpdateStatus { status } => execute_update_status(deps, info, status),
+        ExecuteMsg::RefundEscrow { limit } => execute_refund_escrow(deps, info, limit),
+        ExecuteMsg::RetryHook { id } => execute_retry_hook(deps, info, id),
         ExecuteMsg::RemoveBids { token_id } => execute_remove_bids(deps, env, info, &token_id),
         ExecuteMsg::CheckedRemoveBids { token_id } => {
             execute_removed_overflow_bids(deps, &token_id)
         }
         ExecuteMsg::ManageHooks(action) => manage_hooks(deps, info.sender, action),
+        ExecuteMsg::ProposeManageHooks { action } => {
+            execute_propose_manage_hooks(deps, env, info, action)
+        }
+        ExecuteMsg::ExecuteManageHooks { id } => execute_queued_manage_hooks(deps, env, info, id),
+        ExecuteMsg::CancelManageHooks { id } => execute_cancel_manage_hooks(deps, info, id),
     }
 }
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     let api = deps.api;
 
     match msg {
@@ -142,9 +190,58 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::AskHooks {} => to_json_binary(&ASK_HOOKS.query_hooks(deps)?),
         QueryMsg::BidHooks {} => to_json_binary(&BID_HOOKS.query_hooks(deps)?),
         QueryMsg::SaleHooks {} => to_json_binary(&SALE_HOOKS.query_hooks(deps)?),
+        QueryMsg::ValidationHooks {} => to_json_binary(&VALIDATION_HOOKS.query_hooks(deps)?),
+        QueryMsg::CanBid {
+            token_id,
+            bidder,
+            amount,
+        } => to_json_binary(&query_can_bid(deps, token_id, bidder, amount)?),
         QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
-        QueryMsg::Cooldown { token_id } => {
-            to_json_binary(&COOLDOWN_BID.may_load(deps.storage, &ask_key(&token_id))?)
+        QueryMsg::Cooldown { token_id } => to_json_binary(&query_cooldown(deps, token_id)?),
+        QueryMsg::CounterOffers {
+            token_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query_counter_offers(deps, token_id, start_after, limit)?),
+        QueryMsg::PrivateSaleOffer { token_id } => {
+            to_json_binary(&query_private_sale(deps, token_id)?)
+        }
+        QueryMsg::BundleBid { id } => to_json_binary(&BUNDLE_BIDS.may_load(deps.storage, id)?),
+        QueryMsg::BundleBidsByToken {
+            token_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query_bundle_bids_by_token(
+            deps,
+            token_id,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::BundleCooldown { id } => {
+            to_json_binary(&BUNDLE_COOLDOWN.may_load(deps.storage, id)?)
+        }
+        QueryMsg::EscrowSummary {} => to_json_binary(&query_escrow_summary(deps, env)?),
+        QueryMsg::SalesByToken {
+            token_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query_sales_by_token(deps, token_id, start_after, limit)?),
+        QueryMsg::RecentSales { start_after, limit } => {
+            to_json_binary(&query_recent_sales(deps, start_after, limit)?)
+        }
+        QueryMsg::FloorPrice { length } => to_json_binary(&query_floor_price(deps, length)?),
+        QueryMsg::LastSalePrice { token_id } => {
+            to_json_binary(&query_last_sale_price(deps, token_id)?)
+        }
+        QueryMsg::FailedHooks { start_after, limit } => {
+            to_json_binary(&query_failed_hooks(deps, start_after, limit)?)
+        }
+        QueryMsg::Status {} => {
+            to_json_binary(&MARKET_STATUS.may_load(deps.storage)?.unwrap_or_default())
+        }
+        QueryMsg::AdminDelay {} => to_json_binary(&HOOKS_TIMELOCK.delay(deps.storage)?),
+        QueryMsg::QueuedHookActions { start_after, limit } => {
+            to_json_binary(&HOOKS_TIMELOCK.query(deps.storage, start_after, limit)?)
         }
     }
 }
@@ -160,6 +257,7 @@ pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractE
             ask_interval,
             cooldown_duration,
             cooldown_cancel_fee,
+            cooldown_tiers,
         } => sudo_update_params(
             deps,
             env,
@@ -169,6 +267,7 @@ pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractE
                 ask_interval,
                 cooldown_duration,
                 cooldown_cancel_fee,
+                cooldown_tiers,
             },
         ),
 
@@ -177,18 +276,83 @@ pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractE
         }
         SudoMsg::UpdateAccountFactory { factory } => {
             sudo_update_account_minter(deps, api.addr_validate(&factory)?)
-        } // SudoMsg::EndBlock {  } => todo!(),
+        }
+        SudoMsg::UpdateStatus { status } => sudo_update_status(deps, status),
+        SudoMsg::UpdateAdminDelay { delay } => sudo_update_admin_delay(deps, delay),
+        // SudoMsg::EndBlock {  } => todo!(),
     }
 }
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
     let then = cw2::get_contract_version(deps.storage)?;
-    if then.version >= CONTRACT_VERSION.to_owned() || then.contract != ACCOUNT_MARKETPLACE.to_owned() {
-        return Err(ContractError::Std(StdError::generic_err(
-            "unable to migrate contract.",
-        )));
+    if then.contract != ACCOUNT_MARKETPLACE {
+        return Err(ContractError::InvalidMigrationContract {
+            contract: then.contract,
+        });
+    }
+    let from = parse_version(&then.version)?;
+    if from >= parse_version(CONTRACT_VERSION)? {
+        return Err(ContractError::InvalidMigrationVersion {
+            from: then.version,
+            to: CONTRACT_VERSION.to_string(),
+        });
     }
     cw2::set_contract_version(deps.storage, ACCOUNT_MARKETPLACE, CONTRACT_VERSION)?;
-    Ok(Response::default())
+
+    if from < Version::new(0, 3, 0) {
+        migrate_v0_3(deps.storage)?;
+    }
+
+    let mut res = Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from", then.version)
+        .add_attribute("to", CONTRACT_VERSION);
+    if msg.cooldown_tiers.is_some() {
+        let params = ParamInfo {
+            trading_fee_bps: None,
+            min_price: None,
+            ask_interval: None,
+            cooldown_duration: None,
+            cooldown_cancel_fee: None,
+            cooldown_tiers: msg.cooldown_tiers,
+        };
+        res = res.add_events(sudo_update_params(deps.branch(), env, params)?.events);
+    }
+    if let Some(delay) = msg.admin_delay {
+        res = res.add_events(sudo_update_admin_delay(deps.branch(), delay)?.events);
+    }
+    Ok(res)
+}
+
+fn parse_version(version: &str) -> StdResult<Version> {
+    version
+        .parse()
+        .map_err(|err| StdError::parse_err("semver::Version", err))
+}
+
+/// Params stored before cooldown tiers existed are read without tiers, which matches
+/// the single cooldown of earlier versions. Store them explicitly, and start tracking escrow.
+fn migrate_v0_3(storage: &mut dyn Storage) -> StdResult<()> {
+    let params = SUDO_PARAMS.load(storage)?;
+    SUDO_PARAMS.save(storage, &params)?;
+
+    // escrow was not tracked by earlier versions
+    if !ESCROW_TOTAL.exists(storage) {
+        let total = sum_escrow(storage)?;
+        ESCROW_TOTAL.save(storage, &total)?;
+    }
+
+    // pending sales were accepted with the flat cooldown, before tiers existed
+    let pending = COOLDOWN_BID
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (key, mut p) in pending {
+        if p.cancel_fee.is_none() {
+            p.cooldown = params.cooldown_duration;
+            p.cancel_fee = Some(params.cooldown_fee.clone());
+            COOLDOWN_BID.save(storage, &key, &p)?;
+        }
+    }
+    Ok(())
 }
@@ -1,5 +1,5 @@
 use bs_controllers::HookError;
-use cosmwasm_std::{Coin, Instantiate2AddressError, StdError, Uint128};
+use cosmwasm_std::{Coin, Instantiate2AddressError, OverflowError, StdError, Timestamp, Uint128};
 use cw_utils::PaymentError;
 use thiserror::Error;
 
@@ -17,6 +17,9 @@ pub enum ContractError {
     #[error("{0}")]
     Hook(#[from] HookError),
 
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
     #[error("AlreadySetup")]
     AlreadySetup {},
 
@@ -59,9 +62,43 @@ pub enum ContractError {
     #[error("AskNotFound")]
     AskNotFound {},
 
+    #[error("AskUpdateTooSoon: next update allowed at {0}")]
+    AskUpdateTooSoon(Timestamp),
+
     #[error("CannotProcessFutureRenewal")]
     CannotProcessFutureRenewal {},
 
+    #[error("MarketPaused")]
+    MarketPaused {},
+
+    #[error("NotInEmergency")]
+    NotInEmergency {},
+
+    #[error("CooldownInProgress")]
+    CooldownInProgress {},
+
+    #[error("InvalidCooldownTiers: tiers must be sorted by ascending min_price")]
+    InvalidCooldownTiers {},
+
+    #[error("CounterOfferExpired")]
+    CounterOfferExpired {},
+
+    #[error("BidRejected by {hook}: {reason} (code {code})")]
+    BidRejected {
+        hook: String,
+        code: u32,
+        reason: String,
+    },
+
+    #[error("AccountLocked: the account is locked by the minter")]
+    AccountLocked {},
+
+    #[error("ValidationHookFailed: {hook} could not be queried: {error}")]
+    ValidationHookFailed { hook: String, error: String },
+
+    #[error("InvalidBundle: {0}")]
+    InvalidBundle(String),
+
     #[error("Cannot remove ask with existing bids")]
     ExistingBids {},
 
@@ -76,4 +113,16 @@ pub enum ContractError {
 
     #[error("Contract got an unexpected Reply")]
     UnexpectedReply(),
+
+    #[error("Cannot migrate from contract {contract}")]
+    InvalidMigrationContract { contract: String },
+
+    #[error("Cannot migrate from version {from} to {to}")]
+    InvalidMigrationVersion { from: String, to: String },
+
+    #[error("Hook changes must be proposed and wait for the admin delay")]
+    TimelockRequired {},
+
+    #[error("Hook change can't be executed before {executable_at}")]
+    ActionNotReady { executable_at: Timestamp },
 }
@@ -1,52 +1,145 @@
+use bs_controllers::Hooks;
 use btsg_account::market::{
     hooks::{AskHookMsg, BidHookMsg, HookAction, SaleHookMsg},
-    Ask, Bid,
+    Ask, Bid, FailedHook, HookKind,
 };
 
+use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    Addr, DepsMut, Env, Reply, Response, StdError, StdResult, Storage, SubMsg, WasmMsg,
+    from_json, to_json_binary, Addr, Binary, DepsMut, Env, Event, Reply, Response, StdResult,
+    Storage, SubMsg, SubMsgResult, WasmMsg,
 };
 
 use crate::{state::*, ContractError};
 
-enum HookReply {
+pub enum HookReply {
     Ask = 1,
     Sale,
     Bid,
 }
 
-impl From<u64> for HookReply {
-    fn from(item: u64) -> Self {
+impl TryFrom<u64> for HookReply {
+    type Error = ContractError;
+
+    fn try_from(item: u64) -> Result<Self, Self::Error> {
         match item {
-            1 => HookReply::Ask,
-            2 => HookReply::Sale,
-            3 => HookReply::Bid,
-            _ => panic!("invalid reply type"),
+            1 => Ok(HookReply::Ask),
+            2 => Ok(HookReply::Sale),
+            3 => Ok(HookReply::Bid),
+            _ => Err(ContractError::UnexpectedReply()),
         }
     }
 }
 
-#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
-pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
-    match HookReply::from(msg.id) {
-        HookReply::Ask => {
-            return Err(ContractError::Std(StdError::generic_err(
-                msg.result.unwrap_err(),
-            )))
+impl From<&HookKind> for HookReply {
+    fn from(kind: &HookKind) -> Self {
+        match kind {
+            HookKind::Ask => HookReply::Ask,
+            HookKind::Sale => HookReply::Sale,
+            HookKind::Bid => HookReply::Bid,
         }
-        HookReply::Sale => {
-            return Err(ContractError::Std(StdError::generic_err(
-                msg.result.unwrap_err(),
-            )))
+    }
+}
+
+impl HookReply {
+    fn kind(&self) -> HookKind {
+        match self {
+            HookReply::Ask => HookKind::Ask,
+            HookReply::Sale => HookKind::Sale,
+            HookReply::Bid => HookKind::Bid,
         }
-        HookReply::Bid => {
-            return Err(ContractError::Std(StdError::generic_err(
-                msg.result.unwrap_err(),
-            )))
+    }
+
+    fn hooks(&self) -> Hooks {
+        match self {
+            HookReply::Ask => ASK_HOOKS,
+            HookReply::Sale => SALE_HOOKS,
+            HookReply::Bid => BID_HOOKS,
         }
     }
 }
 
+/// Carried through the submessage so a failed call can be stored and retried
+#[cw_serde]
+struct HookPayload {
+    hook: Addr,
+    msg: Binary,
+}
+
+/// Hook calls reply on success and on error. Errors never abort the trade: the failed
+/// payload is stored for `RetryHook`, and a hook that keeps failing is removed.
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let reply = HookReply::try_from(msg.id)?;
+    let payload: HookPayload = from_json(&msg.payload)?;
+
+    match msg.result {
+        SubMsgResult::Ok(_) => {
+            HOOK_FAILURES.remove(deps.storage, (msg.id, &payload.hook));
+            Ok(Response::new())
+        }
+        SubMsgResult::Err(error) => record_failed_hook(deps, env, reply, payload, error),
+    }
+}
+
+fn record_failed_hook(
+    deps: DepsMut,
+    env: Env,
+    reply: HookReply,
+    payload: HookPayload,
+    error: String,
+) -> Result<Response, ContractError> {
+    let reply_id = reply as u64;
+    let failed = FailedHook {
+        id: increment_failed_hooks(deps.storage)?,
+        kind: reply.kind(),
+        hook: payload.hook.clone(),
+        msg: payload.msg,
+        error: error.clone(),
+        time: env.block.time,
+    };
+    FAILED_HOOKS.save(deps.storage, failed.id, &failed)?;
+
+    let mut event = Event::new("hook-failed")
+        .add_attribute("id", failed.id.to_string())
+        .add_attribute("kind", format!("{:?}", failed.kind))
+        .add_attribute("hook", failed.hook.to_string())
+        .add_attribute("error", error);
+
+    let failures = HOOK_FAILURES
+        .may_load(deps.storage, (reply_id, &failed.hook))?
+        .unwrap_or_default()
+        + 1;
+    let hooks = reply.hooks();
+    let registered = hooks
+        .query_hooks(deps.as_ref())?
+        .hooks
+        .contains(&failed.hook.to_string());
+    if failures >= MAX_HOOK_FAILURES && registered {
+        hooks.remove_hook(deps.storage, failed.hook.clone())?;
+        HOOK_FAILURES.remove(deps.storage, (reply_id, &failed.hook));
+        event = event.add_attribute("disabled", "true");
+    } else {
+        HOOK_FAILURES.save(deps.storage, (reply_id, &failed.hook), &failures)?;
+    }
+
+    Ok(Response::new().add_event(event))
+}
+
+/// Builds a hook call that replies with its payload on success and on error
+pub fn hook_submsg(hook: &Addr, msg: Binary, reply: HookReply) -> StdResult<SubMsg> {
+    let payload = to_json_binary(&HookPayload {
+        hook: hook.clone(),
+        msg: msg.clone(),
+    })?;
+    let execute = WasmMsg::Execute {
+        contract_addr: hook.to_string(),
+        msg,
+        funds: vec![],
+    };
+    Ok(SubMsg::reply_always(execute, reply as u64).with_payload(payload))
+}
+
 pub fn prepare_ask_hook(
     storage: &dyn Storage,
     ask: &Ask,
@@ -54,12 +147,7 @@ pub fn prepare_ask_hook(
 ) -> StdResult<Vec<SubMsg>> {
     let submsgs = ASK_HOOKS.prepare_hooks(storage, |h| {
         let msg = AskHookMsg { ask: ask.clone() };
-        let execute = WasmMsg::Execute {
-            contract_addr: h.to_string(),
-            msg: msg.into_json_binary(action.clone())?,
-            funds: vec![],
-        };
-        Ok(SubMsg::reply_on_error(execute, 1u64))
+        hook_submsg(&h, msg.into_json_binary(action.clone())?, HookReply::Ask)
     })?;
 
     Ok(submsgs)
@@ -73,12 +161,7 @@ pub fn prepare_sale_hook(storage: &dyn Storage, ask: &Ask, buyer: Addr) -> StdRe
             seller: ask.seller.to_string(),
             buyer: buyer.to_string(),
         };
-        let execute = WasmMsg::Execute {
-            contract_addr: h.to_string(),
-            msg: msg.into_json_binary()?,
-            funds: vec![],
-        };
-        Ok(SubMsg::reply_on_error(execute, HookReply::Sale as u64))
+        hook_submsg(&h, msg.into_json_binary()?, HookReply::Sale)
     })?;
 
     Ok(submsgs)
@@ -91,12 +174,7 @@ pub fn prepare_bid_hook(
 ) -> StdResult<Vec<SubMsg>> {
     let submsgs = BID_HOOKS.prepare_hooks(storage, |h| {
         let msg = BidHookMsg { bid: bid.clone() };
-        let execute = WasmMsg::Execute {
-            contract_addr: h.to_string(),
-            msg: msg.into_json_binary(action.clone())?,
-            funds: vec![],
-        };
-        Ok(SubMsg::reply_on_error(execute, HookReply::Bid as u64))
+        hook_submsg(&h, msg.into_json_binary(action.clone())?, HookReply::Bid)
     })?;
 
     Ok(submsgs)
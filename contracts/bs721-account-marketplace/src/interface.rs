@@ -1,13 +1,13 @@
 use cw_orch::{interface, prelude::*};
 
 use crate::{
-    contract::{execute, instantiate, query, sudo, ACCOUNT_MARKETPLACE},
+    contract::{execute, instantiate, migrate, query, sudo, ACCOUNT_MARKETPLACE},
     hooks::reply,
 };
-use btsg_account::market::{ExecuteMsg, MarketplaceInstantiateMsg, QueryMsg};
+use btsg_account::market::{ExecuteMsg, MarketplaceInstantiateMsg, MigrateMsg, QueryMsg};
 
 /// Uploadable trait for bs721_account_minter & use with cw-orchestrator library
-#[interface(MarketplaceInstantiateMsg, ExecuteMsg, QueryMsg, Empty)]
+#[interface(MarketplaceInstantiateMsg, ExecuteMsg, QueryMsg, MigrateMsg)]
 pub struct BtsgAccountMarket;
 
 impl<Chain> Uploadable for BtsgAccountMarket<Chain> {
@@ -22,7 +22,8 @@ impl<Chain> Uploadable for BtsgAccountMarket<Chain> {
         Box::new(
             ContractWrapper::new_with_empty(execute, instantiate, query)
                 .with_sudo(sudo)
-                .with_reply(reply),
+                .with_reply(reply)
+                .with_migrate(migrate),
         )
     }
 }
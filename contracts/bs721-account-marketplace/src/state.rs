@@ -1,8 +1,12 @@
 use bs_controllers::Hooks;
 
-use btsg_account::market::{Ask, AskKey, Bid, BidKey, PendingBid, SudoParams};
+use btsg_account::market::{
+    Ask, AskKey, Bid, BidKey, BundleBid, CounterOffer, FailedHook, ManageHooksAction, MarketStatus,
+    PendingBid, PendingBundle, PrivateSale, Sale, SudoParams,
+};
+use btsg_account::timelock::Timelock;
 use btsg_account::TokenId;
-use cosmwasm_std::{Addr, StdResult, Storage};
+use cosmwasm_std::{Addr, StdResult, Storage, Timestamp, Uint128};
 use cw_storage_macro::index_list;
 use cw_storage_plus::{IndexedMap, Item, Map, MultiIndex, UniqueIndex};
 
@@ -10,21 +14,50 @@ use cw_storage_plus::{IndexedMap, Item, Map, MultiIndex, UniqueIndex};
 pub const MAX_FEE_BPS: u64 = 10000;
 
 pub const MAX_REMOVE_BID_LIMIT: u64 = 30;
+/// Number of recent sales considered when computing the floor price
+pub const FLOOR_PRICE_SAMPLE: usize = 20;
+/// Max number of accounts in a single bundle bid
+pub const MAX_BUNDLE_SIZE: usize = 10;
+/// Max number of expired listing times pruned each time a token is listed
+pub const MAX_ASK_UPDATED_PRUNE: usize = 10;
 pub const COOLDOWN_BID: Map<&TokenId, PendingBid> = Map::new("cdb");
 pub const OVERFLOW_BIDS_REMOVE: Map<TokenId, Vec<BidKey>> = Map::new("obr");
-
+pub const COUNTER_OFFERS: Map<BidKey, CounterOffer> = Map::new("co");
+pub const PRIVATE_SALES: Map<&TokenId, PrivateSale> = Map::new("ps");
+pub const BUNDLE_BIDS: Map<u64, BundleBid> = Map::new("bb");
+/// Bundle bids each token is part of
+pub const TOKEN_BUNDLES: Map<(&TokenId, u64), bool> = Map::new("tb");
+pub const BUNDLE_COUNT: Item<u64> = Item::new("bc");
+pub const BUNDLE_COOLDOWN: Map<u64, PendingBundle> = Map::new("bcd");
+/// Accepted bundle each token is locked in during its cooldown
+pub const BUNDLE_COOLDOWN_TOKENS: Map<&TokenId, u64> = Map::new("bct");
 
 pub const SUDO_PARAMS: Item<SudoParams> = Item::new("sp");
+pub const MARKET_STATUS: Item<MarketStatus> = Item::new("ms");
+/// Hook changes waiting for the admin delay set by governance
+pub const HOOKS_TIMELOCK: Timelock<ManageHooksAction> = Timelock::new("had", "hac", "haq");
+/// Total funds held in escrow for bidders, updated as funds enter and leave the contract.
+/// Bids only accept `NATIVE_DENOM`, so the total is in that denom.
+pub const ESCROW_TOTAL: Item<Uint128> = Item::new("et");
 
 pub const ASK_HOOKS: Hooks = Hooks::new("ah");
 pub const BID_HOOKS: Hooks = Hooks::new("bh");
 pub const SALE_HOOKS: Hooks = Hooks::new("sh");
+/// Queried before a bid is placed or accepted, and may deny it
+pub const VALIDATION_HOOKS: Hooks = Hooks::new("vh");
+/// Consecutive failures before a hook is removed
+pub const MAX_HOOK_FAILURES: u32 = 3;
+pub const FAILED_HOOKS: Map<u64, FailedHook> = Map::new("fh");
+pub const FAILED_HOOK_COUNT: Item<u64> = Item::new("fhc");
+/// Consecutive failures of each hook, keyed by (reply id, hook)
+pub const HOOK_FAILURES: Map<(u64, &Addr), u32> = Map::new("hf");
 
 pub const ACCOUNT_MINTER: Item<Addr> = Item::new("am");
 pub const ACCOUNT_COLLECTION: Item<Addr> = Item::new("ac");
 pub const VERSION_CONTROL: Item<Addr> = Item::new("vc");
 
 pub const ASK_COUNT: Item<u32> = Item::new("ask-count");
+pub const SALE_COUNT: Item<u64> = Item::new("sale-count");
 pub const IS_SETUP: Item<bool> = Item::new("is");
 
 pub fn ask_count(storage: &dyn Storage) -> StdResult<u32> {
@@ -43,6 +76,42 @@ pub fn decrement_asks(storage: &mut dyn Storage) -> StdResult<u32> {
     Ok(val)
 }
 
+pub fn increment_sales(storage: &mut dyn Storage) -> StdResult<u64> {
+    let val = SALE_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    SALE_COUNT.save(storage, &val)?;
+    Ok(val)
+}
+
+pub fn increment_failed_hooks(storage: &mut dyn Storage) -> StdResult<u64> {
+    let val = FAILED_HOOK_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    FAILED_HOOK_COUNT.save(storage, &val)?;
+    Ok(val)
+}
+
+pub fn add_escrow(storage: &mut dyn Storage, amount: Uint128) -> StdResult<Uint128> {
+    let val = ESCROW_TOTAL
+        .may_load(storage)?
+        .unwrap_or_default()
+        .checked_add(amount)?;
+    ESCROW_TOTAL.save(storage, &val)?;
+    Ok(val)
+}
+
+pub fn release_escrow(storage: &mut dyn Storage, amount: Uint128) -> StdResult<Uint128> {
+    let val = ESCROW_TOTAL
+        .may_load(storage)?
+        .unwrap_or_default()
+        .checked_sub(amount)?;
+    ESCROW_TOTAL.save(storage, &val)?;
+    Ok(val)
+}
+
+pub fn increment_bundles(storage: &mut dyn Storage) -> StdResult<u64> {
+    let val = BUNDLE_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    BUNDLE_COUNT.save(storage, &val)?;
+    Ok(val)
+}
+
 /// Convenience ask key constructor
 pub fn ask_key(token_id: &str) -> AskKey {
     token_id.to_string()
@@ -70,6 +139,21 @@ pub fn asks<'a>() -> IndexedMap<AskKey, Ask, AskIndicies<'a>> {
     IndexedMap::new("asks", indexes)
 }
 
+/// Defines indices for accessing listing times
+#[index_list(Timestamp)]
+pub struct AskUpdatedIndicies<'a> {
+    /// Index by time, so listing times can be pruned once the ask interval has passed
+    pub time: MultiIndex<'a, u64, Timestamp, (Addr, TokenId)>,
+}
+
+/// Last time an ask was set for a (seller, token_id). Used to rate limit listing a token again.
+pub fn ask_updated<'a>() -> IndexedMap<(Addr, TokenId), Timestamp, AskUpdatedIndicies<'a>> {
+    let indexes = AskUpdatedIndicies {
+        time: MultiIndex::new(|_pk: &[u8], t: &Timestamp| t.seconds(), "au", "au__t"),
+    };
+    IndexedMap::new("au", indexes)
+}
+
 /// Convenience bid key constructor
 pub fn bid_key(token_id: &str, bidder: &Addr) -> BidKey {
     (token_id.to_string(), bidder.clone())
@@ -99,3 +183,23 @@ pub fn bids<'a>() -> IndexedMap<BidKey, Bid, BidIndicies<'a>> {
     };
     IndexedMap::new("b2", indexes)
 }
+
+/// Defines indices for accessing sales
+#[index_list(Sale)]
+pub struct SaleIndicies<'a> {
+    pub token_id: MultiIndex<'a, String, Sale, u64>,
+    /// Index by the number of characters in the account name
+    pub length: MultiIndex<'a, u32, Sale, u64>,
+}
+
+pub fn sales<'a>() -> IndexedMap<u64, Sale, SaleIndicies<'a>> {
+    let indexes = SaleIndicies {
+        token_id: MultiIndex::new(|_pk: &[u8], s: &Sale| s.token_id.clone(), "s", "s__t"),
+        length: MultiIndex::new(
+            |_pk: &[u8], s: &Sale| s.token_id.chars().count() as u32,
+            "s",
+            "s__l",
+        ),
+    };
+    IndexedMap::new("s", indexes)
+}
@@ -58,6 +58,7 @@ fn bid_indexed_map() {
         bidder: bidder.clone(),
         amount: Uint128::from(500u128),
         created_time: Timestamp::from_seconds(6),
+        waive_cooldown: false,
     };
     let key = bid_key(TOKEN_ID, &bidder);
     let res = bids().save(deps.as_mut().storage, key.clone(), &bid);
@@ -68,6 +69,7 @@ fn bid_indexed_map() {
         bidder: bidder.clone(),
         amount: Uint128::from(500u128),
         created_time: Timestamp::from_seconds(6),
+        waive_cooldown: false,
     };
     let key2 = bid_key(TOKEN_ID_NEXT, &bidder);
     let res = bids().save(deps.as_mut().storage, key2, &bid2);
@@ -173,6 +175,7 @@ fn try_set_bid() {
     // Bidder calls SetBid before an Ask is set, fails
     let set_bid_msg = ExecuteMsg::SetBid {
         token_id: TOKEN_ID.to_string(),
+        waive_cooldown: false,
     };
     let res = execute(deps.as_mut(), mock_env(), bidder, set_bid_msg);
     assert!(res.is_err());
@@ -1,67 +1,273 @@
+use bs721_account::helpers::Bs721Account;
+use bs721_account::msg::Bs721AccountsQueryMsg;
 use btsg_account::charge_fees;
+use btsg_account::merkle;
 use btsg_account::minter::Config;
 use btsg_account::minter::SudoParams;
+use btsg_account::minter::{
+    reservation_leaf, voucher_commitment, AdminAction, BatchMintItem, BlockRule, CanMintResponse,
+    DelegationPolicy, DelegationSnapshot, Delegator, FlaggedAccount, IdnConfig, MintDelegation,
+    MintError, MintPriceResponse, PricingSchedule, ReferralConfig, ReferralStats, Voucher,
+    Whitelist, WhitelistQueryMsg, WhitelistResponse, WhitelistType,
+};
 use btsg_account::Metadata;
-use btsg_account::NATIVE_DENOM;
+use btsg_account::{DEFAULT_QUERY_LIMIT, MAX_QUERY_LIMIT, NATIVE_DENOM};
 use cosmwasm_std::{
-    coin, Coin, Decimal, DepsMut, Env, Event, MessageInfo, Response, Uint128, WasmMsg,
+    coin, coins, BankMsg, Coin, CosmosMsg, Decimal, DepsMut, Empty, Env, Event, HexBinary,
+    MessageInfo, Order, Response, Uint128, WasmMsg,
 };
-use cosmwasm_std::{to_json_binary, Addr, Deps, StdResult};
+use cosmwasm_std::{to_json_binary, Addr, Deps, StdError, StdResult, Storage};
+use cw_storage_plus::Bound;
 use cw_utils::must_pay;
+use sha2::{Digest, Sha256};
 
 use crate::{
+    idn,
     state::ACCOUNT_MARKETPLACE,
-    state::{ACCOUNT_COLLECTION, CONFIG, PAUSED, SUDO_PARAMS},
+    state::{ACCOUNT_COLLECTION, CONFIG, IDN_CONFIG, PAUSED, PREMIUM_WORDS, SUDO_PARAMS},
+    state::{ADMIN_TIMELOCK, BLOCKED_NAMES, BLOCKED_PATTERNS, MAX_BLOCKED_PATTERNS},
+    state::{DELEGATION_POLICY, DELEGATION_SNAPSHOTS, MAX_BATCH_MINT},
+    state::{FLAGGED_ACCOUNTS, MINT_DELEGATIONS},
+    state::{REFERRALS, REFERRAL_CONFIG, VOUCHERS, VOUCHER_COMMITMENTS},
+    state::{RESERVATION_ROOT, RESERVED_ACCOUNTS, WHITELISTS, WHITELIST_COUNT, WHITELIST_MINTS},
     ContractError,
 };
 
+/// Optional arguments of `MintAndList`
+pub struct MintOptions {
+    pub proof: Option<Vec<String>>,
+    pub referrer: Option<String>,
+    pub voucher: Option<String>,
+    pub owner: Option<String>,
+    pub initial: Option<Metadata>,
+    pub associate: Option<String>,
+}
+
 pub fn execute_mint_and_list(
-    deps: DepsMut,
+    mut deps: DepsMut,
     info: MessageInfo,
     env: Env,
     account: &str,
+    options: MintOptions,
 ) -> Result<Response, ContractError> {
+    let MintOptions {
+        proof,
+        referrer,
+        voucher,
+        owner,
+        initial,
+        associate,
+    } = options;
     if PAUSED.load(deps.storage)? {
         return Err(ContractError::MintingPaused {});
     }
 
+    let idn_config = IDN_CONFIG.may_load(deps.storage)?;
+    let account = &match idn_config {
+        Some(_) => idn::normalize(account),
+        None => account.to_string(),
+    };
+    if RESERVED_ACCOUNTS.has(deps.storage, account) {
+        return Err(ContractError::AccountReserved {});
+    }
+    if blocked_by(deps.as_ref(), account, idn_config.is_some())?.is_some() {
+        return Err(ContractError::AccountBlocked {});
+    }
+
     let sender = &info.sender.to_string();
     let config = CONFIG.load(deps.storage)?;
     let params = SUDO_PARAMS.load(deps.storage)?;
-    let acc_len = account.len();
 
-    if env.block.time < config.public_mint_start_time {
-        return Err(ContractError::MintingNotStarted {});
+    let whitelist_discount = if env.block.time < config.public_mint_start_time {
+        whitelist_mint(deps.branch(), &env, &info.sender, proof)?
+    } else {
+        None
+    };
+    let referral = referrer
+        .map(|r| resolve_referrer(deps.as_ref(), &r, &info.sender))
+        .transpose()?;
+    let voucher_discount = voucher
+        .map(|code| use_voucher(deps.branch(), &env, &code, &info.sender))
+        .transpose()?;
+    // discounts don't stack, the largest one applies
+    let discount = [
+        whitelist_discount,
+        referral.as_ref().map(|(_, config)| config.discount),
+        voucher_discount,
+    ]
+    .into_iter()
+    .flatten()
+    .max()
+    .map(Discount::Percent);
+
+    let acc_len = validate_new_account(deps.branch(), account, idn_config.as_ref(), &params)?;
+    let MintPriceResponse { price, delegation } =
+        mint_price(deps.as_ref(), &env, account, acc_len)?;
+    let price = validate_payment(price.amount, &info, discount)?;
+    // the account can be minted for another owner, e.g. as a gift or for a DAO
+    let owner = match owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender.clone(),
+    };
+    let associate = associate
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
+    if associate.is_some() && owner != info.sender {
+        return Err(ContractError::InvalidAssociation {});
+    }
+    let delegator = match DELEGATION_POLICY.may_load(deps.storage)? {
+        Some(DelegationPolicy {
+            delegator: Delegator::Owner,
+            ..
+        }) => &owner,
+        _ => &info.sender,
+    };
+    validate_staking(deps.as_ref(), &env, delegator, delegation)?;
+    clear_mint_record(deps.storage, account);
+    if !delegation.is_zero() {
+        MINT_DELEGATIONS.save(
+            deps.storage,
+            account,
+            &MintDelegation {
+                delegator: delegator.clone(),
+                amount: delegation,
+                owner: owner.clone(),
+                policy: DELEGATION_POLICY.may_load(deps.storage)?,
+            },
+        )?;
     }
 
-    validate_account(
-        account,
-        params.min_account_length,
-        params.max_account_length,
-    )?;
-    let price = validate_payment(acc_len, &info, params.base_price.u128())?;
-    validate_staking(
+    let mut res = Response::new();
+    let fee = price.as_ref().map(|p| p.amount).unwrap_or_default();
+    // a share of the fee goes to the referrer
+    let reward = match &referral {
+        Some((referrer, config)) => {
+            let reward = fee.mul_floor(config.fee_share);
+            let mut stats = REFERRALS
+                .may_load(deps.storage, referrer)?
+                .unwrap_or_default();
+            stats.mints += 1;
+            stats.rewards += reward;
+            REFERRALS.save(deps.storage, referrer, &stats)?;
+            if !reward.is_zero() {
+                res = res.add_message(BankMsg::Send {
+                    to_address: referrer.to_string(),
+                    amount: coins(reward.u128(), NATIVE_DENOM),
+                });
+            }
+            reward
+        }
+        None => Uint128::zero(),
+    };
+    // burns the rest of the fee if required (only ubtsg supported currently)
+    charge_fees(&mut res, fee - reward);
+
+    let mut event = Event::new("mint-and-list")
+        .add_attribute("account", account)
+        .add_attribute("owner", owner.as_str())
+        .add_attribute(
+            "price",
+            price
+                .unwrap_or_else(|| coin(0u128, NATIVE_DENOM))
+                .amount
+                .to_string(),
+        );
+    if let Some((referrer, _)) = referral {
+        event = event
+            .add_attribute("referrer", referrer)
+            .add_attribute("referral_reward", reward.to_string());
+    }
+    if owner != info.sender {
+        event = event.add_attribute("sender", sender);
+    }
+    Ok(res.add_event(event).add_messages(mint_and_list_msgs(
         deps.as_ref(),
-        info.sender.as_ref(),
-        acc_len,
-        params.base_delegation,
-    )?;
+        account,
+        owner.as_str(),
+        initial.unwrap_or_default(),
+        associate.map(String::from),
+    )?))
+}
 
-    let mut res = Response::new();
-    // burns any tokens sent as fees if required (only ubtsg supported currently)
-    if let Some(fee) = &price {
-        charge_fees(&mut res, fee.amount);
+/// Validates an account before minting, returning the length it is priced by.
+/// In IDN mode the length is in graphemes, and look-alikes of minted accounts are rejected.
+fn validate_new_account(
+    deps: DepsMut,
+    account: &str,
+    idn_config: Option<&IdnConfig>,
+    params: &SudoParams,
+) -> Result<usize, ContractError> {
+    match idn_config {
+        None => {
+            validate_account(
+                account,
+                params.min_account_length,
+                params.max_account_length,
+            )?;
+            Ok(account.len())
+        }
+        Some(config) => {
+            idn::validate_idn_account(
+                account,
+                config,
+                params.min_account_length,
+                params.max_account_length,
+            )?;
+            idn::register_skeleton(deps, account)?;
+            Ok(idn::grapheme_len(account))
+        }
     }
+}
+
+/// Price and delegation required to mint an account, before discounts.
+/// `account_len` is the byte count of ASCII accounts, or the grapheme count in IDN mode.
+pub fn mint_price(
+    deps: Deps,
+    env: &Env,
+    account: &str,
+    account_len: usize,
+) -> StdResult<MintPriceResponse> {
+    let params = SUDO_PARAMS.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
+    let since_launch = env
+        .block
+        .time
+        .seconds()
+        .checked_sub(config.public_mint_start_time.seconds());
 
+    let price = params.pricing.price(
+        params.base_price,
+        account,
+        account_len,
+        PREMIUM_WORDS.has(deps.storage, account),
+        since_launch,
+    );
+    Ok(MintPriceResponse {
+        price: coin(price.u128(), NATIVE_DENOM),
+        delegation: params
+            .pricing
+            .delegation(params.base_delegation, account_len),
+    })
+}
+
+/// Mints `account` to `owner` and lists it on the marketplace.
+/// The collection approves the marketplace for the minted token, which listing requires.
+fn mint_and_list_msgs(
+    deps: Deps,
+    account: &str,
+    owner: &str,
+    metadata: Metadata,
+    associate: Option<String>,
+) -> StdResult<Vec<WasmMsg>> {
     let marketplace = ACCOUNT_MARKETPLACE.load(deps.storage)?;
     let collection = ACCOUNT_COLLECTION.load(deps.storage)?;
 
     // mint token
     let mint_msg = bs721_account::msg::ExecuteMsg::Mint {
         token_id: account.to_string(),
-        owner: sender.to_string(),
-        token_uri: None,
-        extension: Metadata::default(),
+        owner: owner.to_string(),
+        token_uri: associate,
+        extension: metadata,
         seller_fee_bps: None,
         payment_addr: None,
     };
@@ -73,7 +279,7 @@ pub fn execute_mint_and_list(
 
     let ask_msg = btsg_account::market::ExecuteMsg::SetAsk {
         token_id: account.to_string(),
-        seller: sender.to_string(),
+        seller: owner.to_string(),
     };
 
     let list_msg_exec = WasmMsg::Execute {
@@ -82,35 +288,603 @@ pub fn execute_mint_and_list(
         funds: vec![],
     };
 
-    let event = Event::new("mint-and-list")
+    Ok(vec![mint_msg_exec, list_msg_exec])
+}
+
+/// Mint and list accounts for their owners, skipping payment, delegation and whitelist checks.
+/// With an admin delay, the batch must be proposed.
+pub fn execute_batch_mint(
+    deps: DepsMut,
+    info: MessageInfo,
+    items: Vec<BatchMintItem>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    ensure_no_admin_delay(deps.storage)?;
+
+    apply_batch_mint(deps, items)
+}
+
+fn apply_batch_mint(
+    mut deps: DepsMut,
+    items: Vec<BatchMintItem>,
+) -> Result<Response, ContractError> {
+    if items.is_empty() || items.len() > MAX_BATCH_MINT {
+        return Err(ContractError::InvalidBatchSize {
+            max: MAX_BATCH_MINT,
+        });
+    }
+
+    let idn_config = IDN_CONFIG.may_load(deps.storage)?;
+    let params = SUDO_PARAMS.load(deps.storage)?;
+    let mut res = Response::new();
+    let mut accounts = Vec::with_capacity(items.len());
+    for item in items {
+        let owner = deps.api.addr_validate(&item.owner)?;
+        let account = match idn_config {
+            Some(_) => idn::normalize(item.account.trim()),
+            None => item.account.trim().to_string(),
+        };
+        if RESERVED_ACCOUNTS.has(deps.storage, &account) {
+            return Err(ContractError::AccountReserved {});
+        }
+        if blocked_by(deps.as_ref(), &account, idn_config.is_some())?.is_some() {
+            return Err(ContractError::AccountBlocked {});
+        }
+        validate_new_account(deps.branch(), &account, idn_config.as_ref(), &params)?;
+        clear_mint_record(deps.storage, &account);
+
+        res = res.add_messages(mint_and_list_msgs(
+            deps.as_ref(),
+            &account,
+            owner.as_str(),
+            item.metadata,
+            None,
+        )?);
+        accounts.push(account);
+    }
+
+    let event = Event::new("batch-mint")
+        .add_attribute("count", accounts.len().to_string())
+        .add_attribute("accounts", accounts.join(","));
+    Ok(res.add_event(event))
+}
+
+/// The blocklist rule an account matches, if any.
+/// In IDN mode, its confusable skeleton is checked too, to catch look-alikes of blocked names.
+fn blocked_by(deps: Deps, account: &str, idn: bool) -> StdResult<Option<BlockRule>> {
+    let mut candidates = vec![account.to_string()];
+    if idn {
+        let skeleton = idn::skeleton(account);
+        if skeleton != account {
+            candidates.push(skeleton);
+        }
+    }
+
+    for candidate in &candidates {
+        if BLOCKED_NAMES.has(deps.storage, candidate) {
+            return Ok(Some(BlockRule::Name(candidate.clone())));
+        }
+    }
+    for pattern in BLOCKED_PATTERNS.keys(deps.storage, None, None, Order::Ascending) {
+        let rule = BlockRule::Pattern(pattern?);
+        if candidates.iter().any(|c| rule.matches(c)) {
+            return Ok(Some(rule));
+        }
+    }
+    Ok(None)
+}
+
+fn update_blocklist(
+    deps: DepsMut,
+    add: &[BlockRule],
+    remove: &[BlockRule],
+) -> Result<(), ContractError> {
+    for rule in add {
+        if !rule.is_valid() {
+            return Err(ContractError::InvalidBlockRule {});
+        }
+        match rule {
+            BlockRule::Name(name) => BLOCKED_NAMES.save(deps.storage, name, &Empty {})?,
+            BlockRule::Pattern(pattern) => {
+                BLOCKED_PATTERNS.save(deps.storage, pattern, &Empty {})?
+            }
+        }
+    }
+    for rule in remove {
+        match rule {
+            BlockRule::Name(name) => BLOCKED_NAMES.remove(deps.storage, name),
+            BlockRule::Pattern(pattern) => BLOCKED_PATTERNS.remove(deps.storage, pattern),
+        }
+    }
+
+    // patterns are all checked on each mint
+    let patterns = BLOCKED_PATTERNS
+        .keys_raw(deps.storage, None, None, Order::Ascending)
+        .count();
+    if patterns > MAX_BLOCKED_PATTERNS {
+        return Err(ContractError::TooManyBlockedPatterns {
+            max: MAX_BLOCKED_PATTERNS,
+        });
+    }
+    Ok(())
+}
+
+/// Add or remove blocklist rules. With an admin delay, the change must be proposed.
+pub fn execute_update_blocklist(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<BlockRule>,
+    remove: Vec<BlockRule>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    ensure_no_admin_delay(deps.storage)?;
+
+    apply_update_blocklist(deps, add, remove)
+}
+
+fn apply_update_blocklist(
+    deps: DepsMut,
+    add: Vec<BlockRule>,
+    remove: Vec<BlockRule>,
+) -> Result<Response, ContractError> {
+    update_blocklist(deps, &add, &remove)?;
+
+    let event = Event::new("update-blocklist")
+        .add_attribute("added", add.len().to_string())
+        .add_attribute("removed", remove.len().to_string());
+    Ok(Response::new().add_event(event))
+}
+
+/// Mint a reserved account for free, skipping the delegation and whitelist checks
+pub fn execute_claim_reserved(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    account: &str,
+    proof: Vec<String>,
+) -> Result<Response, ContractError> {
+    if PAUSED.load(deps.storage)? {
+        return Err(ContractError::MintingPaused {});
+    }
+    let idn_config = IDN_CONFIG.may_load(deps.storage)?;
+    let account = &match idn_config {
+        Some(_) => idn::normalize(account),
+        None => account.to_string(),
+    };
+    if !RESERVED_ACCOUNTS.has(deps.storage, account) {
+        return Err(ContractError::NotReserved {});
+    }
+
+    let root = RESERVATION_ROOT.load(deps.storage)?;
+    let leaf = reservation_leaf(account, info.sender.as_str());
+    if !merkle::verify_proof(&root, &leaf, &proof)? {
+        return Err(ContractError::InvalidReservationProof {});
+    }
+
+    let params = SUDO_PARAMS.load(deps.storage)?;
+    validate_new_account(deps.branch(), account, idn_config.as_ref(), &params)?;
+    RESERVED_ACCOUNTS.remove(deps.storage, account);
+    clear_mint_record(deps.storage, account);
+
+    let event = Event::new("claim-reserved")
         .add_attribute("account", account)
-        .add_attribute("owner", sender)
-        .add_attribute(
-            "price",
-            price
-                .unwrap_or_else(|| coin(0u128, NATIVE_DENOM))
-                .amount
-                .to_string(),
-        );
-    Ok(res
+        .add_attribute("owner", info.sender.to_string());
+    Ok(Response::new()
         .add_event(event)
-        .add_messages(vec![mint_msg_exec, list_msg_exec]))
+        .add_messages(mint_and_list_msgs(
+            deps.as_ref(),
+            account,
+            info.sender.as_str(),
+            Metadata::default(),
+            None,
+        )?))
+}
+
+/// Set the merkle root of reserved (account, address) pairs, and add or remove reserved accounts.
+/// Accounts can be sent over several calls for large reservation lists.
+/// With an admin delay, the change must be proposed.
+pub fn execute_update_reservations(
+    deps: DepsMut,
+    info: MessageInfo,
+    root: Option<String>,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    ensure_no_admin_delay(deps.storage)?;
+
+    apply_update_reservations(deps, root, add, remove)
+}
+
+fn apply_update_reservations(
+    deps: DepsMut,
+    root: Option<String>,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    if let Some(root) = &root {
+        if !merkle::is_valid_root(root) {
+            return Err(ContractError::InvalidMerkleRoot {});
+        }
+        RESERVATION_ROOT.save(deps.storage, root)?;
+    }
+    for account in &add {
+        RESERVED_ACCOUNTS.save(deps.storage, account, &Empty {})?;
+    }
+    for account in &remove {
+        RESERVED_ACCOUNTS.remove(deps.storage, account);
+    }
+
+    let mut event = Event::new("update-reservations")
+        .add_attribute("added", add.len().to_string())
+        .add_attribute("removed", remove.len().to_string());
+    if let Some(root) = root {
+        event = event.add_attribute("root", root);
+    }
+    Ok(Response::new().add_event(event))
+}
+
+/// Counts the mint against the first active whitelist phase the sender can still mint in,
+/// returning its discount.
+fn whitelist_mint(
+    deps: DepsMut,
+    env: &Env,
+    sender: &Addr,
+    proof: Option<Vec<String>>,
+) -> Result<Option<Decimal>, ContractError> {
+    let (id, whitelist, minted) = whitelist_phase(deps.as_ref(), env, sender, proof.as_deref())?;
+    WHITELIST_MINTS.save(deps.storage, (id, sender), &(minted + 1))?;
+    Ok(whitelist.discount)
+}
+
+/// The first active whitelist phase the sender can still mint in, with its id and the
+/// sender's mints in it so far.
+fn whitelist_phase(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    proof: Option<&[String]>,
+) -> Result<(u32, Whitelist, u32), ContractError> {
+    let active = WHITELISTS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?
+        .into_iter()
+        .filter(|(_, wl)| wl.is_active(&env.block.time))
+        .collect::<Vec<_>>();
+    if active.is_empty() {
+        return Err(ContractError::MintingNotStarted {});
+    }
+
+    let mut limit_reached = false;
+    for (id, wl) in active {
+        if !includes_address(deps, &wl.list, sender, proof)? {
+            continue;
+        }
+        let minted = WHITELIST_MINTS
+            .may_load(deps.storage, (id, sender))?
+            .unwrap_or_default();
+        if minted >= wl.per_address_limit {
+            limit_reached = true;
+            continue;
+        }
+        return Ok((id, wl, minted));
+    }
+
+    match limit_reached {
+        true => Err(ContractError::WhitelistLimitReached {}),
+        false => Err(ContractError::NotWhitelisted {}),
+    }
+}
+
+/// A referrer is an address, or an account whose associated address receives the reward
+fn resolve_referrer(
+    deps: Deps,
+    referrer: &str,
+    sender: &Addr,
+) -> Result<(Addr, ReferralConfig), ContractError> {
+    let config = REFERRAL_CONFIG
+        .may_load(deps.storage)?
+        .ok_or(ContractError::ReferralsDisabled {})?;
+
+    let referrer = match deps.api.addr_validate(referrer) {
+        Ok(addr) => addr,
+        Err(_) => {
+            let collection = ACCOUNT_COLLECTION.load(deps.storage)?;
+            let associated: String = deps
+                .querier
+                .query_wasm_smart(
+                    collection,
+                    &Bs721AccountsQueryMsg::AssociatedAddress {
+                        account: referrer.to_string(),
+                    },
+                )
+                .map_err(|_| ContractError::InvalidReferrer {})?;
+            deps.api.addr_validate(&associated)?
+        }
+    };
+    if referrer == *sender {
+        return Err(ContractError::InvalidReferrer {});
+    }
+
+    Ok((referrer, config))
+}
+
+/// Counts a use of the voucher matching `code`, returning its discount. Only its redeemer can
+/// use it, or without a redeemer, a sender that committed to it in an earlier block.
+fn use_voucher(
+    deps: DepsMut,
+    env: &Env,
+    code: &str,
+    sender: &Addr,
+) -> Result<Decimal, ContractError> {
+    let code_hash = HexBinary::from(Sha256::digest(code.as_bytes()).as_slice()).to_hex();
+    let mut voucher = VOUCHERS
+        .may_load(deps.storage, &code_hash)?
+        .ok_or(ContractError::InvalidVoucher {})?;
+    match &voucher.redeemer {
+        Some(redeemer) if redeemer != sender => {
+            return Err(ContractError::VoucherNotRedeemer {});
+        }
+        Some(_) => {}
+        None => {
+            let commitment = voucher_commitment(code, sender.as_str());
+            match VOUCHER_COMMITMENTS.may_load(deps.storage, &commitment)? {
+                Some(height) if height < env.block.height => {
+                    VOUCHER_COMMITMENTS.remove(deps.storage, &commitment);
+                }
+                _ => return Err(ContractError::VoucherNotCommitted {}),
+            }
+        }
+    }
+    if voucher.uses >= voucher.max_uses {
+        return Err(ContractError::VoucherExhausted {});
+    }
+    voucher.uses += 1;
+    VOUCHERS.save(deps.storage, &code_hash, &voucher)?;
+
+    Ok(voucher.discount)
+}
+
+fn includes_address(
+    deps: Deps,
+    list: &WhitelistType,
+    address: &Addr,
+    proof: Option<&[String]>,
+) -> Result<bool, ContractError> {
+    match list {
+        WhitelistType::Contract { address: contract } => Ok(deps.querier.query_wasm_smart(
+            contract,
+            &WhitelistQueryMsg::IncludesAddress {
+                address: address.to_string(),
+            },
+        )?),
+        WhitelistType::Merkle { root } => match proof {
+            Some(proof) => Ok(merkle::verify_proof(root, address.as_bytes(), proof)?),
+            None => Ok(false),
+        },
+    }
+}
+
+/// With an admin delay, the phase must be proposed.
+pub fn execute_add_whitelist(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    whitelist: Whitelist,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    ensure_no_admin_delay(deps.storage)?;
+
+    apply_add_whitelist(deps, env, whitelist)
+}
+
+fn apply_add_whitelist(
+    deps: DepsMut,
+    env: Env,
+    whitelist: Whitelist,
+) -> Result<Response, ContractError> {
+    let valid_list = match &whitelist.list {
+        WhitelistType::Contract { address } => deps.api.addr_validate(address).is_ok(),
+        WhitelistType::Merkle { root } => merkle::is_valid_root(root),
+    };
+    if !valid_list {
+        return Err(ContractError::InvalidWhitelistType {});
+    }
+    if whitelist.start_time >= whitelist.end_time || whitelist.end_time <= env.block.time {
+        return Err(ContractError::InvalidWhitelistPeriod {});
+    }
+    if whitelist.discount.is_some_and(|d| d > Decimal::one()) {
+        return Err(ContractError::InvalidDiscount {});
+    }
+
+    let id = WHITELIST_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    WHITELIST_COUNT.save(deps.storage, &id)?;
+    WHITELISTS.save(deps.storage, id, &whitelist)?;
+
+    let event = Event::new("add-whitelist").add_attribute("id", id.to_string());
+    Ok(Response::new().add_event(event))
+}
+
+/// With an admin delay, the removal must be proposed.
+pub fn execute_remove_whitelist(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u32,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    ensure_no_admin_delay(deps.storage)?;
+
+    apply_remove_whitelist(deps, id)
+}
+
+fn apply_remove_whitelist(deps: DepsMut, id: u32) -> Result<Response, ContractError> {
+    WHITELISTS.load(deps.storage, id)?;
+    WHITELISTS.remove(deps.storage, id);
+
+    let event = Event::new("remove-whitelist").add_attribute("id", id.to_string());
+    Ok(Response::new().add_event(event))
+}
+
+/// With an admin delay, the change must be proposed.
+pub fn execute_update_referral_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    config: ReferralConfig,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    ensure_no_admin_delay(deps.storage)?;
+
+    apply_update_referral_config(deps, config)
+}
+
+fn apply_update_referral_config(
+    deps: DepsMut,
+    config: ReferralConfig,
+) -> Result<Response, ContractError> {
+    if config.fee_share > Decimal::one() || config.discount > Decimal::one() {
+        return Err(ContractError::InvalidDiscount {});
+    }
+    REFERRAL_CONFIG.save(deps.storage, &config)?;
+
+    let event = Event::new("update-referral-config")
+        .add_attribute("fee_share", config.fee_share.to_string())
+        .add_attribute("discount", config.discount.to_string());
+    Ok(Response::new().add_event(event))
+}
+
+/// Vouchers are stored by the hex encoded sha256 hash of their code, so codes aren't public.
+/// A code is revealed once it's used, so a voucher is either bound to the address that may
+/// redeem it, or must be committed to before use.
+pub fn execute_add_voucher(
+    deps: DepsMut,
+    info: MessageInfo,
+    code_hash: String,
+    discount: Decimal,
+    max_uses: u32,
+    redeemer: Option<String>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    let redeemer = redeemer.map(|r| deps.api.addr_validate(&r)).transpose()?;
+
+    let code_hash = HexBinary::from_hex(&code_hash)
+        .ok()
+        .filter(|h| h.len() == 32)
+        .ok_or(ContractError::InvalidVoucher {})?
+        .to_hex();
+    if discount > Decimal::one() {
+        return Err(ContractError::InvalidDiscount {});
+    }
+    if max_uses == 0 {
+        return Err(ContractError::InvalidVoucherUses {});
+    }
+    VOUCHERS.save(
+        deps.storage,
+        &code_hash,
+        &Voucher {
+            discount,
+            redeemer: redeemer.clone(),
+            max_uses,
+            uses: 0,
+        },
+    )?;
+
+    let mut event = Event::new("add-voucher").add_attribute("code_hash", code_hash);
+    if let Some(redeemer) = redeemer {
+        event = event.add_attribute("redeemer", redeemer);
+    }
+    Ok(Response::new().add_event(event))
 }
 
-/// Pause or unpause minting
+/// Commitments are made per (code, sender), so they don't reveal the code. Using the voucher
+/// consumes the commitment.
+pub fn execute_commit_voucher(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    commitment: String,
+) -> Result<Response, ContractError> {
+    let commitment = HexBinary::from_hex(&commitment)
+        .ok()
+        .filter(|h| h.len() == 32)
+        .ok_or(ContractError::InvalidVoucher {})?
+        .to_hex();
+    VOUCHER_COMMITMENTS.save(deps.storage, &commitment, &env.block.height)?;
+
+    let event = Event::new("commit-voucher")
+        .add_attribute("sender", info.sender)
+        .add_attribute("commitment", commitment);
+    Ok(Response::new().add_event(event))
+}
+
+pub fn execute_remove_voucher(
+    deps: DepsMut,
+    info: MessageInfo,
+    code_hash: String,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let code_hash = code_hash.to_lowercase();
+    VOUCHERS.load(deps.storage, &code_hash)?;
+    VOUCHERS.remove(deps.storage, &code_hash);
+
+    let event = Event::new("remove-voucher").add_attribute("code_hash", code_hash);
+    Ok(Response::new().add_event(event))
+}
+
+/// Enable internationalized accounts for the given scripts, or disable them with `None`.
+/// With an admin delay, the change must be proposed.
+pub fn execute_update_idn_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    config: Option<IdnConfig>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    ensure_no_admin_delay(deps.storage)?;
+
+    apply_update_idn_config(deps, config)
+}
+
+fn apply_update_idn_config(
+    deps: DepsMut,
+    config: Option<IdnConfig>,
+) -> Result<Response, ContractError> {
+    let mut event = Event::new("update-idn-config");
+    match config {
+        Some(config) => {
+            idn::validate_scripts(&config)?;
+            event = event
+                .add_attribute("scripts", config.scripts.join(","))
+                .add_attribute("allow_emoji", config.allow_emoji.to_string());
+            IDN_CONFIG.save(deps.storage, &config)?;
+        }
+        None => IDN_CONFIG.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_event(event))
+}
+
+/// Pause or unpause minting. With an admin delay, only pausing is immediate,
+/// unpausing must be proposed.
 pub fn execute_pause(
     deps: DepsMut,
     info: MessageInfo,
     pause: bool,
 ) -> Result<Response, ContractError> {
     cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    if !pause && ADMIN_TIMELOCK.delay(deps.storage)? > 0 {
+        return Err(ContractError::TimelockRequired {});
+    }
+
+    apply_pause(deps, pause)
+}
 
+fn apply_pause(deps: DepsMut, pause: bool) -> Result<Response, ContractError> {
     PAUSED.save(deps.storage, &pause)?;
 
     let event = Event::new("pause").add_attribute("pause", pause.to_string());
     Ok(Response::new().add_event(event))
 }
 
+/// Update the config. With an admin delay, the change must be proposed.
 pub fn execute_update_config(
     deps: DepsMut,
     info: MessageInfo,
@@ -118,7 +892,25 @@ pub fn execute_update_config(
     config: Config,
 ) -> Result<Response, ContractError> {
     cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    ensure_no_admin_delay(deps.storage)?;
 
+    apply_update_config(deps, &info.sender, env, config)
+}
+
+/// Actions listed in `AdminAction` must be proposed while there is an admin delay
+fn ensure_no_admin_delay(storage: &dyn Storage) -> Result<(), ContractError> {
+    if ADMIN_TIMELOCK.delay(storage)? > 0 {
+        return Err(ContractError::TimelockRequired {});
+    }
+    Ok(())
+}
+
+fn apply_update_config(
+    deps: DepsMut,
+    sender: &Addr,
+    env: Env,
+    config: Config,
+) -> Result<Response, ContractError> {
     let start_time = config.public_mint_start_time;
 
     // Can not set public mint time in the past
@@ -131,7 +923,86 @@ pub fn execute_update_config(
 
     CONFIG.save(deps.storage, &config)?;
 
-    let event = Event::new("update-config").add_attribute("address", info.sender.to_string());
+    let event = Event::new("update-config").add_attribute("address", sender.to_string());
+    Ok(Response::new().add_event(event))
+}
+
+/// Queue an admin action, executable once the admin delay has passed
+pub fn execute_propose_admin_action(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    action: AdminAction,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    // the public mint start time must still be ahead once the action can be executed
+    if let AdminAction::UpdateConfig { config } = &action {
+        let executable_at = env
+            .block
+            .time
+            .plus_seconds(ADMIN_TIMELOCK.delay(deps.storage)?);
+        if executable_at > config.public_mint_start_time {
+            return Err(ContractError::InvalidTradingStartTime(
+                executable_at,
+                config.public_mint_start_time,
+            ));
+        }
+    }
+    let queued = ADMIN_TIMELOCK.propose(deps.storage, env.block.time, action)?;
+
+    let event = Event::new("propose-admin-action")
+        .add_attribute("id", queued.id.to_string())
+        .add_attribute("executable_at", queued.executable_at.seconds().to_string());
+    Ok(Response::new().add_event(event))
+}
+
+pub fn execute_admin_action(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+    id: u64,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    let queued = ADMIN_TIMELOCK.take(deps.storage, id)?;
+    if !queued.is_ready(&env.block.time) {
+        return Err(ContractError::ActionNotReady {
+            executable_at: queued.executable_at,
+        });
+    }
+    let res = match queued.action {
+        AdminAction::UpdateConfig { config } => {
+            apply_update_config(deps, &info.sender, env, config)?
+        }
+        AdminAction::Pause { pause } => apply_pause(deps, pause)?,
+        AdminAction::AddWhitelist { whitelist } => apply_add_whitelist(deps, env, whitelist)?,
+        AdminAction::RemoveWhitelist { id } => apply_remove_whitelist(deps, id)?,
+        AdminAction::UpdateBlocklist { add, remove } => apply_update_blocklist(deps, add, remove)?,
+        AdminAction::UpdateReservations { root, add, remove } => {
+            apply_update_reservations(deps, root, add, remove)?
+        }
+        AdminAction::BatchMint { items } => apply_batch_mint(deps, items)?,
+        AdminAction::UpdateIdnConfig { config } => apply_update_idn_config(deps, config)?,
+        AdminAction::UpdateReferralConfig { config } => apply_update_referral_config(deps, config)?,
+        AdminAction::UpdateDelegationPolicy { policy } => {
+            apply_update_delegation_policy(deps, policy)?
+        }
+    };
+
+    Ok(res.add_event(Event::new("execute-admin-action").add_attribute("id", id.to_string())))
+}
+
+pub fn execute_cancel_admin_action(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+
+    ADMIN_TIMELOCK.take(deps.storage, id)?;
+
+    let event = Event::new("cancel-admin-action").add_attribute("id", id.to_string());
     Ok(Response::new().add_event(event))
 }
 
@@ -160,25 +1031,16 @@ pub fn validate_account(account: &str, min: u32, max: u32) -> Result<(), Contrac
 
     Ok(())
 }
-// This follows the same rules as Internet domain accounts
 pub fn validate_staking(
     deps: Deps,
-    delegator: &str,
-    account_len: usize,
-    base_delegation: Uint128,
+    env: &Env,
+    delegator: &Addr,
+    expected: Uint128,
 ) -> Result<(), ContractError> {
-    let sum = deps
-        .querier
-        .query_all_delegations(delegator)?
-        .into_iter()
-        .map(|d| d.amount.amount)
-        .sum::<Uint128>();
-    let expected = match account_len {
-        // never 3 or less, already checked earlier
-        3 => base_delegation * Uint128::new(5u128),
-        4 => base_delegation * Uint128::new(3u128),
-        _ => base_delegation,
-    };
+    let (sum, held) = counted_delegation(deps, env, delegator)?;
+    if !held {
+        return Err(ContractError::DelegationNotHeld {});
+    }
     if sum < expected {
         return Err(ContractError::IncorrectDelegation {
             got: sum.u128(),
@@ -188,6 +1050,227 @@ pub fn validate_staking(
     Ok(())
 }
 
+/// Delegation counted toward minting, and whether it was held for the holding period.
+/// With a holding period, this is the registered snapshot capped by the current delegation.
+fn counted_delegation(deps: Deps, env: &Env, delegator: &Addr) -> StdResult<(Uint128, bool)> {
+    let policy = DELEGATION_POLICY.may_load(deps.storage)?;
+    let current = delegated(deps, policy.as_ref(), delegator)?;
+    let Some(holding_period) = policy.and_then(|p| p.holding_period) else {
+        return Ok((current, true));
+    };
+    match DELEGATION_SNAPSHOTS.may_load(deps.storage, delegator)? {
+        Some(snapshot) if snapshot.time.plus_seconds(holding_period) <= env.block.time => {
+            Ok((snapshot.amount.min(current), true))
+        }
+        _ => Ok((current, false)),
+    }
+}
+
+/// Current delegation to the validators allowed by the policy
+fn delegated(
+    deps: Deps,
+    policy: Option<&DelegationPolicy>,
+    delegator: &Addr,
+) -> StdResult<Uint128> {
+    let delegations = deps.querier.query_all_delegations(delegator)?;
+    let Some(policy) = policy else {
+        return Ok(delegations.into_iter().map(|d| d.amount.amount).sum());
+    };
+    let active_set = match policy.active_set_only {
+        true => Some(deps.querier.query_all_validators()?),
+        false => None,
+    };
+    Ok(delegations
+        .into_iter()
+        .filter(|d| policy.allows(&d.validator))
+        .filter(|d| match &active_set {
+            Some(validators) => validators.iter().any(|v| v.address == d.validator),
+            None => true,
+        })
+        .map(|d| d.amount.amount)
+        .sum())
+}
+
+/// Set which delegations count toward minting, or count all of them with `None`.
+/// With an admin delay, the change must be proposed.
+pub fn execute_update_delegation_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    policy: Option<DelegationPolicy>,
+) -> Result<Response, ContractError> {
+    cw_ownable::assert_owner(deps.storage, &info.sender)?;
+    ensure_no_admin_delay(deps.storage)?;
+
+    apply_update_delegation_policy(deps, policy)
+}
+
+fn apply_update_delegation_policy(
+    deps: DepsMut,
+    policy: Option<DelegationPolicy>,
+) -> Result<Response, ContractError> {
+    let mut event = Event::new("update-delegation-policy");
+    match policy {
+        Some(policy) => {
+            if policy.validators.as_ref().is_some_and(|v| v.is_empty()) {
+                return Err(ContractError::InvalidDelegationPolicy {});
+            }
+            if let Some(holding_period) = policy.holding_period {
+                event = event.add_attribute("holding_period", holding_period.to_string());
+            }
+            if let Some(validators) = &policy.validators {
+                event = event.add_attribute("validators", validators.join(","));
+            }
+            event = event.add_attribute("active_set_only", policy.active_set_only.to_string());
+            DELEGATION_POLICY.save(deps.storage, &policy)?;
+        }
+        None => DELEGATION_POLICY.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_event(event))
+}
+
+/// Snapshot the sender's delegation. Lowering it keeps the holding time,
+/// raising it restarts the holding period.
+pub fn execute_register_delegation(
+    deps: DepsMut,
+    info: MessageInfo,
+    env: Env,
+) -> Result<Response, ContractError> {
+    let policy = DELEGATION_POLICY.may_load(deps.storage)?;
+    if policy.as_ref().and_then(|p| p.holding_period).is_none() {
+        return Err(ContractError::HoldingPeriodDisabled {});
+    }
+
+    let amount = delegated(deps.as_ref(), policy.as_ref(), &info.sender)?;
+    let time = match DELEGATION_SNAPSHOTS.may_load(deps.storage, &info.sender)? {
+        Some(snapshot) if amount <= snapshot.amount => snapshot.time,
+        _ => env.block.time,
+    };
+    DELEGATION_SNAPSHOTS.save(
+        deps.storage,
+        &info.sender,
+        &DelegationSnapshot { amount, time },
+    )?;
+
+    let event = Event::new("register-delegation")
+        .add_attribute("address", info.sender)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("held_since", time.seconds().to_string());
+    Ok(Response::new().add_event(event))
+}
+
+/// Drops the delegation record and flag of an earlier token with the same account, which was burned
+fn clear_mint_record(storage: &mut dyn Storage, account: &str) {
+    MINT_DELEGATIONS.remove(storage, account);
+    FLAGGED_ACCOUNTS.remove(storage, account);
+}
+
+/// Current owner of a minted account
+fn account_owner(deps: Deps, account: &str) -> StdResult<String> {
+    let collection = ACCOUNT_COLLECTION.load(deps.storage)?;
+    Ok(Bs721Account(collection)
+        .owner_of(&deps.querier, account, false)?
+        .owner)
+}
+
+/// Locks or unlocks an account in the collection
+fn lock_account(deps: Deps, account: &str, locked: bool) -> StdResult<CosmosMsg> {
+    let collection = Bs721Account(ACCOUNT_COLLECTION.load(deps.storage)?);
+    let msg: bs721_account::msg::ExecuteMsg<Metadata> = bs721_account::msg::ExecuteMsg::Lock {
+        token_id: account.to_string(),
+        locked,
+    };
+    collection.call(msg)
+}
+
+/// Anyone can flag an account whose delegator's stake, counted with the policy it was minted
+/// under, dropped below the delegation it was minted on. The account must still be owned by
+/// the address it was minted to: once it changes hands, the minter's stake no longer matters.
+/// The account is locked in the collection, so it can't be transferred or sold until the flag
+/// is cleared. The delegator's snapshot is revoked too, so it must register again and wait
+/// another holding period to mint.
+pub fn execute_challenge(
+    deps: DepsMut,
+    env: Env,
+    account: String,
+) -> Result<Response, ContractError> {
+    if FLAGGED_ACCOUNTS.has(deps.storage, &account) {
+        return Err(ContractError::AccountFlagged {});
+    }
+    let minted = MINT_DELEGATIONS
+        .may_load(deps.storage, &account)?
+        .ok_or(ContractError::ChallengeFailed {})?;
+    if account_owner(deps.as_ref(), &account)? != minted.owner.as_str() {
+        return Err(ContractError::ChallengeFailed {});
+    }
+    let current = delegated(deps.as_ref(), minted.policy.as_ref(), &minted.delegator)?;
+    if current >= minted.amount {
+        return Err(ContractError::ChallengeFailed {});
+    }
+
+    FLAGGED_ACCOUNTS.save(
+        deps.storage,
+        &account,
+        &FlaggedAccount {
+            delegator: minted.delegator.clone(),
+            required: minted.amount,
+            delegation: current,
+            time: env.block.time,
+        },
+    )?;
+    DELEGATION_SNAPSHOTS.remove(deps.storage, &minted.delegator);
+
+    let event = Event::new("challenge")
+        .add_attribute("account", &account)
+        .add_attribute("delegator", minted.delegator)
+        .add_attribute("required", minted.amount.to_string())
+        .add_attribute("delegation", current.to_string());
+    Ok(Response::new()
+        .add_message(lock_account(deps.as_ref(), &account, true)?)
+        .add_event(event))
+}
+
+/// Anyone can clear the flag of an account, unlocking it, once its delegator's stake is back
+/// to the delegation it was minted on, counted with the same policy. The flag is also cleared
+/// once the account changed hands, by a sale accepted before the challenge.
+pub fn execute_clear_flag(deps: DepsMut, account: String) -> Result<Response, ContractError> {
+    let flagged = FLAGGED_ACCOUNTS.load(deps.storage, &account)?;
+    let minted = MINT_DELEGATIONS.load(deps.storage, &account)?;
+    let current = delegated(deps.as_ref(), minted.policy.as_ref(), &flagged.delegator)?;
+    let sold = account_owner(deps.as_ref(), &account)? != minted.owner.as_str();
+    if current < flagged.required && !sold {
+        return Err(ContractError::IncorrectDelegation {
+            got: current.u128(),
+            expected: flagged.required.u128(),
+        });
+    }
+    FLAGGED_ACCOUNTS.remove(deps.storage, &account);
+    if sold {
+        MINT_DELEGATIONS.remove(deps.storage, &account);
+    }
+
+    let event = Event::new("clear-flag")
+        .add_attribute("account", &account)
+        .add_attribute("delegation", current.to_string());
+    Ok(Response::new()
+        .add_message(lock_account(deps.as_ref(), &account, false)?)
+        .add_event(event))
+}
+
+/// Whether the collection has a token for `account`
+pub fn is_minted(deps: Deps, account: &str) -> StdResult<bool> {
+    let collection = ACCOUNT_COLLECTION.load(deps.storage)?;
+    Ok(deps
+        .querier
+        .query_wasm_smart::<String>(
+            collection,
+            &Bs721AccountsQueryMsg::AssociatedAddress {
+                account: account.to_string(),
+            },
+        )
+        .is_ok())
+}
+
 pub fn execute_update_owner(
     deps: DepsMut,
     info: MessageInfo,
@@ -203,21 +1286,14 @@ pub enum Discount {
 }
 
 pub fn validate_payment(
-    account_len: usize,
+    price: Uint128,
     info: &MessageInfo,
-    base_price: u128,
-    // discount: Option<Discount>,
+    discount: Option<Discount>,
 ) -> Result<Option<Coin>, ContractError> {
-    // Because we know we are left with ASCII chars, a simple byte count is enough
-    let amount: Uint128 = (match account_len {
-        0..=2 => {
-            return Err(ContractError::AccountTooShort {});
-        }
-        3 => base_price * 100,
-        4 => base_price * 10,
-        _ => base_price,
-    })
-    .into();
+    let amount = match discount {
+        Some(Discount::Percent(percent)) => price.mul_floor(Decimal::one() - percent),
+        None => price,
+    };
 
     if amount.is_zero() {
         return Ok(None);
@@ -251,6 +1327,186 @@ pub fn query_config(deps: Deps) -> StdResult<Config> {
     CONFIG.load(deps.storage)
 }
 
+pub fn query_whitelists(deps: Deps) -> StdResult<Vec<WhitelistResponse>> {
+    WHITELISTS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(id, whitelist)| WhitelistResponse { id, whitelist }))
+        .collect()
+}
+
+pub fn query_reserved_accounts(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<String>> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    RESERVED_ACCOUNTS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+pub fn query_is_blocked(deps: Deps, account: &str) -> StdResult<Option<BlockRule>> {
+    let idn_config = IDN_CONFIG.may_load(deps.storage)?;
+    let (account, _) = normalized_account(idn_config.as_ref(), account);
+    blocked_by(deps, &account, idn_config.is_some())
+}
+
+pub fn query_blocklist(
+    deps: Deps,
+    start_after: Option<BlockRule>,
+    limit: Option<u32>,
+) -> StdResult<Vec<BlockRule>> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let (names_start, patterns_start) = match &start_after {
+        None => (None, None),
+        Some(BlockRule::Name(name)) => (Some(Bound::exclusive(name.as_str())), None),
+        Some(BlockRule::Pattern(pattern)) => (None, Some(Bound::exclusive(pattern.as_str()))),
+    };
+
+    let names = match start_after {
+        Some(BlockRule::Pattern(_)) => vec![],
+        _ => BLOCKED_NAMES
+            .keys(deps.storage, names_start, None, Order::Ascending)
+            .take(limit)
+            .map(|name| name.map(BlockRule::Name))
+            .collect::<StdResult<Vec<_>>>()?,
+    };
+    let patterns = BLOCKED_PATTERNS
+        .keys(deps.storage, patterns_start, None, Order::Ascending)
+        .take(limit - names.len())
+        .map(|pattern| pattern.map(BlockRule::Pattern));
+
+    names.into_iter().map(Ok).chain(patterns).collect()
+}
+
+pub fn query_referral(deps: Deps, address: Addr) -> StdResult<ReferralStats> {
+    Ok(REFERRALS
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default())
+}
+
+pub fn query_voucher(deps: Deps, code_hash: String) -> StdResult<Option<Voucher>> {
+    VOUCHERS.may_load(deps.storage, &code_hash.to_lowercase())
+}
+
+/// The account as it would be minted, and its length for pricing
+fn normalized_account(idn_config: Option<&IdnConfig>, account: &str) -> (String, usize) {
+    let account = match idn_config {
+        Some(_) => idn::normalize(account),
+        None => account.to_string(),
+    };
+    let account_len = match account.is_ascii() {
+        true => account.len(),
+        false => idn::grapheme_len(&account),
+    };
+    (account, account_len)
+}
+
+pub fn query_mint_price(deps: Deps, env: Env, account: &str) -> StdResult<MintPriceResponse> {
+    let idn_config = IDN_CONFIG.may_load(deps.storage)?;
+    let (account, account_len) = normalized_account(idn_config.as_ref(), account);
+    mint_price(deps, &env, &account, account_len)
+}
+
+/// Runs the checks of `MintAndList` without minting, reporting every failed check.
+/// Referrals and vouchers are not checked.
+pub fn query_can_mint(
+    deps: Deps,
+    env: Env,
+    account: &str,
+    minter: Addr,
+    proof: Option<Vec<String>>,
+) -> StdResult<CanMintResponse> {
+    let idn_config = IDN_CONFIG.may_load(deps.storage)?;
+    let params = SUDO_PARAMS.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
+    let (account, account_len) = normalized_account(idn_config.as_ref(), account);
+    let mut errors = vec![];
+
+    let paused = PAUSED.load(deps.storage)?;
+    if paused {
+        errors.push(MintError::Paused);
+    }
+    // before public minting, the minter must be able to mint in an active whitelist phase
+    let started = env.block.time >= config.public_mint_start_time;
+    if !started {
+        match whitelist_phase(deps, &env, &minter, proof.as_deref()) {
+            Ok(_) => {}
+            Err(ContractError::MintingNotStarted {}) => errors.push(MintError::NotStarted),
+            Err(ContractError::NotWhitelisted {}) => errors.push(MintError::NotWhitelisted),
+            Err(ContractError::WhitelistLimitReached {}) => {
+                errors.push(MintError::WhitelistLimitReached)
+            }
+            Err(err) => return Err(StdError::generic_err(err.to_string())),
+        }
+    }
+
+    let (min, max) = (params.min_account_length, params.max_account_length);
+    let valid = match &idn_config {
+        Some(idn_config) => idn::validate_idn_account(&account, idn_config, min, max),
+        None => validate_account(&account, min, max),
+    };
+    match valid {
+        Ok(()) => {}
+        Err(ContractError::AccountTooShort {}) => errors.push(MintError::TooShort),
+        Err(ContractError::AccountTooLong {}) => errors.push(MintError::TooLong),
+        Err(_) => errors.push(MintError::InvalidCharacters),
+    }
+    if idn_config.is_some() {
+        if let Some(existing) = idn::find_confusable(deps, &account)? {
+            errors.push(MintError::Confusable { existing });
+        }
+    }
+    if RESERVED_ACCOUNTS.has(deps.storage, &account) {
+        errors.push(MintError::Reserved);
+    }
+    if blocked_by(deps, &account, idn_config.is_some())?.is_some() {
+        errors.push(MintError::Blocked);
+    }
+    let taken = is_minted(deps, &account)?;
+    if taken {
+        errors.push(MintError::Taken);
+    }
+
+    let MintPriceResponse {
+        price,
+        delegation: required_delegation,
+    } = mint_price(deps, &env, &account, account_len)?;
+    let (delegation, held) = counted_delegation(deps, &env, &minter)?;
+    if !held {
+        errors.push(MintError::DelegationNotHeld);
+    }
+    if delegation < required_delegation {
+        errors.push(MintError::InsufficientDelegation);
+    }
+
+    Ok(CanMintResponse {
+        can_mint: errors.is_empty(),
+        account,
+        price,
+        required_delegation,
+        delegation,
+        taken,
+        paused,
+        started,
+        errors,
+    })
+}
+
+/// The minted account `account` would be confused with in IDN mode
+pub fn query_confusable(deps: Deps, account: &str) -> StdResult<Option<String>> {
+    idn::find_confusable(deps, &idn::normalize(account))
+}
+
+pub fn query_whitelist_mints(deps: Deps, id: u32, address: Addr) -> StdResult<u32> {
+    Ok(WHITELIST_MINTS
+        .may_load(deps.storage, (id, &address))?
+        .unwrap_or_default())
+}
+
 pub fn sudo_update_params(
     deps: DepsMut,
     min_account_length: u32,
@@ -258,6 +1514,7 @@ pub fn sudo_update_params(
     base_price: Uint128,
     base_delegation: Uint128,
 ) -> Result<Response, ContractError> {
+    let pricing = SUDO_PARAMS.load(deps.storage)?.pricing;
     SUDO_PARAMS.save(
         deps.storage,
         &SudoParams {
@@ -265,12 +1522,68 @@ pub fn sudo_update_params(
             max_account_length,
             base_price,
             base_delegation,
+            pricing,
         },
     )?;
 
     Ok(Response::new().add_attribute("action", "sudo_update_params"))
 }
 
+pub fn sudo_update_pricing(
+    deps: DepsMut,
+    pricing: PricingSchedule,
+) -> Result<Response, ContractError> {
+    if !pricing.is_valid() {
+        return Err(ContractError::InvalidPricing {});
+    }
+    SUDO_PARAMS.update(deps.storage, |mut params| -> StdResult<_> {
+        params.pricing = pricing;
+        Ok(params)
+    })?;
+
+    Ok(Response::new().add_attribute("action", "sudo_update_pricing"))
+}
+
+/// Premium words are priced with the schedule's `word_premium`
+pub fn sudo_update_premium_words(
+    deps: DepsMut,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    for word in &add {
+        PREMIUM_WORDS.save(deps.storage, word, &Empty {})?;
+    }
+    for word in &remove {
+        PREMIUM_WORDS.remove(deps.storage, word);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "sudo_update_premium_words")
+        .add_attribute("added", add.len().to_string())
+        .add_attribute("removed", remove.len().to_string()))
+}
+
+pub fn sudo_update_admin_delay(deps: DepsMut, delay: u64) -> Result<Response, ContractError> {
+    ADMIN_TIMELOCK.set_delay(deps.storage, delay)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "sudo_update_admin_delay")
+        .add_attribute("delay", delay.to_string()))
+}
+
+pub fn sudo_update_blocklist(
+    deps: DepsMut,
+    add: Vec<BlockRule>,
+    remove: Vec<BlockRule>,
+) -> Result<Response, ContractError> {
+    update_blocklist(deps, &add, &remove)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "sudo_update_blocklist")
+        .add_attribute("added", add.len().to_string())
+        .add_attribute("removed", remove.len().to_string()))
+}
+
 pub fn sudo_update_account_collection(
     deps: DepsMut,
     collection: Addr,
@@ -1,17 +1,21 @@
-use btsg_account::market::MigrateMsg;
 use btsg_account::minter::{Config, SudoParams};
 use cosmwasm_std::{
     instantiate2_address, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response,
-    StdError, StdResult, WasmMsg,
+    StdError, StdResult, Storage, WasmMsg,
 };
 use cw2::set_contract_version;
+use semver::Version;
 
 // use cw2::set_contract_version;
 
 use crate::commands::*;
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, SudoMsg};
-use crate::state::{ACCOUNT_COLLECTION, ACCOUNT_MARKETPLACE, CONFIG, PAUSED, SUDO_PARAMS};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg};
+use crate::state::{
+    ACCOUNT_COLLECTION, ACCOUNT_MARKETPLACE, ADMIN_TIMELOCK, CONFIG, DELEGATION_POLICY,
+    DELEGATION_SNAPSHOTS, FLAGGED_ACCOUNTS, IDN_CONFIG, PAUSED, REFERRAL_CONFIG, RESERVATION_ROOT,
+    SUDO_PARAMS,
+};
 
 // version info for migration info
 pub const ACCOUNT_MINTER: &str = "crates.io:bs721-account-minter";
@@ -34,6 +38,10 @@ pub fn instantiate(
 
     PAUSED.save(deps.storage, &false)?;
 
+    if msg.pricing.as_ref().is_some_and(|p| !p.is_valid()) {
+        return Err(ContractError::InvalidPricing {});
+    }
+
     let marketplace = deps.api.addr_validate(&msg.marketplace_addr)?;
     ACCOUNT_MARKETPLACE.save(deps.storage, &marketplace)?;
 
@@ -44,6 +52,7 @@ pub fn instantiate(
             max_account_length: msg.max_account_length,
             base_price: msg.base_price,
             base_delegation: msg.base_delegation,
+            pricing: msg.pricing.unwrap_or_default(),
         },
     )?;
 
@@ -100,22 +109,124 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::MintAndList { account } => {
-            execute_mint_and_list(deps, info, env, account.trim())
-        }
+        ExecuteMsg::MintAndList {
+            account,
+            proof,
+            referrer,
+            voucher,
+            owner,
+            initial,
+            associate,
+        } => execute_mint_and_list(
+            deps,
+            info,
+            env,
+            account.trim(),
+            MintOptions {
+                proof,
+                referrer,
+                voucher,
+                owner,
+                initial,
+                associate,
+            },
+        ),
         ExecuteMsg::UpdateOwnership(action) => execute_update_owner(deps, info, env, action),
         ExecuteMsg::Pause { pause } => execute_pause(deps, info, pause),
         ExecuteMsg::UpdateConfig { config } => execute_update_config(deps, info, env, config),
+        ExecuteMsg::ProposeAdminAction { action } => {
+            execute_propose_admin_action(deps, info, env, action)
+        }
+        ExecuteMsg::ExecuteAdminAction { id } => execute_admin_action(deps, info, env, id),
+        ExecuteMsg::CancelAdminAction { id } => execute_cancel_admin_action(deps, info, id),
+        ExecuteMsg::ClaimReserved { account, proof } => {
+            execute_claim_reserved(deps, info, account.trim(), proof)
+        }
+        ExecuteMsg::UpdateReservations { root, add, remove } => {
+            execute_update_reservations(deps, info, root, add, remove)
+        }
+        ExecuteMsg::UpdateIdnConfig { config } => execute_update_idn_config(deps, info, config),
+        ExecuteMsg::UpdateReferralConfig { config } => {
+            execute_update_referral_config(deps, info, config)
+        }
+        ExecuteMsg::AddVoucher {
+            code_hash,
+            discount,
+            max_uses,
+            redeemer,
+        } => execute_add_voucher(deps, info, code_hash, discount, max_uses, redeemer),
+        ExecuteMsg::RemoveVoucher { code_hash } => execute_remove_voucher(deps, info, code_hash),
+        ExecuteMsg::CommitVoucher { commitment } => {
+            execute_commit_voucher(deps, info, env, commitment)
+        }
+        ExecuteMsg::AddWhitelist { whitelist } => execute_add_whitelist(deps, info, env, whitelist),
+        ExecuteMsg::RemoveWhitelist { id } => execute_remove_whitelist(deps, info, id),
+        ExecuteMsg::UpdateDelegationPolicy { policy } => {
+            execute_update_delegation_policy(deps, info, policy)
+        }
+        ExecuteMsg::RegisterDelegation {} => execute_register_delegation(deps, info, env),
+        ExecuteMsg::Challenge { account } => execute_challenge(deps, env, account),
+        ExecuteMsg::ClearFlag { account } => execute_clear_flag(deps, account),
+        ExecuteMsg::BatchMint { items } => execute_batch_mint(deps, info, items),
+        ExecuteMsg::UpdateBlocklist { add, remove } => {
+            execute_update_blocklist(deps, info, add, remove)
+        }
     }
 }
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Ownership {} => to_json_binary(&cw_ownable::get_ownership(deps.storage)?),
         QueryMsg::Collection {} => to_json_binary(&query_collection(deps)?),
         QueryMsg::Params {} => to_json_binary(&query_params(deps)?),
         QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::MintPrice { account } => {
+            to_json_binary(&query_mint_price(deps, env, account.trim())?)
+        }
+        QueryMsg::CanMint {
+            account,
+            minter,
+            proof,
+        } => to_json_binary(&query_can_mint(
+            deps,
+            env,
+            account.trim(),
+            deps.api.addr_validate(&minter)?,
+            proof,
+        )?),
+        QueryMsg::IdnConfig {} => to_json_binary(&IDN_CONFIG.may_load(deps.storage)?),
+        QueryMsg::Confusable { account } => to_json_binary(&query_confusable(deps, &account)?),
+        QueryMsg::ReferralConfig {} => to_json_binary(&REFERRAL_CONFIG.may_load(deps.storage)?),
+        QueryMsg::Referral { address } => {
+            to_json_binary(&query_referral(deps, deps.api.addr_validate(&address)?)?)
+        }
+        QueryMsg::Voucher { code_hash } => to_json_binary(&query_voucher(deps, code_hash)?),
+        QueryMsg::ReservationRoot {} => to_json_binary(&RESERVATION_ROOT.may_load(deps.storage)?),
+        QueryMsg::ReservedAccounts { start_after, limit } => {
+            to_json_binary(&query_reserved_accounts(deps, start_after, limit)?)
+        }
+        QueryMsg::IsBlocked { account } => to_json_binary(&query_is_blocked(deps, account.trim())?),
+        QueryMsg::Blocklist { start_after, limit } => {
+            to_json_binary(&query_blocklist(deps, start_after, limit)?)
+        }
+        QueryMsg::DelegationPolicy {} => to_json_binary(&DELEGATION_POLICY.may_load(deps.storage)?),
+        QueryMsg::DelegationSnapshot { address } => to_json_binary(
+            &DELEGATION_SNAPSHOTS.may_load(deps.storage, &deps.api.addr_validate(&address)?)?,
+        ),
+        QueryMsg::Flagged { account } => {
+            to_json_binary(&FLAGGED_ACCOUNTS.may_load(deps.storage, &account)?)
+        }
+        QueryMsg::AdminDelay {} => to_json_binary(&ADMIN_TIMELOCK.delay(deps.storage)?),
+        QueryMsg::AdminActions { start_after, limit } => {
+            to_json_binary(&ADMIN_TIMELOCK.query(deps.storage, start_after, limit)?)
+        }
+        QueryMsg::Whitelists {} => to_json_binary(&query_whitelists(deps)?),
+        QueryMsg::WhitelistMints { id, address } => to_json_binary(&query_whitelist_mints(
+            deps,
+            id,
+            deps.api.addr_validate(&address)?,
+        )?),
     }
 }
 
@@ -143,27 +254,68 @@ pub fn sudo(deps: DepsMut, _env: Env, msg: SudoMsg) -> Result<Response, Contract
         SudoMsg::UpdateAccountMarketplace { marketplace } => {
             sudo_update_account_marketplace(deps, api.addr_validate(&marketplace)?)
         }
+        SudoMsg::UpdatePricing { pricing } => sudo_update_pricing(deps, pricing),
+        SudoMsg::UpdatePremiumWords { add, remove } => sudo_update_premium_words(deps, add, remove),
+        SudoMsg::UpdateBlocklist { add, remove } => sudo_update_blocklist(deps, add, remove),
+        SudoMsg::UpdateAdminDelay { delay } => sudo_update_admin_delay(deps, delay),
     }
 }
 
 #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(mut deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
     let then = cw2::get_contract_version(deps.storage)?;
-    if then.version >= CONTRACT_VERSION.to_owned() || then.contract != ACCOUNT_MINTER.to_owned() {
-        return Err(ContractError::Std(StdError::generic_err(
-            "unable to migrate bs721-account minter.",
-        )));
+    if then.contract != ACCOUNT_MINTER {
+        return Err(ContractError::InvalidMigrationContract {
+            contract: then.contract,
+        });
+    }
+    let from = parse_version(&then.version)?;
+    if from >= parse_version(CONTRACT_VERSION)? {
+        return Err(ContractError::InvalidMigrationVersion {
+            from: then.version,
+            to: CONTRACT_VERSION.to_string(),
+        });
     }
     cw2::set_contract_version(deps.storage, ACCOUNT_MINTER, CONTRACT_VERSION)?;
-    Ok(Response::default())
+
+    if from < Version::new(0, 3, 0) {
+        migrate_v0_3(deps.storage)?;
+    }
+
+    let mut res = Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from", then.version)
+        .add_attribute("to", CONTRACT_VERSION);
+    if let Some(pricing) = msg.pricing {
+        res = res.add_attributes(sudo_update_pricing(deps.branch(), pricing)?.attributes);
+    }
+    if let Some(delay) = msg.admin_delay {
+        res = res.add_attributes(sudo_update_admin_delay(deps.branch(), delay)?.attributes);
+    }
+    Ok(res)
+}
+
+fn parse_version(version: &str) -> StdResult<Version> {
+    version
+        .parse()
+        .map_err(|err| StdError::parse_err("semver::Version", err))
+}
+
+/// Params stored before the pricing schedule existed are read with the default schedule,
+/// which matches the fixed prices of earlier versions. Store it explicitly.
+fn migrate_v0_3(storage: &mut dyn Storage) -> StdResult<()> {
+    let params = SUDO_PARAMS.load(storage)?;
+    SUDO_PARAMS.save(storage, &params)
 }
 
 #[cfg(test)]
 mod tests {
     use btsg_account::CURRENT_BASE_PRICE;
-    use cosmwasm_std::{coin, Addr, MessageInfo};
+    use cosmwasm_std::{coin, Addr, Decimal, MessageInfo, Uint128};
 
-    use crate::commands::{validate_account, validate_payment};
+    use crate::commands::{validate_account, validate_payment, Discount};
+    use crate::idn::{grapheme_len, normalize, skeleton, validate_idn_account};
+    use btsg_account::minter::{BlockRule, DutchAuction, IdnConfig, PricingSchedule};
 
     #[test]
     fn check_validate_account() {
@@ -197,47 +349,180 @@ mod tests {
         assert!(validate_account("bobo.stars", min, max).is_err());
     }
 
+    #[test]
+    fn check_validate_idn_account() {
+        let min = 3;
+        let max = 63;
+        let config = IdnConfig {
+            scripts: vec![
+                "Latin".to_string(),
+                "Han".to_string(),
+                "Hiragana".to_string(),
+                "Katakana".to_string(),
+                "Hangul".to_string(),
+            ],
+            allow_emoji: true,
+        };
+        assert!(validate_idn_account("bobo", &config, min, max).is_ok());
+        assert!(validate_idn_account("bo--bo", &config, min, max).is_err());
+        assert!(validate_idn_account("café", &config, min, max).is_ok());
+        assert!(validate_idn_account("ミュージック", &config, min, max).is_ok());
+        assert!(validate_idn_account("音楽家", &config, min, max).is_ok());
+        assert!(validate_idn_account("음악가", &config, min, max).is_ok());
+        assert!(validate_idn_account("🎵🎸🎤", &config, min, max).is_ok());
+        // too short in graphemes, although longer in bytes
+        assert!(validate_idn_account("音楽", &config, min, max).is_err());
+        // script not allowed
+        assert!(validate_idn_account("музыка", &config, min, max).is_err());
+        // punctuation and spaces
+        assert!(validate_idn_account("café bar", &config, min, max).is_err());
+        assert!(validate_idn_account("音楽家!", &config, min, max).is_err());
+        // must be normalized first
+        assert!(validate_idn_account("cafe\u{301}", &config, min, max).is_err());
+        assert!(validate_idn_account(&normalize("Cafe\u{301}"), &config, min, max).is_ok());
+
+        let no_emoji = IdnConfig {
+            allow_emoji: false,
+            ..config
+        };
+        assert!(validate_idn_account("🎵🎸🎤", &no_emoji, min, max).is_err());
+
+        assert_eq!(normalize("CAFE\u{301}"), "café");
+        assert_eq!(grapheme_len("café"), 4);
+        assert_eq!(grapheme_len("👩\u{200d}🎤abc"), 4);
+        assert_eq!(skeleton("\u{430}pple"), skeleton("apple"));
+        assert_eq!(skeleton("b0b"), skeleton("bob"));
+    }
+
     #[test]
     fn check_validate_payment() {
         let base_price = CURRENT_BASE_PRICE as u128;
+        let pricing = PricingSchedule::default();
+
+        for (account, multiplier) in [("bobob", 1), ("bobo", 10), ("bob", 100)] {
+            let price = pricing.price(base_price.into(), account, account.len(), false, None);
+            let info = MessageInfo {
+                sender: Addr::unchecked("sender"),
+                funds: vec![coin(base_price * multiplier, "ubtsg")],
+            };
+            assert_eq!(
+                validate_payment(price, &info, None)
+                    .unwrap()
+                    .unwrap()
+                    .amount
+                    .u128(),
+                base_price * multiplier
+            );
+        }
 
         let info = MessageInfo {
             sender: Addr::unchecked("sender"),
-            funds: vec![coin(base_price, "ubtsg")],
+            funds: vec![coin(base_price / 4, "ubtsg")],
         };
         assert_eq!(
-            validate_payment(5, &info, base_price)
-                .unwrap()
-                .unwrap()
-                .amount
-                .u128(),
-            base_price
+            validate_payment(
+                base_price.into(),
+                &info,
+                Some(Discount::Percent(Decimal::percent(75)))
+            )
+            .unwrap()
+            .unwrap()
+            .amount
+            .u128(),
+            base_price / 4
         );
+        assert!(validate_payment(
+            base_price.into(),
+            &info,
+            Some(Discount::Percent(Decimal::one()))
+        )
+        .unwrap()
+        .is_none());
+        assert!(validate_payment(base_price.into(), &info, None).is_err());
+    }
 
-        let info = MessageInfo {
-            sender: Addr::unchecked("sender"),
-            funds: vec![coin(base_price * 10, "ubtsg")],
+    #[test]
+    fn check_pricing_schedule() {
+        let base = Uint128::new(1_000);
+        let pricing = PricingSchedule {
+            word_premium: Decimal::percent(500),
+            numeric_discount: Some(Decimal::percent(50)),
+            dutch_auction: Some(DutchAuction {
+                start_multiplier: Decimal::percent(300),
+                duration: 100,
+            }),
+            ..PricingSchedule::default()
         };
+        assert!(pricing.is_valid());
+
+        assert_eq!(pricing.delegation(base, 3), Uint128::new(5_000));
+        assert_eq!(pricing.delegation(base, 4), Uint128::new(3_000));
+        assert_eq!(pricing.delegation(base, 5), base);
+
+        assert_eq!(pricing.price(base, "music", 5, false, None), base);
+        assert_eq!(
+            pricing.price(base, "music", 5, true, None),
+            Uint128::new(5_000)
+        );
         assert_eq!(
-            validate_payment(4, &info, base_price)
-                .unwrap()
-                .unwrap()
-                .amount
-                .u128(),
-            base_price * 10
+            pricing.price(base, "12345", 5, false, None),
+            Uint128::new(500)
+        );
+        assert_eq!(
+            pricing.price(base, "123", 3, false, None),
+            Uint128::new(50_000)
         );
 
-        let info = MessageInfo {
-            sender: Addr::unchecked("sender"),
-            funds: vec![coin(base_price * 100, "ubtsg")],
-        };
+        // 3x at launch, decaying to 1x after 100 seconds
         assert_eq!(
-            validate_payment(3, &info, base_price)
-                .unwrap()
-                .unwrap()
-                .amount
-                .u128(),
-            base_price * 100
+            pricing.price(base, "music", 5, false, Some(0)),
+            Uint128::new(3_000)
         );
+        assert_eq!(
+            pricing.price(base, "music", 5, false, Some(50)),
+            Uint128::new(2_000)
+        );
+        assert_eq!(pricing.price(base, "music", 5, false, Some(100)), base);
+        assert_eq!(pricing.price(base, "music", 5, false, Some(1_000)), base);
+
+        let invalid = PricingSchedule {
+            dutch_auction: Some(DutchAuction {
+                start_multiplier: Decimal::percent(50),
+                duration: 100,
+            }),
+            ..PricingSchedule::default()
+        };
+        assert!(!invalid.is_valid());
+    }
+
+    #[test]
+    fn check_block_rules() {
+        let name = BlockRule::Name("binance".to_string());
+        assert!(name.matches("binance"));
+        assert!(!name.matches("binance-fan"));
+
+        let contains = BlockRule::Pattern("*binance*".to_string());
+        assert!(contains.matches("binance"));
+        assert!(contains.matches("official-binance-support"));
+        assert!(!contains.matches("bin-ance"));
+
+        let pattern = BlockRule::Pattern("b?nance*".to_string());
+        assert!(pattern.matches("binance"));
+        assert!(pattern.matches("bynance-support"));
+        assert!(!pattern.matches("bnance"));
+        assert!(!pattern.matches("my-binance"));
+
+        let suffix = BlockRule::Pattern("*-support".to_string());
+        assert!(suffix.matches("kraken-support"));
+        assert!(suffix.matches("support-support"));
+        assert!(!suffix.matches("support-desk"));
+
+        assert!(BlockRule::Pattern("*a*a".to_string()).matches("banana"));
+        assert!(!BlockRule::Pattern("*a*a".to_string()).matches("bananas"));
+
+        assert!(!BlockRule::Name(String::new()).is_valid());
+        assert!(!BlockRule::Name("Binance".to_string()).is_valid());
+        assert!(!BlockRule::Pattern("*?*".to_string()).is_valid());
+        assert!(contains.is_valid());
     }
 }
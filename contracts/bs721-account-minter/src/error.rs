@@ -21,6 +21,12 @@ pub enum ContractError {
     #[error("{0}")]
     Instantiate2AddressError(#[from] Instantiate2AddressError),
 
+    #[error("Cannot migrate from contract {contract}")]
+    InvalidMigrationContract { contract: String },
+
+    #[error("Cannot migrate from version {from} to {to}")]
+    InvalidMigrationVersion { from: String, to: String },
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -56,4 +62,96 @@ pub enum ContractError {
 
     #[error("Invalid Whitelist Type")]
     InvalidWhitelistType {},
+
+    #[error("Invalid whitelist period, start must be before end and end in the future")]
+    InvalidWhitelistPeriod {},
+
+    #[error("Discount must be at most 100%")]
+    InvalidDiscount {},
+
+    #[error("NotWhitelisted")]
+    NotWhitelisted {},
+
+    #[error("Whitelist mint limit reached")]
+    WhitelistLimitReached {},
+
+    #[error("Referrals are not enabled")]
+    ReferralsDisabled {},
+
+    #[error("Referrer must be an address or an account, other than the minter")]
+    InvalidReferrer {},
+
+    #[error("Invalid voucher")]
+    InvalidVoucher {},
+
+    #[error("Voucher has no uses left")]
+    VoucherExhausted {},
+
+    #[error("Voucher was issued to another address")]
+    VoucherNotRedeemer {},
+
+    #[error("Voucher must be committed to in an earlier block")]
+    VoucherNotCommitted {},
+
+    #[error("Voucher must have at least one use")]
+    InvalidVoucherUses {},
+
+    #[error("Unknown Unicode script: {script}")]
+    InvalidScript { script: String },
+
+    #[error("Account is confusable with {existing}")]
+    ConfusableAccount { existing: String },
+
+    #[error(
+        "Invalid pricing schedule, discounts must be at most 100% and auctions start at 1x or more"
+    )]
+    InvalidPricing {},
+
+    #[error("Invalid merkle root")]
+    InvalidMerkleRoot {},
+
+    #[error("Account is reserved and can only be claimed with ClaimReserved")]
+    AccountReserved {},
+
+    #[error("Account is not reserved")]
+    NotReserved {},
+
+    #[error("Invalid reservation proof")]
+    InvalidReservationProof {},
+
+    #[error("Only the owner can associate an address when minting")]
+    InvalidAssociation {},
+
+    #[error("Admin action must be proposed and wait for the admin delay")]
+    TimelockRequired {},
+
+    #[error("Admin action can't be executed before {executable_at}")]
+    ActionNotReady { executable_at: Timestamp },
+
+    #[error("Account is blocked")]
+    AccountBlocked {},
+
+    #[error("Block rules must be lowercase, and patterns can't be only wildcards")]
+    InvalidBlockRule {},
+
+    #[error("Too many blocked patterns, max: {max}")]
+    TooManyBlockedPatterns { max: usize },
+
+    #[error("Batch must mint between 1 and {max} accounts")]
+    InvalidBatchSize { max: usize },
+
+    #[error("Delegation policy must allow at least one validator")]
+    InvalidDelegationPolicy {},
+
+    #[error("No delegation holding period is set")]
+    HoldingPeriodDisabled {},
+
+    #[error("Delegation must be registered and held for the holding period")]
+    DelegationNotHeld {},
+
+    #[error("Delegation has not dropped below the delegation the account was minted on, or the account changed hands")]
+    ChallengeFailed {},
+
+    #[error("Account is already flagged")]
+    AccountFlagged {},
 }
@@ -0,0 +1,122 @@
+use btsg_account::minter::IdnConfig;
+use cosmwasm_std::{Deps, DepsMut, StdResult};
+use unicode_normalization::UnicodeNormalization;
+use unicode_script::{Script, UnicodeScript};
+use unicode_security::GeneralSecurityProfile;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    commands::{invalid_char, is_minted, validate_account},
+    state::SKELETONS,
+    ContractError,
+};
+
+/// Lowercase NFC form an account is minted under
+pub fn normalize(account: &str) -> String {
+    account.to_lowercase().nfc().collect()
+}
+
+/// User perceived characters, used for length limits and pricing
+pub fn grapheme_len(account: &str) -> usize {
+    account.graphemes(true).count()
+}
+
+/// Confusable skeleton (UTS #39), equal for accounts that look alike.
+/// Lowercased, as accounts are, so that e.g. `0` and `o` are confusable.
+pub fn skeleton(account: &str) -> String {
+    unicode_security::skeleton(account)
+        .collect::<String>()
+        .to_lowercase()
+}
+
+pub fn validate_scripts(config: &IdnConfig) -> Result<(), ContractError> {
+    match config
+        .scripts
+        .iter()
+        .find(|s| Script::from_full_name(s).is_none())
+    {
+        Some(script) => Err(ContractError::InvalidScript {
+            script: script.clone(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// ASCII accounts follow the usual rules. Others may only use the allowed scripts and emoji.
+pub fn validate_idn_account(
+    account: &str,
+    config: &IdnConfig,
+    min: u32,
+    max: u32,
+) -> Result<(), ContractError> {
+    if account.is_ascii() {
+        return validate_account(account, min, max);
+    }
+
+    let len = grapheme_len(account) as u32;
+    if len < min {
+        return Err(ContractError::AccountTooShort {});
+    } else if len >= max {
+        return Err(ContractError::AccountTooLong {});
+    }
+
+    if account != normalize(account)
+        || account.starts_with('-')
+        || account.ends_with('-')
+        || !account.chars().all(|c| allowed_char(c, config))
+    {
+        return Err(ContractError::InvalidAccount {});
+    }
+
+    Ok(())
+}
+
+fn allowed_char(c: char, config: &IdnConfig) -> bool {
+    if c.is_ascii() {
+        return !invalid_char(c);
+    }
+    match c.script() {
+        // combining marks, variation selectors and zero width joiners
+        Script::Inherited => true,
+        // shared characters, e.g. the Japanese prolonged sound mark, belong to their scripts
+        Script::Common => {
+            let extension = c.script_extension();
+            (config.allow_emoji && is_emoji(c))
+                || (!extension.is_common_or_inherited()
+                    && config
+                        .scripts
+                        .iter()
+                        .filter_map(|s| Script::from_full_name(s.as_str()))
+                        .any(|s| extension.contains_script(s)))
+        }
+        script => config.scripts.iter().any(|s| s == script.full_name()) && c.identifier_allowed(),
+    }
+}
+
+fn is_emoji(c: char) -> bool {
+    matches!(c as u32, 0x2600..=0x27BF | 0x1F000..=0x1FAFF)
+}
+
+/// An account already minted that looks like `account`.
+/// Accounts minted before IDN mode was enabled are only found when they equal the skeleton.
+pub fn find_confusable(deps: Deps, account: &str) -> StdResult<Option<String>> {
+    let skeleton = skeleton(account);
+    if let Some(existing) = SKELETONS.may_load(deps.storage, &skeleton)? {
+        return Ok((existing != account).then_some(existing));
+    }
+    if skeleton == account {
+        return Ok(None);
+    }
+
+    Ok(is_minted(deps, &skeleton)?.then_some(skeleton))
+}
+
+/// Rejects an account that looks like one already minted, then records its skeleton
+pub fn register_skeleton(deps: DepsMut, account: &str) -> Result<(), ContractError> {
+    if let Some(existing) = find_confusable(deps.as_ref(), account)? {
+        return Err(ContractError::ConfusableAccount { existing });
+    }
+    SKELETONS.save(deps.storage, &skeleton(account), &account.to_string())?;
+
+    Ok(())
+}
@@ -1,10 +1,10 @@
 use cw_orch::{interface, prelude::*};
 
-use crate::contract::{execute, instantiate, query, sudo, ACCOUNT_MINTER};
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::contract::{execute, instantiate, migrate, query, sudo, ACCOUNT_MINTER};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
 /// Uploadable trait for bs721_account_minter & use with cw-orchestrator library
-#[interface(InstantiateMsg, ExecuteMsg, QueryMsg, Empty)]
+#[interface(InstantiateMsg, ExecuteMsg, QueryMsg, MigrateMsg)]
 pub struct BtsgAccountMinter;
 
 impl<Chain> Uploadable for BtsgAccountMinter<Chain> {
@@ -16,6 +16,10 @@ impl<Chain> Uploadable for BtsgAccountMinter<Chain> {
     }
     /// Returns a CosmWasm contract wrapper
     fn wrapper() -> Box<dyn MockContract<Empty>> {
-        Box::new(ContractWrapper::new_with_empty(execute, instantiate, query).with_sudo(sudo))
+        Box::new(
+            ContractWrapper::new_with_empty(execute, instantiate, query)
+                .with_sudo(sudo)
+                .with_migrate(migrate),
+        )
     }
 }
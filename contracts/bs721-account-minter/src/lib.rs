@@ -1,5 +1,6 @@
 pub mod commands;
 pub mod contract;
+pub mod idn;
 mod error;
 pub mod msg;
 pub mod state;
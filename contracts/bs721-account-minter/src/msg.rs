@@ -1,6 +1,12 @@
-use btsg_account::minter::{Config, SudoParams};
+use btsg_account::minter::{
+    AdminAction, BatchMintItem, BlockRule, CanMintResponse, Config, DelegationPolicy,
+    DelegationSnapshot, FlaggedAccount, IdnConfig, MintPriceResponse, PricingSchedule,
+    ReferralConfig, ReferralStats, SudoParams, Voucher, Whitelist, WhitelistResponse,
+};
+use btsg_account::timelock::QueuedAction;
+use btsg_account::Metadata;
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Decimal, Uint128};
 
 use cw_ownable::cw_ownable_execute;
 
@@ -24,6 +30,8 @@ pub struct InstantiateMsg {
     pub base_delegation: Uint128,
     /// # of seconds to delay allowing minting to occur from contract creation. Defaults to 1 second
     pub mint_start_delay: Option<u64>,
+    /// Price and delegation multipliers. Defaults to `PricingSchedule::default()`
+    pub pricing: Option<PricingSchedule>,
 }
 
 #[cw_ownable_execute]
@@ -31,11 +39,88 @@ pub struct InstantiateMsg {
 #[derive(cw_orch::ExecuteFns)]
 pub enum ExecuteMsg {
     /// Mint a account and list on Bitsong Account Marketplace
-    MintAndList { account: String },
-    /// Admin can pause minting during whitelist switching
+    /// Before public minting starts, the sender must be on an active whitelist.
+    /// `proof` is required for merkle whitelists.
+    /// `referrer` is an address or an account that receives a share of the fee.
+    /// `voucher` is a discount code issued by the admin. Unless it was issued to the sender,
+    /// the sender must have committed to it with `CommitVoucher` in an earlier block.
+    /// Discounts don't stack, the largest one applies.
+    /// `owner` receives the account instead of the sender, e.g. as a gift or for a DAO.
+    /// `initial` is the metadata the account is minted with.
+    /// `associate` is an address to associate with the account, as with `AssociateAddress`.
+    /// It can't be set when minting for another owner.
+    MintAndList {
+        account: String,
+        proof: Option<Vec<String>>,
+        referrer: Option<String>,
+        voucher: Option<String>,
+        owner: Option<String>,
+        initial: Option<Metadata>,
+        associate: Option<String>,
+    },
+    /// Admin can pause minting during whitelist switching.
+    /// With an admin delay, unpausing must be proposed.
     Pause { pause: bool },
-    /// Update config, only callable by admin
+    /// Update config, only callable by admin. With an admin delay, it must be proposed.
     UpdateConfig { config: Config },
+    /// Queue an admin action, executable once the admin delay has passed. Only callable by admin
+    ProposeAdminAction { action: AdminAction },
+    /// Execute a queued admin action once its delay has passed, only callable by admin
+    ExecuteAdminAction { id: u64 },
+    /// Cancel a queued admin action, only callable by admin
+    CancelAdminAction { id: u64 },
+    /// Mint a reserved account for free. `proof` shows the (account, sender) pair is in the
+    /// reservation merkle tree.
+    ClaimReserved { account: String, proof: Vec<String> },
+    /// Set the reservation merkle root and add or remove reserved accounts, only callable by admin
+    UpdateReservations {
+        root: Option<String>,
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    /// Enable internationalized accounts, or disable them with `None`. Only callable by admin
+    UpdateIdnConfig { config: Option<IdnConfig> },
+    /// Set the referrer fee share and minter discount, only callable by admin
+    UpdateReferralConfig { config: ReferralConfig },
+    /// Add a discount voucher by the hex encoded sha256 hash of its code, only callable by admin.
+    /// Only `redeemer` can mint with it when set, otherwise anyone with the code can.
+    AddVoucher {
+        code_hash: String,
+        discount: Decimal,
+        max_uses: u32,
+        redeemer: Option<String>,
+    },
+    /// Commit to minting with a voucher, by the `voucher_commitment` of its code and the sender.
+    /// Needed for vouchers without a redeemer, so a code seen in a pending mint can't be spent
+    /// by others.
+    CommitVoucher { commitment: String },
+    /// Remove a discount voucher, only callable by admin
+    RemoveVoucher { code_hash: String },
+    /// Add a whitelist mint phase, only callable by admin
+    AddWhitelist { whitelist: Whitelist },
+    /// Remove a whitelist mint phase, only callable by admin
+    RemoveWhitelist { id: u32 },
+    /// Set which delegations count toward minting, or count all with `None`.
+    /// Only callable by admin
+    UpdateDelegationPolicy { policy: Option<DelegationPolicy> },
+    /// Snapshot the sender's delegation. With a holding period, only the snapshot counts
+    /// toward minting, once held for the period and capped by the current delegation.
+    RegisterDelegation {},
+    /// Flag and lock an account whose delegator's stake dropped below the delegation it was
+    /// minted on, while it's still owned by the address it was minted to, and revoke the
+    /// delegator's snapshot. Anyone can call this
+    Challenge { account: String },
+    /// Clear the flag of an account and unlock it once its delegator's stake is restored.
+    /// Anyone can call this
+    ClearFlag { account: String },
+    /// Mint and list accounts for their owners without payment or delegation,
+    /// e.g. to import a legacy registry. Only callable by admin
+    BatchMint { items: Vec<BatchMintItem> },
+    /// Add or remove blocklist rules, only callable by admin
+    UpdateBlocklist {
+        add: Vec<BlockRule>,
+        remove: Vec<BlockRule>,
+    },
 }
 
 #[cw_serde]
@@ -52,6 +137,21 @@ pub enum SudoMsg {
     UpdateAccountMarketplace {
         marketplace: String,
     },
+    UpdatePricing {
+        pricing: PricingSchedule,
+    },
+    UpdatePremiumWords {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    UpdateBlocklist {
+        add: Vec<BlockRule>,
+        remove: Vec<BlockRule>,
+    },
+    /// Seconds admin actions wait between being proposed and executed
+    UpdateAdminDelay {
+        delay: u64,
+    },
 }
 
 #[cw_serde]
@@ -65,7 +165,73 @@ pub enum QueryMsg {
     Params {},
     #[returns(Config)]
     Config {},
+    #[returns(Vec<WhitelistResponse>)]
+    Whitelists {},
+    #[returns(u64)]
+    AdminDelay {},
+    /// Queued admin actions, with optional pagination
+    #[returns(Vec<QueuedAction<AdminAction>>)]
+    AdminActions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Price and delegation required to mint an account, before discounts
+    #[returns(MintPriceResponse)]
+    MintPrice { account: String },
+    /// Check whether `minter` can mint an account, and why not.
+    /// Before public minting, `proof` is the minter's proof for merkle whitelist phases.
+    #[returns(CanMintResponse)]
+    CanMint {
+        account: String,
+        minter: String,
+        proof: Option<Vec<String>>,
+    },
+    #[returns(Option<IdnConfig>)]
+    IdnConfig {},
+    /// The minted account an account would be confused with in IDN mode
+    #[returns(Option<String>)]
+    Confusable { account: String },
+    #[returns(Option<ReferralConfig>)]
+    ReferralConfig {},
+    /// Mints referred by an address and the rewards it received
+    #[returns(ReferralStats)]
+    Referral { address: String },
+    #[returns(Option<Voucher>)]
+    Voucher { code_hash: String },
+    #[returns(Option<String>)]
+    ReservationRoot {},
+    /// Reserved accounts that have not been claimed yet
+    #[returns(Vec<String>)]
+    ReservedAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// The blocklist rule an account matches, if any
+    #[returns(Option<BlockRule>)]
+    IsBlocked { account: String },
+    /// Blocklist rules, names first then patterns, with optional pagination
+    #[returns(Vec<BlockRule>)]
+    Blocklist {
+        start_after: Option<BlockRule>,
+        limit: Option<u32>,
+    },
+    #[returns(Option<DelegationPolicy>)]
+    DelegationPolicy {},
+    #[returns(Option<DelegationSnapshot>)]
+    DelegationSnapshot { address: String },
+    /// Whether an account was flagged by a challenge
+    #[returns(Option<FlaggedAccount>)]
+    Flagged { account: String },
+    /// Accounts minted by an address during a whitelist phase
+    #[returns(u32)]
+    WhitelistMints { id: u32, address: String },
 }
 
-#[cosmwasm_schema::cw_serde]
-pub struct MigrateMsg {}
\ No newline at end of file
+/// Params introduced by the new version, set in the same migration
+#[cw_serde]
+#[derive(Default)]
+pub struct MigrateMsg {
+    pub pricing: Option<PricingSchedule>,
+    /// Seconds admin actions wait between being proposed and executed
+    pub admin_delay: Option<u64>,
+}
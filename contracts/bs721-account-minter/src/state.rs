@@ -1,6 +1,10 @@
-use btsg_account::minter::{Config, SudoParams};
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use btsg_account::minter::{
+    AdminAction, Config, DelegationPolicy, DelegationSnapshot, FlaggedAccount, IdnConfig,
+    MintDelegation, ReferralConfig, ReferralStats, SudoParams, Voucher, Whitelist,
+};
+use btsg_account::timelock::Timelock;
+use cosmwasm_std::{Addr, Empty};
+use cw_storage_plus::{Item, Map};
 
 pub const SUDO_PARAMS: Item<SudoParams> = Item::new("sp");
 
@@ -12,3 +16,60 @@ pub const ACCOUNT_MARKETPLACE: Item<Addr> = Item::new("am");
 pub const PAUSED: Item<bool> = Item::new("paused");
 
 pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Config and pause changes waiting for the admin delay set by governance
+pub const ADMIN_TIMELOCK: Timelock<AdminAction> = Timelock::new("ad", "adc", "adq");
+
+/// Most accounts minted by a single `BatchMint`
+pub const MAX_BATCH_MINT: usize = 50;
+
+pub const WHITELISTS: Map<u32, Whitelist> = Map::new("wl");
+
+pub const WHITELIST_COUNT: Item<u32> = Item::new("wlc");
+
+/// Accounts minted by an address during a whitelist phase
+pub const WHITELIST_MINTS: Map<(u32, &Addr), u32> = Map::new("wlm");
+
+/// Merkle root of reserved (account, address) pairs
+pub const RESERVATION_ROOT: Item<String> = Item::new("rr");
+
+/// Reserved accounts not claimed yet. Only their claimant can mint them, with `ClaimReserved`.
+pub const RESERVED_ACCOUNTS: Map<&str, Empty> = Map::new("ra");
+
+pub const REFERRAL_CONFIG: Item<ReferralConfig> = Item::new("rc");
+
+pub const REFERRALS: Map<&Addr, ReferralStats> = Map::new("rs");
+
+/// Discount vouchers by the hex encoded sha256 hash of their code
+pub const VOUCHERS: Map<&str, Voucher> = Map::new("v");
+
+/// Voucher commitments, see `voucher_commitment`, to the block height they were made at
+pub const VOUCHER_COMMITMENTS: Map<&str, u64> = Map::new("vc");
+
+/// Internationalized accounts are only allowed when set
+pub const IDN_CONFIG: Item<IdnConfig> = Item::new("idn");
+
+/// Confusable skeleton of accounts minted in IDN mode, to the account
+pub const SKELETONS: Map<&str, String> = Map::new("sk");
+
+/// Delegations count toward minting as is when not set
+pub const DELEGATION_POLICY: Item<DelegationPolicy> = Item::new("dp");
+
+pub const DELEGATION_SNAPSHOTS: Map<&Addr, DelegationSnapshot> = Map::new("ds");
+
+/// Delegation each account was minted on with `MintAndList`, checked again by `Challenge`
+pub const MINT_DELEGATIONS: Map<&str, MintDelegation> = Map::new("md");
+
+/// Accounts whose delegator's stake dropped below the delegation they were minted on
+pub const FLAGGED_ACCOUNTS: Map<&str, FlaggedAccount> = Map::new("fa");
+
+/// Accounts that can't be minted with `MintAndList`
+pub const BLOCKED_NAMES: Map<&str, Empty> = Map::new("bn");
+
+/// Patterns of accounts that can't be minted with `MintAndList`, all checked on each mint
+pub const BLOCKED_PATTERNS: Map<&str, Empty> = Map::new("bp");
+
+pub const MAX_BLOCKED_PATTERNS: usize = 100;
+
+/// Accounts priced with the pricing schedule's `word_premium`
+pub const PREMIUM_WORDS: Map<&str, Empty> = Map::new("pw");
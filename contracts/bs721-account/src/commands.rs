@@ -2,6 +2,7 @@ use bs721_base::msg::ExecuteMsg as Bs721ExecuteMsg;
 
 use crate::{
     error::ContractError,
+    hooks::{prepare_metadata_hook, prepare_transfer_hook},
     msg::Bs721AccountsQueryMsg,
     state::{SudoParams, ACCOUNT_MARKETPLACE, REVERSE_MAP, REVERSE_MAP_KEY, SUDO_PARAMS, VERIFIER},
     Bs721AccountContract,
@@ -21,16 +22,20 @@ pub mod manifest {
         ownership::{self, Ownership},
     };
     use bs721::Expiration;
-    use bs721_base::state::TokenInfo;
+    use bs721_base::state::{Approval, TokenInfo};
     use btsg_account::{
+        collection::{MetadataAction, TransferAction},
         market::{PendingBid, QueryMsg},
         validate_aa_ownership,
         verify_generic::CosmosArbitrary,
         Metadata,
     };
-    use cosmwasm_std::{to_json_binary, Attribute, CosmosMsg, SubMsg, WasmMsg};
+    use cosmwasm_std::{to_json_binary, Attribute, CosmosMsg, Empty, Storage, SubMsg, WasmMsg};
 
-    use crate::state::{REVERSE_MAP_KEY, REVMAP_LIMIT};
+    use crate::{
+        msg::ManageHooksAction,
+        state::{LOCKED_ACCOUNTS, METADATA_HOOKS, REVERSE_MAP_KEY, REVMAP_LIMIT, TRANSFER_HOOKS},
+    };
 
     use super::*;
 
@@ -173,10 +178,12 @@ pub mod manifest {
         if let Some(address) = address {
             event = event.add_attribute("address", address);
         }
+        let hooks = prepare_metadata_hook(deps.storage, &account, None, MetadataAction::Associate)?;
 
         // remove bids since changes were made and may create differences between what is being bid on
         Ok(Response::new()
             .add_event(event)
+            .add_submessages(hooks)
             .add_message(WasmMsg::Execute {
                 contract_addr: market.to_string(),
                 msg: to_json_binary(&btsg_account::market::ExecuteMsg::RemoveBids {
@@ -518,11 +525,18 @@ pub mod manifest {
             },
         )?;
 
+        let hooks = prepare_metadata_hook(
+            deps.storage,
+            &token_id,
+            Some(record.account.clone()),
+            MetadataAction::AddRecord,
+        )?;
+
         let event = Event::new("add-text-record")
             .add_attribute("sender", info.sender)
             .add_attribute("account", token_id)
             .add_attribute("record", record.into_json_string());
-        Ok(Response::new().add_event(event))
+        Ok(Response::new().add_event(event).add_submessages(hooks))
     }
 
     pub fn execute_remove_text_record(
@@ -550,11 +564,18 @@ pub mod manifest {
             },
         )?;
 
+        let hooks = prepare_metadata_hook(
+            deps.storage,
+            &token_id,
+            Some(record_account.clone()),
+            MetadataAction::RemoveRecord,
+        )?;
+
         let event = Event::new("remove-text-record")
             .add_attribute("sender", info.sender)
             .add_attribute("account", token_id)
             .add_attribute("record_account", record_account);
-        Ok(Response::new().add_event(event))
+        Ok(Response::new().add_event(event).add_submessages(hooks))
     }
 
     pub fn execute_update_text_record(
@@ -596,11 +617,18 @@ pub mod manifest {
             },
         )?;
 
+        let hooks = prepare_metadata_hook(
+            deps.storage,
+            &token_id,
+            Some(record.account.clone()),
+            MetadataAction::UpdateRecord,
+        )?;
+
         let event = Event::new("update-text-record")
             .add_attribute("sender", info.sender)
             .add_attribute("account", token_id)
             .add_attribute("record", record.into_json_string());
-        Ok(Response::new().add_event(event))
+        Ok(Response::new().add_event(event).add_submessages(hooks))
     }
 
     pub fn execute_verify_text_record(
@@ -634,12 +662,19 @@ pub mod manifest {
             },
         )?;
 
+        let hooks = prepare_metadata_hook(
+            deps.storage,
+            &token_id,
+            Some(record_account.clone()),
+            MetadataAction::VerifyRecord,
+        )?;
+
         let event = Event::new("verify-text-record")
             .add_attribute("sender", info.sender)
             .add_attribute("account", token_id)
             .add_attribute("record", record_account)
             .add_attribute("result", result.to_string());
-        Ok(Response::new().add_event(event))
+        Ok(Response::new().add_event(event).add_submessages(hooks))
     }
 
     pub fn update_image_nft(
@@ -698,6 +733,43 @@ pub mod manifest {
         Ok(Response::new().add_event(event))
     }
 
+    pub fn execute_manage_hooks(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        action: ManageHooksAction,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info)?;
+        // only contract admin may invoke
+        let admin = deps
+            .querier
+            .query_wasm_contract_info(&env.contract.address)?
+            .admin;
+        if admin.as_ref() != Some(&info.sender) {
+            return Err(ContractError::UnauthorizedCreatorOrAdmin {});
+        }
+
+        let event = match action {
+            ManageHooksAction::AddTransferHook(hook) => {
+                TRANSFER_HOOKS.add_hook(deps.storage, deps.api.addr_validate(&hook)?)?;
+                Event::new("add-transfer-hook").add_attribute("hook", hook)
+            }
+            ManageHooksAction::RemoveTransferHook(hook) => {
+                TRANSFER_HOOKS.remove_hook(deps.storage, deps.api.addr_validate(&hook)?)?;
+                Event::new("remove-transfer-hook").add_attribute("hook", hook)
+            }
+            ManageHooksAction::AddMetadataHook(hook) => {
+                METADATA_HOOKS.add_hook(deps.storage, deps.api.addr_validate(&hook)?)?;
+                Event::new("add-metadata-hook").add_attribute("hook", hook)
+            }
+            ManageHooksAction::RemoveMetadataHook(hook) => {
+                METADATA_HOOKS.remove_hook(deps.storage, deps.api.addr_validate(&hook)?)?;
+                Event::new("remove-metadata-hook").add_attribute("hook", hook)
+            }
+        };
+        Ok(Response::new().add_event(event))
+    }
+
     fn only_owner(deps: Deps, sender: &Addr, token_id: &str) -> Result<Addr, ContractError> {
         let owner = Bs721AccountContract::default()
             .tokens
@@ -732,6 +804,74 @@ pub mod manifest {
         Ok(())
     }
 
+    /// Metadata an account is minted with gets the same checks as records added later.
+    /// Records can't be verified, and tokenized account support is enabled by the owner after minting.
+    fn validate_initial_metadata(deps: Deps, metadata: &Metadata) -> Result<(), ContractError> {
+        if metadata.account_ownership {
+            return Err(ContractError::InvalidMetadata {});
+        }
+        let params = SUDO_PARAMS.load(deps.storage)?;
+        if metadata.records.len() > params.max_record_count as usize {
+            return Err(ContractError::TooManyRecords {
+                max: params.max_record_count,
+            });
+        }
+        for (i, record) in metadata.records.iter().enumerate() {
+            validate_record(record)?;
+            if metadata.records[..i]
+                .iter()
+                .any(|r| r.account == record.account)
+            {
+                return Err(ContractError::RecordAccountAlreadyExists {});
+            }
+        }
+        Ok(())
+    }
+
+    pub fn execute_lock(
+        deps: DepsMut,
+        info: MessageInfo,
+        token_id: String,
+        locked: bool,
+    ) -> Result<Response, ContractError> {
+        nonpayable(&info)?;
+        let bs721 = Bs721AccountContract::default();
+        if info.sender != bs721.minter.load(deps.storage)? {
+            return Err(ContractError::UnauthorizedMinter {});
+        }
+
+        bs721.tokens.load(deps.storage, &token_id)?;
+        match locked {
+            true => LOCKED_ACCOUNTS.save(deps.storage, &token_id, &Empty {})?,
+            false => LOCKED_ACCOUNTS.remove(deps.storage, &token_id),
+        }
+
+        let event = Event::new("lock-account")
+            .add_attribute("token_id", token_id)
+            .add_attribute("locked", locked.to_string());
+        Ok(Response::new().add_event(event))
+    }
+
+    /// Locked accounts can only be moved by the marketplace, to settle a sale accepted
+    /// before the lock. The new owner receives it unlocked.
+    fn release_lock(
+        storage: &mut dyn Storage,
+        sender: &Addr,
+        names_marketplace: &Addr,
+        token_id: &str,
+    ) -> Result<(), ContractError> {
+        if !LOCKED_ACCOUNTS.has(storage, token_id) {
+            return Ok(());
+        }
+        if sender != names_marketplace {
+            return Err(ContractError::AccountCannotBeTransfered {
+                reason: "Account is locked".to_string(),
+            });
+        }
+        LOCKED_ACCOUNTS.remove(storage, token_id);
+        Ok(())
+    }
+
     /// BS721 FUNCTIONS
     pub fn execute_mint(
         deps: DepsMut,
@@ -743,7 +883,7 @@ pub mod manifest {
             return Err(ContractError::UnauthorizedMinter {});
         }
 
-        let (token_id, owner, _, extension, _, _) = match msg {
+        let (token_id, owner, token_uri, extension, _, _) = match msg {
             Bs721ExecuteMsg::Mint {
                 token_id,
                 owner,
@@ -762,11 +902,27 @@ pub mod manifest {
             _ => return Err(ContractError::NotImplemented {}),
         };
 
-        // create the token
+        validate_initial_metadata(deps.as_ref(), &extension)?;
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        // token_uri is the address to associate, with the same authorization as AssociateAddress by the owner
+        let associated = token_uri
+            .map(|address| {
+                validate_address(
+                    deps.as_ref(),
+                    &owner_addr,
+                    deps.api.addr_validate(&address)?,
+                )
+            })
+            .transpose()?;
+
+        // create the token, approving the marketplace for it since it is listed at mint
         let token = TokenInfo {
-            owner: deps.api.addr_validate(&owner)?,
-            approvals: vec![],
-            token_uri: None, // reserved for reverse map
+            owner: owner_addr,
+            approvals: vec![Approval {
+                spender: ACCOUNT_MARKETPLACE.load(deps.storage)?,
+                expires: Expiration::Never {},
+            }],
+            token_uri: associated.as_ref().map(Addr::to_string), // reserved for reverse map
             extension,
             seller_fee_bps: None,
             payment_addr: None,
@@ -786,11 +942,30 @@ pub mod manifest {
         let canonv = deps.api.addr_canonicalize(&owner)?;
         REVMAP_LIMIT.save(deps.storage, &canonv.to_string(), &0)?;
 
-        let event = Event::new("mint")
+        let mut event = Event::new("mint")
             .add_attribute("minter", info.sender)
             .add_attribute("token_id", &token_id)
             .add_attribute("owner", &owner);
-        Ok(Response::new().add_event(event))
+        let mut hooks = vec![];
+        if let Some(address) = associated {
+            // the address moves from the account it was associated with, if any
+            if let Some(old_account) = REVERSE_MAP.may_load(deps.storage, &address)? {
+                Bs721AccountContract::default()
+                    .tokens
+                    .update(deps.storage, &old_account, |t| match t {
+                        Some(mut ti) => {
+                            ti.token_uri = None;
+                            Ok(ti)
+                        }
+                        None => Err(ContractError::AccountNotFound {}),
+                    })?;
+            }
+            REVERSE_MAP.save(deps.storage, &address, &token_id)?;
+            hooks =
+                prepare_metadata_hook(deps.storage, &token_id, None, MetadataAction::Associate)?;
+            event = event.add_attribute("address", address);
+        }
+        Ok(Response::new().add_event(event).add_submessages(hooks))
     }
 
     pub fn execute_burn(
@@ -804,6 +979,11 @@ pub mod manifest {
         ensure_not_in_cooldown(deps.as_ref(), market, &account)?;
 
         let bs721 = Bs721AccountContract::default();
+        let owner = bs721.tokens.load(deps.storage, &account)?.owner;
+        let hooks =
+            prepare_transfer_hook(deps.storage, &account, &owner, None, TransferAction::Burn)?;
+        // the account can be minted again, unlocked
+        LOCKED_ACCOUNTS.remove(deps.storage, &account);
 
         bs721.execute(
             deps,
@@ -815,6 +995,7 @@ pub mod manifest {
         )?;
 
         Ok(Response::new()
+            .add_submessages(hooks)
             .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: market.to_string(),
                 msg: to_json_binary(&btsg_account::market::ExecuteMsg::RemoveAsk {
@@ -837,8 +1018,9 @@ pub mod manifest {
 
         let names_marketplace = ACCOUNT_MARKETPLACE.load(deps.storage)?;
         ensure_not_in_cooldown(deps.as_ref(), &names_marketplace, &token_id)?;
+        release_lock(deps.storage, &info.sender, &names_marketplace, &token_id)?;
 
-        let update_ask_msg =
+        let (update_ask_msg, hooks) =
             _transfer_nft(deps, env, &info, &recipient, &token_id, &names_marketplace)?;
 
         let event = Event::new("transfer")
@@ -846,7 +1028,10 @@ pub mod manifest {
             .add_attribute("recipient", recipient)
             .add_attribute("token_id", token_id);
 
-        Ok(Response::new().add_message(update_ask_msg).add_event(event))
+        Ok(Response::new()
+            .add_message(update_ask_msg)
+            .add_submessages(hooks)
+            .add_event(event))
     }
 
     // Update the ask on the marketplace
@@ -898,10 +1083,22 @@ pub mod manifest {
         recipient: &Addr,
         token_id: &str,
         names_marketplace: &Addr,
-    ) -> Result<WasmMsg, ContractError> {
+    ) -> Result<(WasmMsg, Vec<SubMsg>), ContractError> {
         let update_ask_msg =
             update_ask_on_marketplace(token_id, recipient.clone(), names_marketplace)?;
 
+        let owner = Bs721AccountContract::default()
+            .tokens
+            .load(deps.storage, token_id)?
+            .owner;
+        let hooks = prepare_transfer_hook(
+            deps.storage,
+            token_id,
+            &owner,
+            Some(recipient),
+            TransferAction::Transfer,
+        )?;
+
         reset_token_metadata_and_reverse_map(&mut deps, env.contract.address.clone(), token_id)?;
 
         let msg = bs721_base::ExecuteMsg::TransferNft {
@@ -920,7 +1117,7 @@ pub mod manifest {
 
         bs721.execute(deps, env, info.clone(), msg)?;
 
-        Ok(update_ask_msg)
+        Ok((update_ask_msg, hooks))
     }
 
     pub fn execute_send_nft(
@@ -934,9 +1131,22 @@ pub mod manifest {
         let contract_addr = deps.api.addr_validate(&contract)?;
         let names_marketplace = ACCOUNT_MARKETPLACE.load(deps.storage)?;
         ensure_not_in_cooldown(deps.as_ref(), &names_marketplace, &token_id)?;
+        release_lock(deps.storage, &info.sender, &names_marketplace, &token_id)?;
         let update_ask_msg =
             update_ask_on_marketplace(&token_id, contract_addr.clone(), &names_marketplace)?;
 
+        let owner = Bs721AccountContract::default()
+            .tokens
+            .load(deps.storage, &token_id)?
+            .owner;
+        let hooks = prepare_transfer_hook(
+            deps.storage,
+            &token_id,
+            &owner,
+            Some(&contract_addr),
+            TransferAction::Transfer,
+        )?;
+
         reset_token_metadata_and_reverse_map(&mut deps, env.contract.address.clone(), &token_id)?;
 
         let msg = bs721_base::ExecuteMsg::SendNft {
@@ -952,7 +1162,10 @@ pub mod manifest {
             .add_attribute("contract", contract_addr.to_string())
             .add_attribute("token_id", token_id);
 
-        Ok(Response::new().add_message(update_ask_msg).add_event(event))
+        Ok(Response::new()
+            .add_message(update_ask_msg)
+            .add_submessages(hooks)
+            .add_event(event))
     }
 }
 
@@ -1,4 +1,4 @@
-use bs_controllers::AdminError;
+use bs_controllers::{AdminError, HookError};
 use cosmwasm_std::StdError;
 use cw_ownable::OwnershipError;
 use cw_utils::PaymentError;
@@ -21,6 +21,9 @@ pub enum ContractError {
     #[error("{0}")]
     Base(#[from] bs721_base::ContractError),
 
+    #[error("{0}")]
+    Hook(#[from] HookError),
+
     #[error("AccountIsNotTokenized")]
     AccountIsNotTokenized {},
 
@@ -86,4 +89,13 @@ pub enum ContractError {
 
     #[error("NotImplemented")]
     NotImplemented {},
+
+    #[error("Contract got an unexpected Reply")]
+    UnexpectedReply(),
+
+    #[error("Cannot migrate from contract {contract}")]
+    InvalidMigrationContract { contract: String },
+
+    #[error("Cannot migrate from version {from} to {to}")]
+    InvalidMigrationVersion { from: String, to: String },
 }
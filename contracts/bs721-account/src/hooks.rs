@@ -0,0 +1,95 @@
+use btsg_account::collection::{MetadataAction, MetadataHookMsg, TransferAction, TransferHookMsg};
+use cosmwasm_std::{
+    Addr, DepsMut, Env, Event, Reply, Response, StdResult, Storage, SubMsg, SubMsgResult, WasmMsg,
+};
+
+use crate::{
+    state::{METADATA_HOOKS, TRANSFER_HOOKS},
+    Bs721AccountContract, ContractError,
+};
+
+enum HookReply {
+    Transfer = 1,
+    Metadata,
+}
+
+impl TryFrom<u64> for HookReply {
+    type Error = ContractError;
+
+    fn try_from(item: u64) -> Result<Self, Self::Error> {
+        match item {
+            1 => Ok(HookReply::Transfer),
+            2 => Ok(HookReply::Metadata),
+            _ => Err(ContractError::UnexpectedReply()),
+        }
+    }
+}
+
+/// A failing hook never blocks a transfer or metadata update. The error is emitted as an event.
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let kind = match HookReply::try_from(msg.id)? {
+        HookReply::Transfer => "transfer",
+        HookReply::Metadata => "metadata",
+    };
+    match msg.result {
+        SubMsgResult::Ok(_) => Ok(Response::new()),
+        SubMsgResult::Err(error) => Ok(Response::new().add_event(
+            Event::new("hook-failed")
+                .add_attribute("kind", kind)
+                .add_attribute("error", error),
+        )),
+    }
+}
+
+pub fn prepare_transfer_hook(
+    storage: &dyn Storage,
+    token_id: &str,
+    owner: &Addr,
+    recipient: Option<&Addr>,
+    action: TransferAction,
+) -> StdResult<Vec<SubMsg>> {
+    let submsgs = TRANSFER_HOOKS.prepare_hooks(storage, |h| {
+        let msg = TransferHookMsg::new(
+            token_id,
+            owner.to_string(),
+            recipient.map(|r| r.to_string()),
+        );
+        let execute = WasmMsg::Execute {
+            contract_addr: h.to_string(),
+            msg: msg.into_json_binary(action.clone())?,
+            funds: vec![],
+        };
+        Ok(SubMsg::reply_on_error(execute, HookReply::Transfer as u64))
+    })?;
+
+    Ok(submsgs)
+}
+
+/// Sends the token's metadata, as stored after the change, to each metadata hook
+pub fn prepare_metadata_hook(
+    storage: &dyn Storage,
+    token_id: &str,
+    record_account: Option<String>,
+    action: MetadataAction,
+) -> StdResult<Vec<SubMsg>> {
+    let token = Bs721AccountContract::default()
+        .tokens
+        .load(storage, token_id)?;
+    let submsgs = METADATA_HOOKS.prepare_hooks(storage, |h| {
+        let msg = MetadataHookMsg {
+            token_id: token_id.to_string(),
+            associated_address: token.token_uri.clone(),
+            record_account: record_account.clone(),
+            metadata: token.extension.clone(),
+        };
+        let execute = WasmMsg::Execute {
+            contract_addr: h.to_string(),
+            msg: msg.into_json_binary(action.clone())?,
+            funds: vec![],
+        };
+        Ok(SubMsg::reply_on_error(execute, HookReply::Metadata as u64))
+    })?;
+
+    Ok(submsgs)
+}
@@ -1,12 +1,13 @@
 use cw_orch::{interface, prelude::*};
 
-use crate::entry::{execute, instantiate, query, sudo};
-use crate::msg::{Bs721AccountsQueryMsg as QueryMsg, ExecuteMsg, InstantiateMsg};
+use crate::entry::{execute, instantiate, migrate, query, sudo};
+use crate::hooks::reply;
+use crate::msg::{Bs721AccountsQueryMsg as QueryMsg, ExecuteMsg, InstantiateMsg, MigrateMsg};
 use crate::ACCOUNT_CONTRACT;
 use btsg_account::Metadata;
 
 /// Uploadable trait for bs721_account & use with cw-orchestrator library
-#[interface(InstantiateMsg, ExecuteMsg::<Metadata>, QueryMsg, Empty)]
+#[interface(InstantiateMsg, ExecuteMsg::<Metadata>, QueryMsg, MigrateMsg)]
 pub struct BtsgAccountCollection;
 
 impl<Chain> Uploadable for BtsgAccountCollection<Chain, Metadata> {
@@ -18,6 +19,11 @@ impl<Chain> Uploadable for BtsgAccountCollection<Chain, Metadata> {
     }
     /// Returns a CosmWasm contract wrapper
     fn wrapper() -> Box<dyn MockContract<Empty>> {
-        Box::new(ContractWrapper::new_with_empty(execute, instantiate, query).with_sudo(sudo))
+        Box::new(
+            ContractWrapper::new_with_empty(execute, instantiate, query)
+                .with_sudo(sudo)
+                .with_reply(reply)
+                .with_migrate(migrate),
+        )
     }
 }
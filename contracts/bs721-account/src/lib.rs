@@ -1,6 +1,7 @@
 pub mod commands;
 mod error;
 pub mod helpers;
+pub mod hooks;
 pub mod msg;
 pub mod state;
 pub use crate::error::ContractError;
@@ -16,7 +17,7 @@ use btsg_account::Metadata;
 use msg::Bs721AccountsQueryMsg;
 
 // version info for migration info
-const ACCOUNT_CONTRACT: &str = "crates.io:bs721-account";
+pub const ACCOUNT_CONTRACT: &str = "crates.io:bs721-account";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub type Bs721AccountContract<'a> =
@@ -32,7 +33,10 @@ pub mod entry {
     use cosmwasm_std::StdError;
     use cw_utils::maybe_addr;
     use msg::{InstantiateMsg, SudoMsg};
-    use state::{SudoParams, ACCOUNT_MARKETPLACE, SUDO_PARAMS, VERIFIER};
+    use state::{
+        SudoParams, ACCOUNT_MARKETPLACE, LOCKED_ACCOUNTS, METADATA_HOOKS, SUDO_PARAMS,
+        TRANSFER_HOOKS, VERIFIER,
+    };
 
     #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
     pub fn instantiate(
@@ -153,6 +157,9 @@ pub mod entry {
             ExecuteMsg::ApproveAllViaMarket { owner, expires } => {
                 execute_approve_all_via_market(deps, env, info, owner, expires)
             }
+            ExecuteMsg::ManageHooks(action) => execute_manage_hooks(deps, env, info, action),
+            // only collection minter authorized
+            ExecuteMsg::Lock { token_id, locked } => execute_lock(deps, info, token_id, locked),
             _ => Bs721AccountContract::default()
                 .execute(deps, env, info, msg.into())
                 .map_err(|e| e.into()),
@@ -166,6 +173,11 @@ pub mod entry {
             QueryMsg::AccountMarketplace {} => to_json_binary(&query_profile_marketplace(deps)?),
             QueryMsg::Account { address } => to_json_binary(&query_account(deps, address)?),
             QueryMsg::Verifier {} => to_json_binary(&VERIFIER.query_admin(deps)?),
+            QueryMsg::TransferHooks {} => to_json_binary(&TRANSFER_HOOKS.query_hooks(deps)?),
+            QueryMsg::MetadataHooks {} => to_json_binary(&METADATA_HOOKS.query_hooks(deps)?),
+            QueryMsg::Locked { token_id } => {
+                to_json_binary(&LOCKED_ACCOUNTS.has(deps.storage, &token_id))
+            }
             QueryMsg::AssociatedAddress { account } => {
                 to_json_binary(&query_associated_address(deps, &account)?)
             }
@@ -198,13 +210,24 @@ pub mod entry {
     #[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
     pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
         let then = cw2::get_contract_version(deps.storage)?;
-        if then.version >= CONTRACT_VERSION.to_owned() || then.contract != ACCOUNT_CONTRACT.to_owned()
-        {
-            return Err(ContractError::Std(StdError::generic_err(
-                "unable to migrate bs721-account.",
-            )));
+        if then.contract != ACCOUNT_CONTRACT {
+            return Err(ContractError::InvalidMigrationContract {
+                contract: then.contract,
+            });
+        }
+        if parse_version(&then.version)? >= parse_version(CONTRACT_VERSION)? {
+            return Err(ContractError::InvalidMigrationVersion {
+                from: then.version,
+                to: CONTRACT_VERSION.to_string(),
+            });
         }
         cw2::set_contract_version(deps.storage, ACCOUNT_CONTRACT, CONTRACT_VERSION)?;
         Ok(Response::default())
     }
+
+    fn parse_version(version: &str) -> StdResult<semver::Version> {
+        version
+            .parse()
+            .map_err(|err| StdError::parse_err("semver::Version", err))
+    }
 }
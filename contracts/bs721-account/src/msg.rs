@@ -1,4 +1,5 @@
 use crate::{state::SudoParams, Metadata};
+use bs_controllers::HooksResponse;
 use btsg_account::verify_generic::CosmosArbitrary;
 use btsg_account::{TextRecord, NFT};
 use cosmwasm_schema::{cw_serde, QueryResponses};
@@ -95,6 +96,7 @@ pub enum ExecuteMsg<T> {
         /// The owner of the newly minted NFT
         owner: String,
         /// This will be a smart contract address that is an EOA using this token as ownership authentication
+        /// When set, the address is associated with the account, as if by the owner's `AssociateAddress`.
         token_uri: Option<String>,
         /// Seller fee basis points, 0-10000
         /// 0 means no fee, 100 means 1%, 10000 means 100%
@@ -114,6 +116,20 @@ pub enum ExecuteMsg<T> {
         to_add: Vec<CosmosArbitrary>,
         to_remove: Vec<String>,
     },
+    /// Add or remove transfer and metadata hooks. Only the contract admin can call this.
+    ManageHooks(ManageHooksAction),
+    /// Lock or unlock an account. A locked account can't be transferred or sent by its owner,
+    /// only by the marketplace to settle a sale accepted before the lock, which unlocks it.
+    /// Only the minter can call this.
+    Lock { token_id: String, locked: bool },
+}
+
+#[cw_serde]
+pub enum ManageHooksAction {
+    AddTransferHook(String),
+    RemoveTransferHook(String),
+    AddMetadataHook(String),
+    RemoveMetadataHook(String),
 }
 
 impl<T> From<ExecuteMsg<T>> for bs721_base::msg::ExecuteMsg<T> {
@@ -212,6 +228,15 @@ pub enum Bs721AccountsQueryMsg {
     /// Returns the verification oracle address
     #[returns(Option<String>)]
     Verifier {},
+    /// Show all registered transfer hooks
+    #[returns(HooksResponse)]
+    TransferHooks {},
+    /// Show all registered metadata hooks
+    #[returns(HooksResponse)]
+    MetadataHooks {},
+    /// Whether an account is locked by the minter
+    #[returns(bool)]
+    Locked { token_id: String },
     /// Everything below is inherited from sg721
     #[returns(OwnerOfResponse)]
     OwnerOf {
@@ -1,5 +1,5 @@
-use bs_controllers::Admin;
-use cosmwasm_std::{Addr, Binary};
+use bs_controllers::{Admin, Hooks};
+use cosmwasm_std::{Addr, Binary, Empty};
 use cw_storage_plus::{Item, Map};
 
 pub type TokenUri = Addr;
@@ -15,6 +15,14 @@ pub const VERIFIER: Admin = Admin::new("v");
 pub const SUDO_PARAMS: Item<SudoParams> = Item::new("sp");
 pub const ACCOUNT_MARKETPLACE: Item<Addr> = Item::new("am");
 
+/// Notified when a token is transferred, sent, or burned
+pub const TRANSFER_HOOKS: Hooks = Hooks::new("th");
+/// Notified when a token's associated address or text records change
+pub const METADATA_HOOKS: Hooks = Hooks::new("mh");
+
+/// Accounts locked by the minter, e.g. after a successful challenge
+pub const LOCKED_ACCOUNTS: Map<&str, Empty> = Map::new("la");
+
 #[cosmwasm_schema::cw_serde]
 pub struct SudoParams {
     pub max_record_count: u32,
@@ -0,0 +1,90 @@
+//! Messages sent by the account collection to its registered hooks.
+//! Each payload is wrapped in an execute variant named after the event, so a new
+//! payload shape is added as a new variant and existing receivers keep working.
+use crate::Metadata;
+use cosmwasm_std::{to_json_binary, Binary, StdResult};
+
+#[cosmwasm_schema::cw_serde]
+pub enum TransferAction {
+    Transfer,
+    Burn,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct TransferHookMsg {
+    pub token_id: String,
+    /// Owner of the token before the transfer
+    pub owner: String,
+    /// `None` when the token is burned
+    pub recipient: Option<String>,
+}
+
+impl TransferHookMsg {
+    pub fn new(token_id: &str, owner: String, recipient: Option<String>) -> Self {
+        TransferHookMsg {
+            token_id: token_id.to_string(),
+            owner,
+            recipient,
+        }
+    }
+
+    /// serializes the message
+    pub fn into_json_binary(self, action: TransferAction) -> StdResult<Binary> {
+        let msg = match action {
+            TransferAction::Transfer => TransferHookExecuteMsg::AccountTransferredHook(self),
+            TransferAction::Burn => TransferHookExecuteMsg::AccountBurnedHook(self),
+        };
+        to_json_binary(&msg)
+    }
+}
+
+// This is just a helper to properly serialize the above message
+#[cosmwasm_schema::cw_serde]
+pub enum TransferHookExecuteMsg {
+    AccountTransferredHook(TransferHookMsg),
+    AccountBurnedHook(TransferHookMsg),
+}
+
+#[cosmwasm_schema::cw_serde]
+pub enum MetadataAction {
+    Associate,
+    AddRecord,
+    UpdateRecord,
+    RemoveRecord,
+    VerifyRecord,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct MetadataHookMsg {
+    pub token_id: String,
+    /// Address associated with the account after the change
+    pub associated_address: Option<String>,
+    /// Name of the text record that changed, if any
+    pub record_account: Option<String>,
+    /// Token metadata after the change
+    pub metadata: Metadata,
+}
+
+impl MetadataHookMsg {
+    /// serializes the message
+    pub fn into_json_binary(self, action: MetadataAction) -> StdResult<Binary> {
+        let msg = match action {
+            MetadataAction::Associate => MetadataHookExecuteMsg::AddressAssociatedHook(self),
+            MetadataAction::AddRecord => MetadataHookExecuteMsg::TextRecordAddedHook(self),
+            MetadataAction::UpdateRecord => MetadataHookExecuteMsg::TextRecordUpdatedHook(self),
+            MetadataAction::RemoveRecord => MetadataHookExecuteMsg::TextRecordRemovedHook(self),
+            MetadataAction::VerifyRecord => MetadataHookExecuteMsg::TextRecordVerifiedHook(self),
+        };
+        to_json_binary(&msg)
+    }
+}
+
+// This is just a helper to properly serialize the above message
+#[cosmwasm_schema::cw_serde]
+pub enum MetadataHookExecuteMsg {
+    AddressAssociatedHook(MetadataHookMsg),
+    TextRecordAddedHook(MetadataHookMsg),
+    TextRecordUpdatedHook(MetadataHookMsg),
+    TextRecordRemovedHook(MetadataHookMsg),
+    TextRecordVerifiedHook(MetadataHookMsg),
+}
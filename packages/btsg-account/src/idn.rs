@@ -0,0 +1,33 @@
+/// Punycode (`xn--`) form of an account, for display where only ASCII is supported
+pub fn to_punycode(account: &str) -> Option<String> {
+    if account.is_ascii() {
+        return Some(account.to_string());
+    }
+    idna::punycode::encode_str(account).map(|encoded| format!("xn--{encoded}"))
+}
+
+/// Unicode form of an account given in punycode. ASCII accounts are returned as is.
+pub fn from_punycode(account: &str) -> Option<String> {
+    match account.strip_prefix("xn--") {
+        Some(encoded) => idna::punycode::decode_to_string(encoded),
+        None => Some(account.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn punycode_round_trip() {
+        assert_eq!(to_punycode("bitsong").unwrap(), "bitsong");
+        assert_eq!(to_punycode("café").unwrap(), "xn--caf-dma");
+        assert_eq!(from_punycode("xn--caf-dma").unwrap(), "café");
+        for account in ["音楽", "음악", "🎵🎵🎵"] {
+            assert_eq!(
+                from_punycode(&to_punycode(account).unwrap()).unwrap(),
+                account
+            );
+        }
+    }
+}
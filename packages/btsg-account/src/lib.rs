@@ -2,8 +2,13 @@ use abstract_std::objects::gov_type::GovernanceDetails;
 use abstract_std::objects::ownership::Ownership;
 use cosmwasm_std::{Addr, Deps, StdError};
 
+#[cfg(feature = "collection-hooks")]
+pub mod collection;
+pub mod idn;
 pub mod market;
+pub mod merkle;
 pub mod minter;
+pub mod timelock;
 pub mod verify_generic;
 
 // Query limits
@@ -1,3 +1,4 @@
+use crate::timelock::QueuedAction;
 use crate::TokenId;
 use bs_controllers::HooksResponse;
 use cosmwasm_schema::QueryResponses;
@@ -26,6 +27,9 @@ pub struct Bid {
     pub bidder: Addr,
     pub amount: Uint128,
     pub created_time: Timestamp,
+    /// Bidder agrees to skip the cooldown period if this bid is accepted
+    #[serde(default)]
+    pub waive_cooldown: bool,
 }
 
 impl Bid {
@@ -35,6 +39,7 @@ impl Bid {
             bidder,
             amount,
             created_time,
+            waive_cooldown: false,
         }
     }
 }
@@ -45,18 +50,133 @@ pub struct PendingBid {
     pub new_owner: Addr,
     pub amount: Uint128,
     pub unlock_time: Timestamp,
+    /// Cooldown duration of the tier the sale was accepted in (in seconds)
+    #[serde(default)]
+    pub cooldown: u64,
+    /// Fee the seller pays to cancel the sale, from the tier it was accepted in.
+    /// `None` for bundle sales, which can't be cancelled.
+    #[serde(default)]
+    pub cancel_fee: Option<Coin>,
 }
 impl PendingBid {
-    pub fn new(ask: Ask, new_owner: Addr, amount: Uint128, unlock_time: Timestamp) -> Self {
+    pub fn new(
+        ask: Ask,
+        new_owner: Addr,
+        amount: Uint128,
+        unlock_time: Timestamp,
+        cooldown: u64,
+        cancel_fee: Option<Coin>,
+    ) -> Self {
         PendingBid {
             ask,
             new_owner,
             amount,
             unlock_time,
+            cooldown,
+            cancel_fee,
         }
     }
 }
 
+/// The hook list a payload was sent to
+#[cosmwasm_schema::cw_serde]
+pub enum HookKind {
+    Ask,
+    Bid,
+    Sale,
+}
+
+/// A hook call that returned an error, kept so it can be retried
+#[cosmwasm_schema::cw_serde]
+pub struct FailedHook {
+    pub id: u64,
+    pub kind: HookKind,
+    pub hook: Addr,
+    pub msg: Binary,
+    pub error: String,
+    pub time: Timestamp,
+}
+
+/// The trade a validation hook is asked about
+#[cosmwasm_schema::cw_serde]
+pub enum ValidationAction {
+    SetBid,
+    /// Accepting a bid, a counter offer or a private sale
+    AcceptBid,
+}
+
+/// Answer of a validation hook. Code `0` is reserved for the marketplace's own checks.
+#[cosmwasm_schema::cw_serde]
+pub enum ValidationResponse {
+    Allow {},
+    Deny { code: u32, reason: String },
+}
+
+/// A completed sale, recorded when an account is transferred to its buyer
+#[cosmwasm_schema::cw_serde]
+pub struct Sale {
+    pub id: u64,
+    pub token_id: TokenId,
+    pub price: Uint128,
+    pub denom: String,
+    pub seller: Addr,
+    pub buyer: Addr,
+    pub time: Timestamp,
+}
+
+/// A seller's counter to an existing bid. The bidder may accept by topping up their escrow.
+#[cosmwasm_schema::cw_serde]
+pub struct CounterOffer {
+    pub token_id: TokenId,
+    pub seller: Addr,
+    pub bidder: Addr,
+    pub price: Uint128,
+    pub expires: Timestamp,
+}
+
+/// An account offered by its owner to a single buyer at a fixed price
+#[cosmwasm_schema::cw_serde]
+pub struct PrivateSale {
+    pub token_id: TokenId,
+    pub seller: Addr,
+    pub buyer: Addr,
+    pub price: Uint128,
+}
+
+/// A single bid escrowed across several accounts listed by the same seller
+#[cosmwasm_schema::cw_serde]
+pub struct BundleBid {
+    pub id: u64,
+    pub token_ids: Vec<TokenId>,
+    pub seller: Addr,
+    pub bidder: Addr,
+    pub amount: Uint128,
+    pub created_time: Timestamp,
+}
+
+/// An accepted bundle bid, waiting for its cooldown period to end
+#[cosmwasm_schema::cw_serde]
+pub struct PendingBundle {
+    pub bundle: BundleBid,
+    pub unlock_time: Timestamp,
+    /// Cooldown duration of the tier the bundle was accepted in (in seconds)
+    #[serde(default)]
+    pub cooldown: u64,
+}
+
+/// Circuit breaker for the marketplace
+#[cosmwasm_schema::cw_serde]
+#[derive(Default)]
+pub enum MarketStatus {
+    /// All actions are enabled
+    #[default]
+    Active,
+    /// New bids and sales are halted. Bidders may still remove their bids.
+    Paused,
+    /// Paused, and anyone may release all escrow back to bidders with `RefundEscrow`.
+    Emergency,
+}
+
 #[cosmwasm_schema::cw_serde]
 pub struct MarketplaceInstantiateMsg {
     /// Community pool fee for winning bids
@@ -97,9 +217,12 @@ pub enum ExecuteMsg {
         token_id: TokenId,
         seller: String,
     },
-    /// Place a bid on an existing ask
+    /// Place a bid on an existing ask.
+    /// If `waive_cooldown` is set, accepting the bid transfers the account immediately.
     SetBid {
         token_id: TokenId,
+        #[serde(default)]
+        waive_cooldown: bool,
     },
     /// Remove an existing bid from an ask.
     /// If bid is in cooldown period & current token_id owner is calling, this will revert
@@ -128,12 +251,83 @@ pub enum ExecuteMsg {
     CancelCooldown {
         token_id: TokenId,
     },
+    /// Counter an existing bid with a higher price. Only the token owner can call this.
+    CounterOffer {
+        token_id: TokenId,
+        bidder: String,
+        price: Uint128,
+        expires: Timestamp,
+    },
+    /// Accept a counter offer made on the senders bid.
+    /// Must include the difference between the counter offer and the existing bid.
+    AcceptCounterOffer {
+        token_id: TokenId,
+    },
+    /// Offer an account to a single buyer at a fixed price. Only the token owner can call this.
+    PrivateSale {
+        token_id: TokenId,
+        buyer: String,
+        price: Uint128,
+    },
+    /// Remove a private sale offer. Only the seller can call this.
+    CancelPrivateSale {
+        token_id: TokenId,
+    },
+    /// Accept a private sale offered to the sender. Must include the sale price.
+    AcceptPrivateSale {
+        token_id: TokenId,
+    },
+    /// Place a single bid on several accounts listed by the same seller.
+    /// Must include `amount` as payment.
+    SetBundleBid {
+        token_ids: Vec<TokenId>,
+        amount: Uint128,
+    },
+    /// Remove a bundle bid and refund the bidder. Only the bidder can call this.
+    RemoveBundleBid {
+        id: u64,
+    },
+    /// Accept a bundle bid. All accounts enter a single cooldown period.
+    AcceptBundleBid {
+        id: u64,
+    },
+    /// Transfer every account in an accepted bundle once the cooldown period is complete.
+    /// Anyone can call this.
+    FinalizeBundleBid {
+        id: u64,
+    },
+    /// Pause or resume the marketplace. Only the hooks admin can call this.
+    UpdateStatus {
+        status: MarketStatus,
+    },
+    /// Refund up to `limit` escrowed bids, accepted bids in cooldown, and bundle bids.
+    /// Anyone can call this while the marketplace is in emergency mode.
+    RefundEscrow {
+        limit: Option<u32>,
+    },
+    /// Resend a failed hook call. Only the hooks admin can call this.
+    RetryHook {
+        id: u64,
+    },
     Setup {
         minter: String,
         collection: String,
     },
-    /// Add a new hook to be informed of all asks
+    /// Add a new hook to be informed of all asks.
+    /// With an admin delay, hook changes must be proposed.
     ManageHooks(ManageHooksAction),
+    /// Queue a hook change, executable once the admin delay has passed. Only the hooks admin can call this.
+    ProposeManageHooks {
+        action: ManageHooksAction,
+    },
+    /// Execute a queued hook change once its delay has passed. Only the hooks admin can call this.
+    ExecuteManageHooks {
+        id: u64,
+    },
+    /// Cancel a queued hook change. Only the hooks admin can call this.
+    CancelManageHooks {
+        id: u64,
+    },
 }
 
 #[cosmwasm_schema::cw_serde]
@@ -144,6 +338,8 @@ pub enum ManageHooksAction {
     RemoveBidHook(String),
     AddSaleHook(String),
     RemoveSaleHook(String),
+    AddValidationHook(String),
+    RemoveValidationHook(String),
 }
 
 #[cosmwasm_schema::cw_serde]
@@ -216,6 +412,16 @@ pub enum QueryMsg {
     /// Show all registered sale hooks
     #[returns(HooksResponse)]
     SaleHooks {},
+    /// Show all registered validation hooks
+    #[returns(HooksResponse)]
+    ValidationHooks {},
+    /// Check whether `bidder` can bid `amount` on a token, including validation hooks
+    #[returns(ValidationResponse)]
+    CanBid {
+        token_id: TokenId,
+        bidder: String,
+        amount: Uint128,
+    },
     /// Get the config for the contract
     #[returns(SudoParams)]
     Params {},
@@ -224,6 +430,72 @@ pub enum QueryMsg {
     Config {},
     #[returns(Option<PendingBid>)]
     Cooldown { token_id: TokenId },
+    /// Get all counter offers made on bids for a token
+    #[returns(Vec<CounterOffer>)]
+    CounterOffers {
+        token_id: TokenId,
+        start_after: Option<Bidder>,
+        limit: Option<u32>,
+    },
+    /// Get the private sale offered for a token
+    #[returns(Option<PrivateSale>)]
+    PrivateSaleOffer { token_id: TokenId },
+    /// Get data for a specific bundle bid
+    #[returns(Option<BundleBid>)]
+    BundleBid { id: u64 },
+    /// Get all bundle bids a token is part of
+    #[returns(Vec<BundleBid>)]
+    BundleBidsByToken {
+        token_id: TokenId,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Get an accepted bundle bid in its cooldown period
+    #[returns(Option<PendingBundle>)]
+    BundleCooldown { id: u64 },
+    /// Get the current marketplace status
+    #[returns(MarketStatus)]
+    Status {},
+    /// Compare the escrow owed to bidders with the running escrow total and the marketplace balance.
+    /// Sums every escrow entry, so its cost grows with the number of open bids.
+    /// Bids are single-denom by design, only `NATIVE_DENOM` can be escrowed, so every amount is
+    /// in that denom and other funds sent to the marketplace are not counted.
+    #[returns(EscrowSummaryResponse)]
+    EscrowSummary {},
+    /// Get the sale history of a token, most recent first
+    #[returns(Vec<Sale>)]
+    SalesByToken {
+        token_id: TokenId,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Get all sales, most recent first
+    #[returns(Vec<Sale>)]
+    RecentSales {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Get the lowest price among recent sales of accounts with `length` characters
+    #[returns(Option<Coin>)]
+    FloorPrice { length: u32 },
+    /// Get the price a token last sold for
+    #[returns(Option<Coin>)]
+    LastSalePrice { token_id: TokenId },
+    /// Get hook calls that failed and can be retried
+    #[returns(Vec<FailedHook>)]
+    FailedHooks {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Get the seconds hook changes wait between being proposed and executed
+    #[returns(u64)]
+    AdminDelay {},
+    /// Get the queued hook changes
+    #[returns(Vec<QueuedAction<ManageHooksAction>>)]
+    QueuedHookActions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[cosmwasm_schema::cw_serde]
@@ -236,11 +508,32 @@ pub enum SudoMsg {
         ask_interval: Option<u64>,
         cooldown_duration: Option<u64>,
         cooldown_cancel_fee: Option<Coin>,
+        /// Replaces the cooldown schedule. Tiers must be sorted by ascending `min_price`.
+        cooldown_tiers: Option<Vec<CooldownTier>>,
     },
     /// Update the contract address of the account factory
     UpdateAccountFactory { factory: String },
     /// Update the contract address of the name collection
     UpdateAccountCollection { collection: String },
+    /// Pause or resume the marketplace
+    UpdateStatus { status: MarketStatus },
+    /// Update the seconds hook changes wait between being proposed and executed
+    UpdateAdminDelay { delay: u64 },
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct EscrowSummaryResponse {
+    /// Running escrow total, updated as funds are escrowed and released. All amounts are in
+    /// `NATIVE_DENOM`, the only denom bids accept.
+    pub tracked: Coin,
+    /// Funds owed to bidders, summed from open bids, bundle bids, and accepted sales in cooldown
+    pub escrowed: Coin,
+    /// Marketplace bank balance in `NATIVE_DENOM`
+    pub balance: Coin,
+    /// Whether the balance covers all escrow
+    pub solvent: bool,
+    /// Whether the running total matches the summed escrow
+    pub consistent: bool,
 }
 
 #[cosmwasm_schema::cw_serde]
@@ -284,9 +577,37 @@ pub struct SudoParams {
     pub ask_interval: u64,
     /// The number of bids to query to when searching for the highest bid
     pub valid_bid_query_limit: u32,
+    /// Cooldown for sales priced below the lowest tier
     pub cooldown_duration: u64,
+    /// Cancel fee for sales priced below the lowest tier
     pub cooldown_fee: Coin,
     pub hooks_admin: String,
+    /// Cooldown schedule by sale price, sorted by ascending `min_price`
+    #[serde(default)]
+    pub cooldown_tiers: Vec<CooldownTier>,
+}
+
+impl SudoParams {
+    /// Returns the cooldown duration and cancel fee for a sale at `price`,
+    /// taken from the highest tier the price reaches.
+    pub fn cooldown_for(&self, price: Uint128) -> (u64, Coin) {
+        self.cooldown_tiers
+            .iter()
+            .rev()
+            .find(|tier| price >= tier.min_price)
+            .map(|tier| (tier.duration, tier.cancel_fee.clone()))
+            .unwrap_or((self.cooldown_duration, self.cooldown_fee.clone()))
+    }
+}
+
+/// Cooldown applied to sales priced at or above `min_price`
+#[cosmwasm_schema::cw_serde]
+pub struct CooldownTier {
+    pub min_price: Uint128,
+    /// Time accepted bids are in escrow (in seconds)
+    pub duration: u64,
+    /// Fee required by the token owner to cancel an accepted bid
+    pub cancel_fee: Coin,
 }
 
 pub struct ParamInfo {
@@ -295,6 +616,7 @@ pub struct ParamInfo {
     pub ask_interval: Option<u64>,
     pub cooldown_duration: Option<u64>,
     pub cooldown_cancel_fee: Option<Coin>,
+    pub cooldown_tiers: Option<Vec<CooldownTier>>,
 }
 
 /// Offset for ask pagination
@@ -310,8 +632,15 @@ impl AskOffset {
     }
 }
 
+/// Params introduced by the new version, set in the same migration
 #[cosmwasm_schema::cw_serde]
-pub struct MigrateMsg {}
+#[derive(Default)]
+pub struct MigrateMsg {
+    /// Replaces the cooldown schedule. Tiers must be sorted by ascending `min_price`.
+    pub cooldown_tiers: Option<Vec<CooldownTier>>,
+    /// Seconds hook changes wait between being proposed and executed
+    pub admin_delay: Option<u64>,
+}
 
 #[cfg(feature = "market-hooks")]
 pub mod hooks {
@@ -348,6 +677,21 @@ pub mod hooks {
         SaleHook(SaleHookMsg),
     }
 
+    #[cosmwasm_schema::cw_serde]
+    pub struct ValidateBidMsg {
+        pub token_id: TokenId,
+        pub bidder: String,
+        pub amount: Uint128,
+        pub action: ValidationAction,
+    }
+
+    /// Query sent to validation hooks before a bid is placed or accepted.
+    /// Hooks answer with a `ValidationResponse`.
+    #[cosmwasm_schema::cw_serde]
+    pub enum ValidationHookQueryMsg {
+        ValidateBid(ValidateBidMsg),
+    }
+
     #[cosmwasm_schema::cw_serde]
     pub enum HookAction {
         Create,
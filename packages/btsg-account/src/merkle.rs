@@ -0,0 +1,32 @@
+use cosmwasm_std::{HexBinary, StdError, StdResult};
+use sha2::{Digest, Sha256};
+
+/// Hash of a single merkle leaf, e.g. an address.
+pub fn leaf_hash(leaf: &[u8]) -> [u8; 32] {
+    Sha256::digest(leaf).into()
+}
+
+/// Hashes two nodes in sorted order, so proofs do not need to carry their position.
+pub fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    Sha256::new()
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+/// A root must be a hex encoded sha256 hash.
+pub fn is_valid_root(root: &str) -> bool {
+    HexBinary::from_hex(root).is_ok_and(|r| r.len() == 32)
+}
+
+/// Verifies a proof of hex encoded sibling hashes for `leaf` against a hex encoded `root`.
+pub fn verify_proof(root: &str, leaf: &[u8], proof: &[String]) -> StdResult<bool> {
+    let root: [u8; 32] = HexBinary::from_hex(root)?.to_array()?;
+    let hash = proof.iter().try_fold(leaf_hash(leaf), |hash, sibling| {
+        let sibling: [u8; 32] = HexBinary::from_hex(sibling)?.to_array()?;
+        Ok::<_, StdError>(hash_pair(hash, sibling))
+    })?;
+    Ok(hash == root)
+}
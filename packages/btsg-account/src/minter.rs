@@ -1,8 +1,51 @@
+use cosmwasm_std::{Addr, Coin, Decimal, Timestamp, Uint128};
+
 #[cosmwasm_schema::cw_serde]
 pub struct Config {
     pub public_mint_start_time: cosmwasm_std::Timestamp,
 }
 
+/// Admin actions that wait for the admin delay set by governance: every action changing who can
+/// mint which accounts, when, or at what delegation. Exempt are pausing, which only stops minting
+/// and must stay immediate for emergencies, and vouchers, which only discount mints by holders
+/// of a code.
+#[cosmwasm_schema::cw_serde]
+pub enum AdminAction {
+    UpdateConfig {
+        config: Config,
+    },
+    Pause {
+        pause: bool,
+    },
+    AddWhitelist {
+        whitelist: Whitelist,
+    },
+    RemoveWhitelist {
+        id: u32,
+    },
+    UpdateBlocklist {
+        add: Vec<BlockRule>,
+        remove: Vec<BlockRule>,
+    },
+    UpdateReservations {
+        root: Option<String>,
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    BatchMint {
+        items: Vec<BatchMintItem>,
+    },
+    UpdateIdnConfig {
+        config: Option<IdnConfig>,
+    },
+    UpdateReferralConfig {
+        config: ReferralConfig,
+    },
+    UpdateDelegationPolicy {
+        policy: Option<DelegationPolicy>,
+    },
+}
+
 #[cosmwasm_schema::cw_serde]
 pub struct SudoParams {
     /// 3 (same as DNS)
@@ -15,6 +58,380 @@ pub struct SudoParams {
     pub base_delegation: cosmwasm_std::Uint128,
     // Fair Burn fee (rest goes to Community Pool)
     // pub fair_burn_percent: Decimal,
+    /// Price and delegation multipliers on top of the base values
+    #[serde(default)]
+    pub pricing: PricingSchedule,
+}
+
+/// Price and delegation multipliers for accounts of a given length
+#[cosmwasm_schema::cw_serde]
+pub struct LengthMultiplier {
+    pub length: u32,
+    pub price: Decimal,
+    pub delegation: Decimal,
+}
+
+/// Launch price decaying linearly from `start_multiplier` times the price when public minting
+/// starts, down to the price after `duration` seconds
+#[cosmwasm_schema::cw_serde]
+pub struct DutchAuction {
+    pub start_multiplier: Decimal,
+    pub duration: u64,
+}
+
+impl DutchAuction {
+    pub fn multiplier(&self, elapsed: u64) -> Decimal {
+        if elapsed >= self.duration {
+            return Decimal::one();
+        }
+        let decay = self.start_multiplier.saturating_sub(Decimal::one())
+            * Decimal::from_ratio(elapsed, self.duration);
+        self.start_multiplier - decay
+    }
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct PricingSchedule {
+    /// Lengths not listed are priced at the base values
+    pub length_multipliers: Vec<LengthMultiplier>,
+    /// Price multiplier for accounts in the premium word list
+    pub word_premium: Decimal,
+    /// Percent taken off the price of accounts made of digits only
+    pub numeric_discount: Option<Decimal>,
+    pub dutch_auction: Option<DutchAuction>,
+}
+
+/// 100x the base price and 5x the base delegation for 3 characters, 10x and 3x for 4
+impl Default for PricingSchedule {
+    fn default() -> Self {
+        Self {
+            length_multipliers: vec![
+                LengthMultiplier {
+                    length: 3,
+                    price: Decimal::from_atomics(100u128, 0).unwrap(),
+                    delegation: Decimal::from_atomics(5u128, 0).unwrap(),
+                },
+                LengthMultiplier {
+                    length: 4,
+                    price: Decimal::from_atomics(10u128, 0).unwrap(),
+                    delegation: Decimal::from_atomics(3u128, 0).unwrap(),
+                },
+            ],
+            word_premium: Decimal::one(),
+            numeric_discount: None,
+            dutch_auction: None,
+        }
+    }
+}
+
+impl PricingSchedule {
+    fn length_multiplier(&self, length: usize) -> Option<&LengthMultiplier> {
+        self.length_multipliers
+            .iter()
+            .find(|m| m.length as usize == length)
+    }
+
+    /// Price before mint discounts. `since_launch` is the time since public minting started.
+    pub fn price(
+        &self,
+        base_price: Uint128,
+        account: &str,
+        length: usize,
+        premium_word: bool,
+        since_launch: Option<u64>,
+    ) -> Uint128 {
+        let mut price = base_price.mul_floor(
+            self.length_multiplier(length)
+                .map_or(Decimal::one(), |m| m.price),
+        );
+        if premium_word {
+            price = price.mul_floor(self.word_premium);
+        }
+        if let Some(discount) = self.numeric_discount {
+            if account.chars().all(|c| c.is_ascii_digit()) {
+                price = price.mul_floor(Decimal::one() - discount);
+            }
+        }
+        if let (Some(auction), Some(elapsed)) = (&self.dutch_auction, since_launch) {
+            price = price.mul_floor(auction.multiplier(elapsed));
+        }
+        price
+    }
+
+    pub fn delegation(&self, base_delegation: Uint128, length: usize) -> Uint128 {
+        base_delegation.mul_floor(
+            self.length_multiplier(length)
+                .map_or(Decimal::one(), |m| m.delegation),
+        )
+    }
+
+    pub fn is_valid(&self) -> bool {
+        let valid_discount = match self.numeric_discount {
+            Some(discount) => discount <= Decimal::one(),
+            None => true,
+        };
+        let valid_auction = match &self.dutch_auction {
+            Some(auction) => auction.start_multiplier >= Decimal::one() && auction.duration > 0,
+            None => true,
+        };
+        valid_discount && valid_auction
+    }
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct MintPriceResponse {
+    /// Price before whitelist, referral and voucher discounts
+    pub price: Coin,
+    /// Tokens the minter must have delegated
+    pub delegation: Uint128,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub enum WhitelistType {
+    /// Contract answering `WhitelistQueryMsg::IncludesAddress`
+    Contract { address: String },
+    /// Hex encoded merkle root of the allowed addresses, see `crate::merkle`
+    Merkle { root: String },
+}
+
+/// A mint phase open to a list of addresses before public minting starts
+#[cosmwasm_schema::cw_serde]
+pub struct Whitelist {
+    pub list: WhitelistType,
+    pub start_time: cosmwasm_std::Timestamp,
+    pub end_time: cosmwasm_std::Timestamp,
+    /// Max accounts a single address can mint during this phase
+    pub per_address_limit: u32,
+    /// Percent taken off the account price during this phase
+    pub discount: Option<cosmwasm_std::Decimal>,
+}
+
+impl Whitelist {
+    pub fn is_active(&self, now: &cosmwasm_std::Timestamp) -> bool {
+        self.start_time <= *now && *now < self.end_time
+    }
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct WhitelistResponse {
+    pub id: u32,
+    pub whitelist: Whitelist,
+}
+
+/// A rule of the account blocklist
+#[cosmwasm_schema::cw_serde]
+pub enum BlockRule {
+    /// Blocks this account only
+    Name(String),
+    /// Blocks accounts matching the pattern, where `*` matches any characters and `?` a single one.
+    /// `*word*` blocks accounts containing `word`.
+    Pattern(String),
+}
+
+impl BlockRule {
+    pub fn matches(&self, account: &str) -> bool {
+        match self {
+            BlockRule::Name(name) => name == account,
+            BlockRule::Pattern(pattern) => glob_match(pattern, account),
+        }
+    }
+
+    /// Rules must be lowercase, and patterns can't be only wildcards
+    pub fn is_valid(&self) -> bool {
+        match self {
+            BlockRule::Name(name) => !name.is_empty() && *name == name.to_lowercase(),
+            BlockRule::Pattern(pattern) => {
+                pattern.chars().any(|c| c != '*' && c != '?') && *pattern == pattern.to_lowercase()
+            }
+        }
+    }
+}
+
+/// Matches `text` against a pattern of literal characters, `*` and `?`
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // the last `*` seen and the text position it matches up to, to backtrack to
+    let mut star = None;
+    while t < text.len() {
+        if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if let Some((star_p, star_t)) = star {
+            star = Some((star_p, star_t + 1));
+            p = star_p + 1;
+            t = star_t + 1;
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// An account minted by the admin with `BatchMint`
+#[cosmwasm_schema::cw_serde]
+pub struct BatchMintItem {
+    pub account: String,
+    pub owner: String,
+    pub metadata: crate::Metadata,
+}
+
+/// Why an account can't be minted
+#[cosmwasm_schema::cw_serde]
+pub enum MintError {
+    Paused,
+    NotStarted,
+    NotWhitelisted,
+    WhitelistLimitReached,
+    TooShort,
+    TooLong,
+    InvalidCharacters,
+    Confusable { existing: String },
+    Reserved,
+    Blocked,
+    Taken,
+    InsufficientDelegation,
+    DelegationNotHeld,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct CanMintResponse {
+    pub can_mint: bool,
+    /// The account as it would be minted, normalized in IDN mode
+    pub account: String,
+    /// Price before whitelist, referral and voucher discounts
+    pub price: Coin,
+    pub required_delegation: Uint128,
+    /// Tokens delegated by the minter, as counted by the delegation policy
+    pub delegation: Uint128,
+    pub taken: bool,
+    pub paused: bool,
+    /// Whether public minting started
+    pub started: bool,
+    pub errors: Vec<MintError>,
+}
+
+/// Which delegations count toward the mint requirement, set by the admin
+#[cosmwasm_schema::cw_serde]
+#[derive(Default)]
+pub struct DelegationPolicy {
+    /// Seconds a delegation must be held, from its `RegisterDelegation` snapshot, before it counts
+    pub holding_period: Option<u64>,
+    /// Only delegations to these validators count
+    pub validators: Option<Vec<String>>,
+    /// Only delegations to validators in the active set count
+    pub active_set_only: bool,
+    /// Whose delegation is checked when minting for another owner
+    #[serde(default)]
+    pub delegator: Delegator,
+}
+
+#[cosmwasm_schema::cw_serde]
+#[derive(Default)]
+pub enum Delegator {
+    /// The address sending `MintAndList`
+    #[default]
+    Sender,
+    /// The owner the account is minted for
+    Owner,
+}
+
+impl DelegationPolicy {
+    pub fn allows(&self, validator: &str) -> bool {
+        match &self.validators {
+            Some(validators) => validators.iter().any(|v| v == validator),
+            None => true,
+        }
+    }
+}
+
+/// Delegation registered by an address, held since `time`
+#[cosmwasm_schema::cw_serde]
+pub struct DelegationSnapshot {
+    pub amount: Uint128,
+    pub time: cosmwasm_std::Timestamp,
+}
+
+/// Delegation an account was minted on, and the address it was counted for
+#[cosmwasm_schema::cw_serde]
+pub struct MintDelegation {
+    pub delegator: Addr,
+    pub amount: Uint128,
+    /// Owner the account was minted to. The account can only be challenged while it still owns it.
+    pub owner: Addr,
+    /// Policy the delegation was counted with, so later policy changes don't affect the account
+    pub policy: Option<DelegationPolicy>,
+}
+
+/// An account whose delegator's stake dropped below the delegation it was minted on
+#[cosmwasm_schema::cw_serde]
+pub struct FlaggedAccount {
+    pub delegator: Addr,
+    pub required: Uint128,
+    /// Delegation counted when the account was challenged
+    pub delegation: Uint128,
+    pub time: Timestamp,
+}
+
+/// Internationalized account names, disabled unless set by the admin
+#[cosmwasm_schema::cw_serde]
+pub struct IdnConfig {
+    /// Unicode scripts allowed besides ASCII, by full name, e.g. `Han`, `Hangul`, `Latin`
+    pub scripts: Vec<String>,
+    pub allow_emoji: bool,
+}
+
+/// Rewards for referring a minter, set by the admin
+#[cosmwasm_schema::cw_serde]
+pub struct ReferralConfig {
+    /// Share of the mint fee sent to the referrer, the rest is burned
+    pub fee_share: cosmwasm_std::Decimal,
+    /// Percent taken off the account price when minting with a referrer
+    pub discount: cosmwasm_std::Decimal,
+}
+
+#[cosmwasm_schema::cw_serde]
+#[derive(Default)]
+pub struct ReferralStats {
+    pub mints: u32,
+    pub rewards: cosmwasm_std::Uint128,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct Voucher {
+    /// Percent taken off the account price
+    pub discount: cosmwasm_std::Decimal,
+    /// Only this address can use the voucher when set. Otherwise anyone holding the code can,
+    /// after committing to it with `CommitVoucher` in an earlier block.
+    pub redeemer: Option<cosmwasm_std::Addr>,
+    pub max_uses: u32,
+    pub uses: u32,
+}
+
+/// Hex encoded sha256 hash an address commits to before minting with a voucher that has no
+/// redeemer. It doesn't reveal the code, and a code copied from a pending mint is useless
+/// without a commitment by the copier in an earlier block.
+pub fn voucher_commitment(code: &str, address: &str) -> String {
+    use sha2::{Digest, Sha256};
+    let hash = Sha256::digest(format!("{code}:{address}").as_bytes());
+    cosmwasm_std::HexBinary::from(hash.as_slice()).to_hex()
+}
+
+/// Merkle leaf of a reserved account and the address that can claim it
+pub fn reservation_leaf(account: &str, address: &str) -> Vec<u8> {
+    format!("{account}:{address}").into_bytes()
+}
+
+/// Query a whitelist contract must implement
+#[cosmwasm_schema::cw_serde]
+#[derive(cosmwasm_schema::QueryResponses)]
+pub enum WhitelistQueryMsg {
+    #[returns(bool)]
+    IncludesAddress { address: String },
 }
 
 #[cosmwasm_schema::cw_serde]
@@ -22,7 +439,7 @@ pub struct SudoParams {
 pub enum BsAccountMinterQueryMsg {
     #[returns(cw_ownable::Ownership<cosmwasm_std::Addr>)]
     Admin {},
-    #[returns(Vec<cosmwasm_std::Addr>)]
+    #[returns(Vec<WhitelistResponse>)]
     Whitelists {},
     #[returns(cosmwasm_std::Addr)]
     Collection {},
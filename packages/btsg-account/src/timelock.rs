@@ -0,0 +1,107 @@
+//! Admin actions that are proposed, then executed once a delay set by governance has passed,
+//! so the community can react to them. The current delay applies to queued actions too,
+//! so governance raising it also postpones actions already proposed.
+use cosmwasm_schema::serde::{de::DeserializeOwned, Serialize};
+use cosmwasm_std::{Order, StdResult, Storage, Timestamp};
+use cw_storage_plus::{Bound, Item, Map};
+
+use crate::{DEFAULT_QUERY_LIMIT, MAX_QUERY_LIMIT};
+
+#[cosmwasm_schema::cw_serde]
+pub struct QueuedAction<T> {
+    pub id: u64,
+    pub action: T,
+    pub proposed_at: Timestamp,
+    /// Earliest time the action can be executed, with the current delay
+    pub executable_at: Timestamp,
+}
+
+impl<T> QueuedAction<T> {
+    pub fn is_ready(&self, now: &Timestamp) -> bool {
+        self.executable_at <= *now
+    }
+}
+
+pub struct Timelock<T> {
+    delay: Item<u64>,
+    count: Item<u64>,
+    queue: Map<u64, QueuedAction<T>>,
+}
+
+impl<T: Serialize + DeserializeOwned> Timelock<T> {
+    pub const fn new(
+        delay_key: &'static str,
+        count_key: &'static str,
+        queue_key: &'static str,
+    ) -> Self {
+        Self {
+            delay: Item::new(delay_key),
+            count: Item::new(count_key),
+            queue: Map::new(queue_key),
+        }
+    }
+
+    /// Seconds between proposing and executing an action. Actions aren't queued when 0.
+    pub fn delay(&self, storage: &dyn Storage) -> StdResult<u64> {
+        Ok(self.delay.may_load(storage)?.unwrap_or_default())
+    }
+
+    pub fn set_delay(&self, storage: &mut dyn Storage, delay: u64) -> StdResult<()> {
+        self.delay.save(storage, &delay)
+    }
+
+    pub fn propose(
+        &self,
+        storage: &mut dyn Storage,
+        now: Timestamp,
+        action: T,
+    ) -> StdResult<QueuedAction<T>> {
+        let id = self.count.may_load(storage)?.unwrap_or_default() + 1;
+        self.count.save(storage, &id)?;
+        let queued = QueuedAction {
+            id,
+            action,
+            proposed_at: now,
+            executable_at: now.plus_seconds(self.delay(storage)?),
+        };
+        self.queue.save(storage, id, &queued)?;
+        Ok(queued)
+    }
+
+    /// Removes an action from the queue, to execute or cancel it
+    pub fn take(&self, storage: &mut dyn Storage, id: u64) -> StdResult<QueuedAction<T>> {
+        let queued = self.queue.load(storage, id)?;
+        self.queue.remove(storage, id);
+        self.with_current_delay(storage, queued)
+    }
+
+    fn with_current_delay(
+        &self,
+        storage: &dyn Storage,
+        queued: QueuedAction<T>,
+    ) -> StdResult<QueuedAction<T>> {
+        Ok(QueuedAction {
+            executable_at: queued.proposed_at.plus_seconds(self.delay(storage)?),
+            ..queued
+        })
+    }
+
+    pub fn query(
+        &self,
+        storage: &dyn Storage,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<QueuedAction<T>>> {
+        let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+        self.queue
+            .range(
+                storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|item| self.with_current_delay(storage, item?.1))
+            .collect()
+    }
+}
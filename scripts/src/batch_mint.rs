@@ -0,0 +1,88 @@
+//! Submits the minter's admin `BatchMint` in chunks, from a CSV of `account,owner,metadata` rows.
+//! The number of rows minted is written to a checkpoint file after each chunk,
+//! so an interrupted import resumes where it stopped.
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::bail;
+use bs721_account_minter::interface::BtsgAccountMinter;
+use btsg_account::minter::BatchMintItem;
+use btsg_account::Metadata;
+use cw_orch::prelude::*;
+use serde::Deserialize;
+
+use crate::BtsgAccountMinterExecuteFns;
+
+#[derive(Debug, Clone, Deserialize)]
+struct BatchRow {
+    account: String,
+    owner: String,
+    /// JSON encoded `Metadata`, defaults when empty
+    #[serde(default)]
+    metadata: Option<String>,
+}
+
+pub fn read_items(reader: impl Read) -> anyhow::Result<Vec<BatchMintItem>> {
+    let rows = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(reader)
+        .deserialize()
+        .collect::<Result<Vec<BatchRow>, _>>()?;
+    if rows.is_empty() {
+        bail!("no accounts to mint");
+    }
+
+    rows.into_iter()
+        .map(|row| {
+            let metadata = match row.metadata.as_deref() {
+                None | Some("") => Metadata::default(),
+                Some(json) => serde_json::from_str(json)?,
+            };
+            Ok(BatchMintItem {
+                account: row.account,
+                owner: row.owner,
+                metadata,
+            })
+        })
+        .collect()
+}
+
+/// Rows already minted, 0 when there is no checkpoint yet
+pub fn load_checkpoint(path: &Path) -> anyhow::Result<usize> {
+    match std::fs::read_to_string(path) {
+        Ok(minted) => Ok(minted.trim().parse()?),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(0),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Mints `items` in chunks of `chunk_size`, skipping the rows recorded in `checkpoint`.
+/// Returns the number of rows minted by this call.
+pub fn submit<Chain: CwEnv>(
+    minter: &BtsgAccountMinter<Chain>,
+    items: &[BatchMintItem],
+    chunk_size: usize,
+    checkpoint: &Path,
+) -> anyhow::Result<usize> {
+    if chunk_size == 0 || chunk_size > bs721_account_minter::state::MAX_BATCH_MINT {
+        bail!(
+            "chunk size must be between 1 and {}",
+            bs721_account_minter::state::MAX_BATCH_MINT
+        );
+    }
+    let start = load_checkpoint(checkpoint)?;
+    if start > items.len() {
+        bail!("checkpoint is past the end of the input");
+    }
+
+    let mut minted = start;
+    for chunk in items[start..].chunks(chunk_size) {
+        minter.batch_mint(chunk.to_vec())?;
+        minted += chunk.len();
+        std::fs::write(checkpoint, minted.to_string())?;
+        log::info!("minted {minted}/{}", items.len());
+    }
+
+    Ok(minted - start)
+}
@@ -0,0 +1,91 @@
+#![cfg(not(test))]
+use std::{fs::File, path::PathBuf};
+
+use bs721_account_minter::interface::BtsgAccountMinter;
+use btsg_account_scripts::{
+    batch_mint::{read_items, submit},
+    networks::{ping_grpc, BITSONG_MAINNET, BITSONG_TESTNET},
+};
+use clap::Parser;
+use cw_orch::{daemon::DaemonBuilder, prelude::*};
+use tokio::runtime::Runtime;
+
+/// Mint accounts from a CSV with the minter's admin `BatchMint`, resuming from a checkpoint
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Network to mint on: main, testnet
+    #[clap(short, long, default_value = "main")]
+    network: String,
+
+    /// Minter contract address
+    #[clap(short, long)]
+    minter: String,
+
+    /// CSV file with an `account,owner,metadata` header. `metadata` is optional JSON
+    #[clap(short, long)]
+    input: PathBuf,
+
+    /// Accounts minted per transaction
+    #[clap(short, long, default_value = "25")]
+    chunk_size: usize,
+
+    /// File recording the rows already minted. Defaults to the input path with a `.checkpoint` extension
+    #[clap(long)]
+    checkpoint: Option<PathBuf>,
+
+    /// Execute through an AuthZ grant of the minter admin
+    #[clap(long)]
+    granter: Option<String>,
+}
+
+fn main() {
+    let args = Args::parse();
+    env_logger::init();
+    dotenv::dotenv().ok();
+
+    if let Err(ref err) = batch_mint(args) {
+        log::error!("{}", err);
+        err.chain()
+            .skip(1)
+            .for_each(|cause| log::error!("because: {}", cause));
+
+        ::std::process::exit(1);
+    }
+}
+
+fn batch_mint(args: Args) -> anyhow::Result<()> {
+    let network: ChainInfoOwned = match args.network.as_str() {
+        "main" => BITSONG_MAINNET.to_owned(),
+        "testnet" => BITSONG_TESTNET.to_owned(),
+        _ => panic!("Invalid network"),
+    }
+    .into();
+
+    let rt = Runtime::new()?;
+    for url in network.grpc_urls.iter() {
+        rt.block_on(ping_grpc(url))?;
+    }
+    let mut chain = DaemonBuilder::new(network).handle(rt.handle()).build()?;
+    if let Some(granter) = &args.granter {
+        chain
+            .sender_mut()
+            .set_authz_granter(&Addr::unchecked(granter));
+    }
+
+    let minter = BtsgAccountMinter::new("bs721_account_minter", chain);
+    minter.set_address(&Addr::unchecked(&args.minter));
+
+    let items = read_items(File::open(&args.input)?)?;
+    let checkpoint = args
+        .checkpoint
+        .unwrap_or_else(|| args.input.with_extension("checkpoint"));
+    let minted = submit(&minter, &items, args.chunk_size, &checkpoint)?;
+    println!(
+        "Minted {minted} accounts, {} in total. Checkpoint: {}",
+        items.len(),
+        checkpoint.display()
+    );
+
+    Ok(())
+}
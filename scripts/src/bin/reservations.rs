@@ -0,0 +1,30 @@
+use std::{fs::File, path::PathBuf};
+
+use btsg_account_scripts::reservations::ReservationTree;
+use clap::Parser;
+
+/// Build the minter's reservation merkle root and the proofs for each claimant
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// CSV file with an `account,address` header
+    #[clap(short, long)]
+    input: PathBuf,
+
+    /// Where to write the root and proofs as JSON. Defaults to stdout
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    let tree = ReservationTree::from_csv(File::open(&args.input)?)?;
+    let json = serde_json::to_string_pretty(&tree)?;
+    match args.output {
+        Some(path) => std::fs::write(path, json)?,
+        None => println!("{json}"),
+    }
+
+    Ok(())
+}
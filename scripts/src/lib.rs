@@ -1,6 +1,8 @@
 pub mod suite;
 pub use suite::BtsgAccountSuite;
 pub mod networks;
+pub mod reservations;
+pub mod batch_mint;
 
 // re-export contract cw-orch functions & entrypoint types
 pub use bs721_account::msg::{
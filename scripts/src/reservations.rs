@@ -0,0 +1,111 @@
+//! Builds the reservation merkle tree checked by the minter's `ClaimReserved`,
+//! from a CSV of `account,address` rows.
+use std::collections::HashSet;
+use std::io::Read;
+
+use anyhow::bail;
+use btsg_account::merkle::{hash_pair, leaf_hash};
+use btsg_account::minter::reservation_leaf;
+use cosmwasm_std::HexBinary;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Reservation {
+    pub account: String,
+    pub address: String,
+}
+
+/// What a claimant needs to call `ClaimReserved`
+#[derive(Debug, Clone, Serialize)]
+pub struct Claim {
+    pub account: String,
+    pub address: String,
+    pub proof: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReservationTree {
+    /// Hex encoded root, set on the minter with `UpdateReservations`
+    pub root: String,
+    pub claims: Vec<Claim>,
+}
+
+impl ReservationTree {
+    pub fn new(reservations: Vec<Reservation>) -> anyhow::Result<Self> {
+        if reservations.is_empty() {
+            bail!("no reservations");
+        }
+        let mut accounts = HashSet::new();
+        for r in &reservations {
+            if !accounts.insert(r.account.as_str()) {
+                bail!("account {} is reserved more than once", r.account);
+            }
+        }
+
+        let leaves = reservations
+            .iter()
+            .map(|r| leaf_hash(&reservation_leaf(&r.account, &r.address)))
+            .collect();
+        let layers = build_layers(leaves);
+        let claims = reservations
+            .into_iter()
+            .enumerate()
+            .map(|(i, r)| Claim {
+                account: r.account,
+                address: r.address,
+                proof: proof(&layers, i),
+            })
+            .collect();
+
+        Ok(Self {
+            root: HexBinary::from(layers[layers.len() - 1][0]).to_hex(),
+            claims,
+        })
+    }
+
+    pub fn from_csv(reader: impl Read) -> anyhow::Result<Self> {
+        let reservations = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(reader)
+            .deserialize()
+            .collect::<Result<Vec<Reservation>, _>>()?;
+        Self::new(reservations)
+    }
+
+    /// Reserved accounts, to send with `UpdateReservations { add, .. }`
+    pub fn accounts(&self) -> Vec<String> {
+        self.claims.iter().map(|c| c.account.clone()).collect()
+    }
+
+    pub fn claim(&self, account: &str) -> Option<&Claim> {
+        self.claims.iter().find(|c| c.account == account)
+    }
+}
+
+/// Layers from the leaves up to the root. A node without a sibling moves up unchanged.
+fn build_layers(leaves: Vec<[u8; 32]>) -> Vec<Vec<[u8; 32]>> {
+    let mut layers = vec![leaves];
+    while layers[layers.len() - 1].len() > 1 {
+        let next = layers[layers.len() - 1]
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => hash_pair(*a, *b),
+                [a] => *a,
+                _ => unreachable!(),
+            })
+            .collect();
+        layers.push(next);
+    }
+    layers
+}
+
+fn proof(layers: &[Vec<[u8; 32]>], mut index: usize) -> Vec<String> {
+    layers[..layers.len() - 1]
+        .iter()
+        .filter_map(|layer| {
+            let sibling = layer.get(index ^ 1);
+            index /= 2;
+            sibling.map(|s| HexBinary::from(*s).to_hex())
+        })
+        .collect()
+}
@@ -1,5 +1,5 @@
 use crate::BtsgAccountMarketExecuteFns;
-use abstract_interface::{AbstractIbc, AccountI, AnsHost, ModuleFactory, Registry};
+use abstract_interface::{Abstract, AbstractIbc, AccountI, AnsHost, ModuleFactory, Registry};
 use abstract_std::{native_addrs, ACCOUNT, ANS_HOST, MODULE_FACTORY, REGISTRY};
 use account_registry_middleware::interface::AccountRegistryMiddleware;
 use account_registry_middleware::ExecuteMsgFns;
@@ -8,16 +8,17 @@ use bs721_account::interface::BtsgAccountCollection;
 use bs721_account_marketplace::interface::BtsgAccountMarket;
 use bs721_account_minter::interface::BtsgAccountMinter;
 use btsg_account::{
-    Metadata, CURRENT_BASE_DELEGATION, CURRENT_BASE_PRICE, CURRENT_COOLDOWN_FEE,
-    CURRENT_MINIMUM_BID_PRICE,
+    market::ManageHooksAction, Metadata, CURRENT_BASE_DELEGATION, CURRENT_BASE_PRICE,
+    CURRENT_COOLDOWN_FEE, CURRENT_MINIMUM_BID_PRICE,
 };
 use cosmwasm_std::{
     coin, instantiate2_address, Binary, CanonicalAddr, Instantiate2AddressError, Uint128,
 };
 use cw_blob::interface::{CwBlob, DeterministicInstantiation};
+use cw_ownable::{Action, Ownership};
 use ownership_verifier::interface::TestingOwnershipVerifier;
 
-use cw_orch::prelude::*;
+use cw_orch::{mock::MockBech32, prelude::*};
 pub struct BtsgAccountSuite<Chain>
 where
     Chain: cw_orch::prelude::CwEnv,
@@ -81,6 +82,49 @@ impl<Chain: CwEnv> BtsgAccountSuite<Chain> {
     }
 }
 
+impl BtsgAccountSuite<MockBech32> {
+    /// Deploys the abstract framework and the registry middleware on top of an existing setup.
+    /// The middleware takes ownership of the registry and is added as an ask hook to the marketplace,
+    /// so every account token listed afterwards gets an abstract account and namespace.
+    pub fn deploy_abstract(&mut self, mock: MockBech32) -> anyhow::Result<()> {
+        Abstract::deploy_on(mock.clone(), ())?;
+
+        self.middleware.upload()?;
+        self.middleware.instantiate(
+            &account_registry_middleware::InstantiateMsg {
+                market: self.market.addr_str()?,
+                collection: self.nft.addr_str()?,
+                account_code_id: self.account.code_id()?,
+            },
+            Some(&mock.sender_addr()),
+            &[],
+        )?;
+        self.middleware
+            .update_config(None, None, None, Some(self.registry.addr_str()?))?;
+
+        // hand registry ownership to the middleware, which accepts it as a routed registry action
+        let ownership: Ownership<Addr> = self
+            .registry
+            .query(&abstract_std::registry::QueryMsg::Ownership {})?;
+        let registry_owner = ownership.owner.ok_or(anyhow!("registry has no owner"))?;
+        self.registry.call_as(&registry_owner).execute(
+            &abstract_std::registry::ExecuteMsg::UpdateOwnership(Action::TransferOwnership {
+                new_owner: self.middleware.addr_str()?,
+                expiry: None,
+            }),
+            &[],
+        )?;
+        self.middleware
+            .registry_action(abstract_std::registry::ExecuteMsg::UpdateOwnership(
+                Action::AcceptOwnership,
+            ))?;
+
+        self.market
+            .manage_hooks(ManageHooksAction::AddAskHook(self.middleware.addr_str()?))?;
+        Ok(())
+    }
+}
+
 // Bitsong Accounts `Deploy` Suite
 impl<Chain: CwEnv> cw_orch::contract::Deploy<Chain> for BtsgAccountSuite<Chain> {
     // We don't have a custom error type
@@ -141,6 +185,7 @@ impl<Chain: CwEnv> cw_orch::contract::Deploy<Chain> for BtsgAccountSuite<Chain>
                     base_delegation: CURRENT_BASE_DELEGATION.into(),
                     marketplace_addr: suite.market.addr_str()?,
                     mint_start_delay: None,
+                    pricing: None,
                 },
                 Some(&Addr::unchecked(data.clone())),
                 &[],
@@ -1,6 +1,7 @@
 use crate::BtsgAccountExecuteFns;
+use crate::BtsgAccountMarketQueryFns;
 use ::bs721_account::{commands::transcode, ContractError};
-use bs721_account::msg::Bs721AccountsQueryMsgFns;
+use bs721_account::msg::{Bs721AccountsQueryMsgFns, ManageHooksAction};
 use bs721_account::state::REVERSE_MAP_KEY;
 use btsg_account::verify_generic::{
     preamble_msg_arb_036, pubkey_to_address, CosmosArbitrary, TestCosmosArb,
@@ -247,6 +248,72 @@ fn test_burn_function() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_transfer_and_metadata_hooks() -> anyhow::Result<()> {
+    let mock = MockBech32::new("bitsong");
+    let mut suite = BtsgAccountSuite::new(mock.clone());
+    suite.default_setup(mock.clone(), None, None)?;
+    mock.wait_seconds(200u64)?;
+    let admin = mock.addr_make("admin2");
+    let token_id = "enterprise";
+    suite.mint_and_list(mock.clone(), token_id, &mock.sender.clone())?;
+
+    // an address without a contract rejects every hook call
+    let hook = mock.addr_make("hook");
+
+    // only the contract admin can manage hooks
+    let err = suite
+        .nft
+        .manage_hooks(ManageHooksAction::AddTransferHook(hook.to_string()))
+        .unwrap_err();
+    assert_eq!(
+        err.root().to_string(),
+        ContractError::UnauthorizedCreatorOrAdmin {}.to_string()
+    );
+    suite
+        .nft
+        .call_as(&admin)
+        .manage_hooks(ManageHooksAction::AddTransferHook(hook.to_string()))?;
+    suite
+        .nft
+        .call_as(&admin)
+        .manage_hooks(ManageHooksAction::AddMetadataHook(hook.to_string()))?;
+    assert_eq!(suite.nft.transfer_hooks()?.hooks, vec![hook.to_string()]);
+    assert_eq!(suite.nft.metadata_hooks()?.hooks, vec![hook.to_string()]);
+
+    // a failing hook does not block record updates
+    let res = suite
+        .nft
+        .add_text_record(token_id, TextRecord::new("twitter", "bitsong"))?;
+    assert_eq!(
+        res.event_attr_value("wasm-hook-failed", "kind")?,
+        "metadata"
+    );
+
+    // nor transfers, which still update the marketplace ask
+    let recipient = mock.addr_make("recipient");
+    let res = suite.nft.transfer_nft(recipient.to_string(), token_id)?;
+    assert_eq!(
+        res.event_attr_value("wasm-hook-failed", "kind")?,
+        "transfer"
+    );
+    assert_eq!(suite.owner_of(token_id.to_string())?, recipient.to_string());
+    assert_eq!(
+        suite.market.ask(token_id.to_string())?.unwrap().seller,
+        recipient
+    );
+
+    // removed hooks are no longer called
+    suite
+        .nft
+        .call_as(&admin)
+        .manage_hooks(ManageHooksAction::RemoveTransferHook(hook.to_string()))?;
+    let res = suite.nft.call_as(&recipient).burn(token_id)?;
+    assert!(res.event_attr_value("wasm-hook-failed", "kind").is_err());
+
+    Ok(())
+}
+
 #[test]
 fn test_reverse_map_key_limit() -> anyhow::Result<()> {
     let mock = MockBech32::new("bitsong");
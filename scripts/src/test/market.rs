@@ -1 +1,141 @@
+use btsg_account::market::ExecuteMsg;
+use cosmwasm_std::{coins, Uint128};
+use cw_orch::{anyhow, mock::MockBech32, prelude::*};
 
+use crate::{
+    BtsgAccountExecuteFns, BtsgAccountMarketExecuteFns, BtsgAccountMarketQueryFns, BtsgAccountSuite,
+};
+
+const BID_AMOUNT: u128 = 1_000_000_000;
+const CANCEL_FEE: u128 = 500_000_000;
+const TOKENS: [&str; 3] = ["escrow-one", "escrow-two", "escrow-three"];
+
+/// Small deterministic PRNG, so a failing sequence can be replayed from its seed.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// Escrow tracked by the marketplace, escrow summed by the marketplace, and the sum of open
+/// and accepted bids must all agree, and be fully backed by the contract balance.
+fn assert_escrow_invariant(
+    suite: &BtsgAccountSuite<MockBech32>,
+    seed: u64,
+    step: usize,
+) -> anyhow::Result<()> {
+    let mut expected = Uint128::zero();
+    for token_id in TOKENS {
+        for bid in suite.market.bids(token_id.to_string(), None, None)? {
+            expected += bid.amount;
+        }
+        if let Some(pending) = suite.market.cooldown(token_id.to_string())? {
+            expected += pending.amount;
+        }
+    }
+
+    let summary = suite.market.escrow_summary()?;
+    assert_eq!(
+        summary.tracked.amount, expected,
+        "seed {seed}, step {step}: tracked escrow does not match bids"
+    );
+    assert_eq!(
+        summary.escrowed.amount, expected,
+        "seed {seed}, step {step}: summed escrow does not match bids"
+    );
+    assert_eq!(
+        summary.balance.amount, expected,
+        "seed {seed}, step {step}: balance does not match escrow"
+    );
+    assert!(summary.solvent);
+    assert!(summary.consistent);
+    Ok(())
+}
+
+/// Runs a random sequence of marketplace actions, checking the escrow invariant after every step.
+/// Actions that are invalid in the current state are expected to fail without moving funds.
+fn run_sequence(seed: u64, steps: usize) -> anyhow::Result<()> {
+    let mock = MockBech32::new("bitsong");
+    let mut suite = BtsgAccountSuite::new(mock.clone());
+    suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+    mock.wait_seconds(200)?;
+
+    let owner = mock.sender.clone();
+    for token_id in TOKENS {
+        suite.mint_and_list(mock.clone(), token_id, &owner)?;
+    }
+    mock.add_balance(&owner, coins(CANCEL_FEE * 100, "ubtsg"))?;
+
+    // bidders approve the market so they can resell accounts they win
+    let market = suite.market.address()?;
+    let bidders = (0..4)
+        .map(|i| mock.addr_make(format!("bidder{}", i)))
+        .collect::<Vec<_>>();
+    for bidder in &bidders {
+        mock.add_balance(bidder, coins(BID_AMOUNT * 100, "ubtsg"))?;
+        suite
+            .nft
+            .call_as(bidder)
+            .approve_all(market.clone(), None)?;
+    }
+
+    let mut rng = XorShift(seed);
+    let mut applied = 0;
+    for step in 0..steps {
+        let token_id = TOKENS[rng.below(TOKENS.len())].to_string();
+        let bidder = bidders[rng.below(bidders.len())].clone();
+        let seller = Addr::unchecked(suite.owner_of(token_id.clone())?);
+
+        let res = match rng.below(6) {
+            0 | 1 => {
+                let amount = BID_AMOUNT + rng.below(10) as u128 * 1_000_000;
+                suite.market.call_as(&bidder).execute(
+                    &ExecuteMsg::SetBid {
+                        token_id,
+                        waive_cooldown: false,
+                    },
+                    &coins(amount, "ubtsg"),
+                )
+            }
+            2 => suite.market.call_as(&bidder).remove_bid(token_id),
+            3 => suite
+                .market
+                .call_as(&seller)
+                .accept_bid(bidder.to_string(), token_id),
+            4 => suite.market.call_as(&seller).execute(
+                &ExecuteMsg::CancelCooldown { token_id },
+                &coins(CANCEL_FEE, "ubtsg"),
+            ),
+            _ => {
+                mock.wait_seconds(rng.below(90) as u64)?;
+                suite.market.finalize_bid(token_id)
+            }
+        };
+        if res.is_ok() {
+            applied += 1;
+        }
+
+        assert_escrow_invariant(&suite, seed, step)?;
+    }
+    assert!(applied > 0, "seed {seed}: no action succeeded");
+    Ok(())
+}
+
+#[test]
+fn test_escrow_invariant_random_sequences() -> anyhow::Result<()> {
+    for seed in [1, 42, 1337, 0xb175] {
+        run_sequence(seed, 60)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use abstract_interface::{Abstract, AccountI};
+use abstract_std::objects::{
+    gov_type::GovernanceDetails, namespace::Namespace, ownership::Ownership,
+};
+use abstract_std::registry::{
+    ExecuteMsg as RegistryExecuteMsg, NamespaceResponse, QueryMsg as RegistryQueryMsg,
+};
+use account_registry_middleware::{
+    account_id, token_namespace, ContractError as MiddlewareContractError, ACCOUNT_ID_OFFSET,
+};
+use btsg_account::{market::HookKind, minter::IdnConfig};
+use cw_orch::{anyhow, mock::MockBech32, prelude::*};
+use cw_ownable::Action;
+
+use super::BASE_PRICE;
+use crate::{
+    AccountRegistryExecuteFns, BtsgAccountMarketExecuteFns, BtsgAccountMarketQueryFns,
+    BtsgAccountMinterExecuteFns, BtsgAccountSuite,
+};
+
+fn namespace(
+    suite: &BtsgAccountSuite<MockBech32>,
+    token_id: &str,
+) -> anyhow::Result<NamespaceResponse> {
+    Ok(suite.registry.query(&RegistryQueryMsg::Namespace {
+        namespace: Namespace::new(token_id)?,
+    })?)
+}
+
+#[test]
+fn test_middleware_claims_namespace() -> anyhow::Result<()> {
+    let mock = MockBech32::new("bitsong");
+    let mut suite = BtsgAccountSuite::new(mock.clone());
+    suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+    suite.deploy_abstract(mock.clone())?;
+    mock.wait_seconds(200)?;
+
+    let owner = mock.sender.clone();
+    suite.mint_and_list(mock.clone(), "first", &owner)?;
+    let ask = suite.market.ask("first".to_string())?.unwrap();
+
+    // the account is created with the ask id and owned by the account token
+    let NamespaceResponse::Claimed(info) = namespace(&suite, "first")? else {
+        panic!("namespace was not claimed");
+    };
+    assert_eq!(info.account_id, account_id(ask.id)?);
+    let ownership: Ownership<String> = mock.query(
+        &abstract_std::account::QueryMsg::Ownership {},
+        info.account.addr(),
+    )?;
+    assert_eq!(
+        ownership.owner,
+        GovernanceDetails::NFT {
+            collection_addr: suite.nft.addr_str()?,
+            token_id: "first".to_string(),
+        }
+    );
+    assert!(suite.market.failed_hooks(None, None)?.is_empty());
+
+    // without registry ownership the claim fails, which the marketplace records without blocking the mint
+    suite
+        .middleware
+        .registry_action(RegistryExecuteMsg::UpdateOwnership(
+            Action::TransferOwnership {
+                new_owner: owner.to_string(),
+                expiry: None,
+            },
+        ))?;
+    suite.registry.execute(
+        &RegistryExecuteMsg::UpdateOwnership(Action::AcceptOwnership),
+        &[],
+    )?;
+    suite.mint_and_list(mock.clone(), "second", &owner)?;
+    assert_eq!(suite.owner_of("second".to_string())?, owner.to_string());
+    assert!(matches!(
+        namespace(&suite, "second")?,
+        NamespaceResponse::Unclaimed {}
+    ));
+
+    let failed = suite.market.failed_hooks(None, None)?;
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].kind, HookKind::Ask);
+    assert_eq!(failed[0].hook, suite.middleware.address()?);
+    assert!(failed[0].error.contains(
+        &MiddlewareContractError::NamespaceClaimFailed {
+            token_id: "second".to_string(),
+            error: String::new(),
+        }
+        .to_string()
+    ));
+
+    // once ownership is handed back, retrying the hook creates the account and claims the namespace
+    suite.registry.execute(
+        &RegistryExecuteMsg::UpdateOwnership(Action::TransferOwnership {
+            new_owner: suite.middleware.addr_str()?,
+            expiry: None,
+        }),
+        &[],
+    )?;
+    suite
+        .middleware
+        .registry_action(RegistryExecuteMsg::UpdateOwnership(Action::AcceptOwnership))?;
+    suite.market.retry_hook(failed[0].id)?;
+
+    let ask = suite.market.ask("second".to_string())?.unwrap();
+    let NamespaceResponse::Claimed(info) = namespace(&suite, "second")? else {
+        panic!("namespace was not claimed on retry");
+    };
+    assert_eq!(info.account_id, account_id(ask.id)?);
+    assert!(suite.market.failed_hooks(None, None)?.is_empty());
+
+    // internationalized accounts claim their punycode form
+    suite.minter.update_idn_config(Some(IdnConfig {
+        scripts: vec!["Latin".to_string()],
+        allow_emoji: true,
+    }))?;
+    // priced by graphemes, as 4 and 3 characters
+    for (account, price) in [("café", BASE_PRICE * 10), ("🎵🎸🎤", BASE_PRICE * 100)] {
+        mock.add_balance(&owner, coins(price, "ubtsg"))?;
+        suite.minter.execute(
+            &bs721_account_minter::msg::ExecuteMsg::MintAndList {
+                account: account.to_string(),
+                proof: None,
+                referrer: None,
+                voucher: None,
+                owner: None,
+                initial: None,
+                associate: None,
+            },
+            &coins(price, "ubtsg"),
+        )?;
+        let ask = suite.market.ask(account.to_string())?.unwrap();
+        let NamespaceResponse::Claimed(info) = namespace(&suite, &token_namespace(account)?)?
+        else {
+            panic!("namespace of {account} was not claimed");
+        };
+        assert_eq!(info.account_id, account_id(ask.id)?);
+    }
+    assert_eq!(token_namespace("café")?, "xn--caf-dma");
+    assert!(suite.market.failed_hooks(None, None)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_middleware_account_ids_skip_registry_sequence() -> anyhow::Result<()> {
+    let mock = MockBech32::new("bitsong");
+    let mut suite = BtsgAccountSuite::new(mock.clone());
+    suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+    suite.deploy_abstract(mock.clone())?;
+    mock.wait_seconds(200)?;
+
+    // accounts created through the registry take ids from its sequence
+    let owner = mock.sender.clone();
+    let abstr = Abstract::load_from(mock.clone())?;
+    let created = AccountI::create_default_account(
+        &abstr,
+        GovernanceDetails::Monarchy {
+            monarch: owner.to_string(),
+        },
+    )?;
+    let created_id = created.id()?;
+
+    // listing a token afterwards still creates its account, in the predictable range
+    suite.mint_and_list(mock.clone(), "listed", &owner)?;
+    let ask = suite.market.ask("listed".to_string())?.unwrap();
+    let NamespaceResponse::Claimed(info) = namespace(&suite, "listed")? else {
+        panic!("namespace was not claimed");
+    };
+    assert_eq!(info.account_id, account_id(ask.id)?);
+    assert_ne!(info.account_id, created_id);
+    assert!(info.account_id.seq() >= ACCOUNT_ID_OFFSET);
+    assert!(suite.market.failed_hooks(None, None)?.is_empty());
+
+    // and the registry sequence keeps going for accounts created after it
+    let next = AccountI::create_default_account(
+        &abstr,
+        GovernanceDetails::Monarchy {
+            monarch: owner.to_string(),
+        },
+    )?;
+    assert_eq!(next.id()?.seq(), created_id.seq() + 1);
+
+    Ok(())
+}
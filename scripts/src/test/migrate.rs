@@ -0,0 +1,365 @@
+use account_registry_middleware::{
+    ContractError as MiddlewareContractError, MigrateMsg as MiddlewareMigrateMsg, CONTRACT_NAME,
+};
+use bs721_account::msg::MigrateMsg as CollectionMigrateMsg;
+use bs721_account::{ContractError as CollectionContractError, ACCOUNT_CONTRACT};
+use bs721_account_marketplace::contract::ACCOUNT_MARKETPLACE;
+use bs721_account_marketplace::ContractError as MarketContractError;
+use bs721_account_minter::contract::ACCOUNT_MINTER;
+use bs721_account_minter::msg::MigrateMsg as MinterMigrateMsg;
+use bs721_account_minter::ContractError as MinterContractError;
+use btsg_account::market::{CooldownTier, MigrateMsg as MarketMigrateMsg};
+use btsg_account::minter::PricingSchedule;
+use cosmwasm_std::{coin, Decimal, Uint128};
+use cw_orch::{anyhow, mock::MockBech32, prelude::*};
+
+use crate::{BtsgAccountMarketQueryFns, BtsgAccountMinterQueryMsgFns, BtsgAccountSuite};
+
+const BID_AMOUNT: u128 = 1_000_000_000;
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Rewrites the contract version stored by a contract
+fn set_version(
+    mock: &MockBech32,
+    contract: &Addr,
+    name: &str,
+    version: &str,
+) -> anyhow::Result<()> {
+    let mut app = mock.app.borrow_mut();
+    let mut storage = app.contract_storage_mut(contract);
+    cw2::set_contract_version(storage.as_mut(), name, version)?;
+    Ok(())
+}
+
+/// Rewrites a contract's storage as an earlier `version` left it: params without `field`.
+///
+/// These tests don't deploy the wasm of earlier versions in `artifacts/`: the mock chain only
+/// runs the contracts compiled into this crate. The state an earlier version left is recreated
+/// by hand instead, so a field those versions stored differently must be rewritten here too.
+fn downgrade(
+    mock: &MockBech32,
+    contract: &Addr,
+    name: &str,
+    version: &str,
+    field: &str,
+) -> anyhow::Result<()> {
+    set_version(mock, contract, name, version)?;
+    let mut app = mock.app.borrow_mut();
+    let mut storage = app.contract_storage_mut(contract);
+    let mut params: serde_json::Value = serde_json::from_slice(&storage.get(b"sp").unwrap())?;
+    params.as_object_mut().unwrap().remove(field);
+    storage.set(b"sp", &serde_json::to_vec(&params)?);
+    Ok(())
+}
+
+/// Whether the params stored by a contract include `field`
+fn stored_param(mock: &MockBech32, contract: &Addr, field: &str) -> anyhow::Result<bool> {
+    let app = mock.app.borrow();
+    let params: serde_json::Value =
+        serde_json::from_slice(&app.contract_storage(contract).get(b"sp").unwrap())?;
+    Ok(params.get(field).is_some())
+}
+
+#[test]
+fn test_migrate_minter() -> anyhow::Result<()> {
+    let mock = MockBech32::new("bitsong");
+    let mut suite = BtsgAccountSuite::new(mock.clone());
+    suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+    let minter = suite.minter.address()?;
+    let code_id = suite.minter.code_id()?;
+
+    // already on the current version
+    let err = suite
+        .minter
+        .migrate(&MinterMigrateMsg::default(), code_id)
+        .unwrap_err();
+    assert_eq!(
+        err.root().to_string(),
+        MinterContractError::InvalidMigrationVersion {
+            from: CONTRACT_VERSION.to_string(),
+            to: CONTRACT_VERSION.to_string(),
+        }
+        .to_string()
+    );
+
+    // versions are compared as semver, not as strings
+    downgrade(&mock, &minter, ACCOUNT_MINTER, "0.10.0", "pricing")?;
+    let err = suite
+        .minter
+        .migrate(&MinterMigrateMsg::default(), code_id)
+        .unwrap_err();
+    assert_eq!(
+        err.root().to_string(),
+        MinterContractError::InvalidMigrationVersion {
+            from: "0.10.0".to_string(),
+            to: CONTRACT_VERSION.to_string(),
+        }
+        .to_string()
+    );
+
+    downgrade(&mock, &minter, ACCOUNT_MARKETPLACE, "0.2.0", "pricing")?;
+    let err = suite
+        .minter
+        .migrate(&MinterMigrateMsg::default(), code_id)
+        .unwrap_err();
+    assert_eq!(
+        err.root().to_string(),
+        MinterContractError::InvalidMigrationContract {
+            contract: ACCOUNT_MARKETPLACE.to_string(),
+        }
+        .to_string()
+    );
+
+    // params stored before the pricing schedule get the default schedule
+    downgrade(&mock, &minter, ACCOUNT_MINTER, "0.2.0", "pricing")?;
+    assert!(!stored_param(&mock, &minter, "pricing")?);
+    suite
+        .minter
+        .migrate(&MinterMigrateMsg::default(), code_id)?;
+    assert!(stored_param(&mock, &minter, "pricing")?);
+    assert_eq!(suite.minter.params()?.pricing, PricingSchedule::default());
+    assert_eq!(suite.minter.admin_delay()?, 0);
+
+    mock.wait_seconds(200)?;
+    let sender = mock.sender.clone();
+    suite.mint_and_list(mock.clone(), "abc", &sender)?;
+
+    // new params can be set in the same migration
+    downgrade(&mock, &minter, ACCOUNT_MINTER, "0.2.0", "pricing")?;
+    let pricing = PricingSchedule {
+        word_premium: Decimal::percent(500),
+        ..PricingSchedule::default()
+    };
+    let err = suite
+        .minter
+        .migrate(
+            &MinterMigrateMsg {
+                pricing: Some(PricingSchedule {
+                    numeric_discount: Some(Decimal::percent(150)),
+                    ..pricing.clone()
+                }),
+                admin_delay: None,
+            },
+            code_id,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root().to_string(),
+        MinterContractError::InvalidPricing {}.to_string()
+    );
+    suite.minter.migrate(
+        &MinterMigrateMsg {
+            pricing: Some(pricing.clone()),
+            admin_delay: Some(30),
+        },
+        code_id,
+    )?;
+    assert_eq!(suite.minter.params()?.pricing, pricing);
+    assert_eq!(suite.minter.admin_delay()?, 30);
+
+    Ok(())
+}
+
+#[test]
+fn test_migrate_market() -> anyhow::Result<()> {
+    let mock = MockBech32::new("bitsong");
+    let mut suite = BtsgAccountSuite::new(mock.clone());
+    suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+    let market = suite.market.address()?;
+    let code_id = suite.market.code_id()?;
+    mock.wait_seconds(200)?;
+
+    let sender = mock.sender.clone();
+    suite.mint_and_list(mock.clone(), "migrate", &sender)?;
+    suite.bid_w_funds(
+        mock.clone(),
+        "migrate",
+        mock.addr_make("bidder"),
+        BID_AMOUNT,
+    )?;
+
+    let err = suite
+        .market
+        .migrate(&MarketMigrateMsg::default(), code_id)
+        .unwrap_err();
+    assert_eq!(
+        err.root().to_string(),
+        MarketContractError::InvalidMigrationVersion {
+            from: CONTRACT_VERSION.to_string(),
+            to: CONTRACT_VERSION.to_string(),
+        }
+        .to_string()
+    );
+
+    // escrow and cooldown tiers were not stored by earlier versions
+    downgrade(
+        &mock,
+        &market,
+        ACCOUNT_MARKETPLACE,
+        "0.2.0",
+        "cooldown_tiers",
+    )?;
+    mock.app
+        .borrow_mut()
+        .contract_storage_mut(&market)
+        .remove(b"et");
+
+    let tier = CooldownTier {
+        min_price: Uint128::new(BID_AMOUNT),
+        duration: 120,
+        cancel_fee: coin(1_000_000_000, "ubtsg"),
+    };
+    let err = suite
+        .market
+        .migrate(
+            &MarketMigrateMsg {
+                cooldown_tiers: Some(vec![tier.clone(), tier.clone()]),
+                admin_delay: None,
+            },
+            code_id,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root().to_string(),
+        MarketContractError::InvalidCooldownTiers {}.to_string()
+    );
+    suite.market.migrate(
+        &MarketMigrateMsg {
+            cooldown_tiers: Some(vec![tier.clone()]),
+            admin_delay: Some(60),
+        },
+        code_id,
+    )?;
+
+    assert!(stored_param(&mock, &market, "cooldown_tiers")?);
+    assert_eq!(suite.market.params()?.cooldown_tiers, vec![tier]);
+    assert_eq!(suite.market.admin_delay()?, 60);
+    let summary = suite.market.escrow_summary()?;
+    assert_eq!(summary.tracked.amount, Uint128::new(BID_AMOUNT));
+    assert_eq!(summary.escrowed.amount, Uint128::new(BID_AMOUNT));
+    assert!(summary.solvent);
+    assert!(summary.consistent);
+
+    Ok(())
+}
+
+#[test]
+fn test_migrate_collection() -> anyhow::Result<()> {
+    let mock = MockBech32::new("bitsong");
+    let mut suite = BtsgAccountSuite::new(mock.clone());
+    suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+    let nft = suite.nft.address()?;
+    let code_id = suite.nft.code_id()?;
+
+    let err = suite
+        .nft
+        .migrate(&CollectionMigrateMsg {}, code_id)
+        .unwrap_err();
+    assert_eq!(
+        err.root().to_string(),
+        CollectionContractError::InvalidMigrationVersion {
+            from: CONTRACT_VERSION.to_string(),
+            to: CONTRACT_VERSION.to_string(),
+        }
+        .to_string()
+    );
+
+    // versions are compared as semver, not as strings
+    set_version(&mock, &nft, ACCOUNT_CONTRACT, "0.10.0")?;
+    let err = suite
+        .nft
+        .migrate(&CollectionMigrateMsg {}, code_id)
+        .unwrap_err();
+    assert_eq!(
+        err.root().to_string(),
+        CollectionContractError::InvalidMigrationVersion {
+            from: "0.10.0".to_string(),
+            to: CONTRACT_VERSION.to_string(),
+        }
+        .to_string()
+    );
+
+    set_version(&mock, &nft, ACCOUNT_MINTER, "0.2.0")?;
+    let err = suite
+        .nft
+        .migrate(&CollectionMigrateMsg {}, code_id)
+        .unwrap_err();
+    assert_eq!(
+        err.root().to_string(),
+        CollectionContractError::InvalidMigrationContract {
+            contract: ACCOUNT_MINTER.to_string(),
+        }
+        .to_string()
+    );
+
+    set_version(&mock, &nft, ACCOUNT_CONTRACT, "0.2.0")?;
+    suite.nft.migrate(&CollectionMigrateMsg {}, code_id)?;
+    assert_eq!(
+        cw2::query_contract_info(&mock.app.borrow().wrap(), &nft)?.version,
+        CONTRACT_VERSION
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_migrate_middleware() -> anyhow::Result<()> {
+    let mock = MockBech32::new("bitsong");
+    let mut suite = BtsgAccountSuite::new(mock.clone());
+    suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+    suite.deploy_abstract(mock.clone())?;
+    let middleware = suite.middleware.address()?;
+    let code_id = suite.middleware.code_id()?;
+
+    let err = suite
+        .middleware
+        .migrate(&MiddlewareMigrateMsg {}, code_id)
+        .unwrap_err();
+    assert_eq!(
+        err.root().to_string(),
+        MiddlewareContractError::InvalidMigrationVersion {
+            from: CONTRACT_VERSION.to_string(),
+            to: CONTRACT_VERSION.to_string(),
+        }
+        .to_string()
+    );
+
+    // versions are compared as semver, not as strings
+    set_version(&mock, &middleware, CONTRACT_NAME, "0.10.0")?;
+    let err = suite
+        .middleware
+        .migrate(&MiddlewareMigrateMsg {}, code_id)
+        .unwrap_err();
+    assert_eq!(
+        err.root().to_string(),
+        MiddlewareContractError::InvalidMigrationVersion {
+            from: "0.10.0".to_string(),
+            to: CONTRACT_VERSION.to_string(),
+        }
+        .to_string()
+    );
+
+    set_version(&mock, &middleware, ACCOUNT_MARKETPLACE, "0.2.0")?;
+    let err = suite
+        .middleware
+        .migrate(&MiddlewareMigrateMsg {}, code_id)
+        .unwrap_err();
+    assert_eq!(
+        err.root().to_string(),
+        MiddlewareContractError::InvalidMigrationContract {
+            contract: ACCOUNT_MARKETPLACE.to_string(),
+        }
+        .to_string()
+    );
+
+    set_version(&mock, &middleware, CONTRACT_NAME, "0.2.0")?;
+    suite
+        .middleware
+        .migrate(&MiddlewareMigrateMsg {}, code_id)?;
+    assert_eq!(
+        cw2::query_contract_info(&mock.app.borrow().wrap(), &middleware)?.version,
+        CONTRACT_VERSION
+    );
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
 use btsg_account::market::{ConfigResponse, ManageHooksAction};
 use cw_orch::{anyhow, mock::MockBech32, prelude::*};
 
+use crate::batch_mint;
+use crate::reservations::ReservationTree;
 use crate::BtsgAccountSuite;
 use crate::{
     Bs721AccountMarketExecuteMsgTypes, Bs721AccountsQueryMsgFns, BtsgAccountExecuteFns,
@@ -9,17 +11,27 @@ use crate::{
 };
 
 use cosmwasm_std::Uint128;
-use cosmwasm_std::{coins, to_json_binary, Decimal};
-use cw_orch::mock::cw_multi_test::{SudoMsg, WasmSudo};
+use cosmwasm_std::{coins, to_json_binary, Decimal, Order, StdResult};
+use cw_orch::mock::cw_multi_test::{ContractWrapper, Executor, SudoMsg, WasmSudo};
 
 use std::error::Error;
 
-use bs721_account_marketplace::state::MAX_FEE_BPS;
+use bs721_account_marketplace::state::{ask_updated, MAX_FEE_BPS, MAX_REMOVE_BID_LIMIT};
 use bs721_account_marketplace::ContractError as MarketContractError;
+use bs721_account_minter::state::MAX_BATCH_MINT;
 use bs721_account_minter::ContractError as MinterContractError;
-use btsg_account::market::{Ask, Bid, ExecuteMsg, PendingBid};
-use btsg_account::DEPLOYMENT_DAO;
-use cosmwasm_std::{coin, Attribute, Binary, Event};
+use btsg_account::market::{
+    hooks::ValidationHookQueryMsg, Ask, Bid, CooldownTier, ExecuteMsg, HookKind, MarketStatus,
+    PendingBid, PrivateSale, ValidationAction, ValidationResponse,
+};
+use btsg_account::merkle;
+use btsg_account::minter::{
+    voucher_commitment, BatchMintItem, BlockRule, DelegationPolicy, Delegator, DutchAuction,
+    IdnConfig, MintError, PricingSchedule, ReferralConfig, ReferralStats, Whitelist, WhitelistType,
+};
+use btsg_account::{Metadata, TextRecord, DEPLOYMENT_DAO};
+use cosmwasm_std::{coin, Attribute, Binary, Event, HexBinary};
+use sha2::{Digest, Sha256};
 
 const BID_AMOUNT: u128 = 1_000_000_000;
 #[test]
@@ -350,6 +362,7 @@ mod execute {
                 bidder: bidder.clone(),
                 amount: BID_AMOUNT.into(),
                 created_time: mock.block_info()?.time.clone(),
+                waive_cooldown: false,
             }],
         );
         assert_eq!(
@@ -359,6 +372,7 @@ mod execute {
                 bidder: bidder.clone(),
                 amount: BID_AMOUNT.into(),
                 created_time: mock.block_info()?.time.clone(),
+                waive_cooldown: false,
             }],
         );
 
@@ -369,6 +383,7 @@ mod execute {
                 bidder: bidder.clone(),
                 amount: BID_AMOUNT.into(),
                 created_time: mock.block_info()?.time.clone(),
+                waive_cooldown: false,
             }],
         );
 
@@ -464,6 +479,92 @@ mod execute {
 
         Ok(())
     }
+    #[test]
+    fn test_ask_update_rate_limit() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        let owner = mock.sender.clone();
+        let user1 = mock.addr_make("user1");
+        let token_id = "bandura";
+        let ask_interval = suite.market.params()?.ask_interval;
+
+        mock.wait_seconds(200)?;
+        suite.mint_and_list(mock.clone(), token_id, &owner)?;
+        let listed_at = mock.block_info()?.time;
+
+        // transfers within the ask interval, including back to a recent holder, still update the ask
+        suite.nft.transfer_nft(user1.clone(), token_id)?;
+        suite
+            .nft
+            .call_as(&user1)
+            .transfer_nft(owner.clone(), token_id)?;
+        assert_eq!(
+            suite.market.ask(token_id.to_string())?.unwrap().seller,
+            owner
+        );
+
+        // so does a sale back to a recent holder
+        let bid = coins(BID_AMOUNT, "ubtsg");
+        mock.add_balance(&user1, bid.clone())?;
+        suite.market.call_as(&user1).execute(
+            &ExecuteMsg::SetBid {
+                token_id: token_id.into(),
+                waive_cooldown: true,
+            },
+            &bid,
+        )?;
+        suite.market.accept_bid(user1.clone(), token_id.into())?;
+        assert_eq!(suite.owner_of(token_id.to_string())?, user1.to_string());
+        assert_eq!(
+            suite.market.ask(token_id.to_string())?.unwrap().seller,
+            user1
+        );
+
+        // listing the same token again is rate limited per seller
+        suite
+            .nft
+            .call_as(&user1)
+            .transfer_nft(owner.clone(), token_id)?;
+        suite.nft.burn(token_id)?;
+        assert_eq!(
+            suite
+                .mint_and_list(mock.clone(), token_id, &owner)
+                .unwrap_err()
+                .root()
+                .to_string(),
+            MarketContractError::AskUpdateTooSoon(listed_at.plus_seconds(ask_interval)).to_string()
+        );
+
+        mock.wait_seconds(ask_interval)?;
+        suite.mint_and_list(mock.clone(), token_id, &owner)?;
+        assert_eq!(
+            suite.market.ask(token_id.to_string())?.unwrap().seller,
+            owner
+        );
+
+        // listing times are pruned once the interval has passed
+        let market = suite.market.address()?;
+        let listing_times = || -> anyhow::Result<Vec<(String, u64)>> {
+            let app = mock.app.borrow();
+            let storage = app.contract_storage(&market);
+            Ok(ask_updated()
+                .range(storage.as_ref(), None, None, Order::Ascending)
+                .map(|item| item.map(|((_, token_id), time)| (token_id, time.seconds())))
+                .collect::<StdResult<_>>()?)
+        };
+        let relisted_at = mock.block_info()?.time.seconds();
+        assert_eq!(listing_times()?, vec![(token_id.to_string(), relisted_at)]);
+        mock.wait_seconds(ask_interval)?;
+        suite.mint_and_list(mock.clone(), "other", &owner)?;
+        assert_eq!(
+            listing_times()?,
+            vec![("other".to_string(), relisted_at + ask_interval)]
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_reverse_map() -> anyhow::Result<()> {
         let token_id = "bandura";
@@ -585,359 +686,2711 @@ mod execute {
     }
 
     #[test]
-    fn test_update_mkt_sudo() -> anyhow::Result<()> {
+    fn test_whitelist_phases() -> anyhow::Result<()> {
         let mock = MockBech32::new("bitsong");
         let mut suite = BtsgAccountSuite::new(mock.clone());
-        suite.default_setup(mock.clone(), None, None)?;
-        let token_id = "bandura";
-        let admin2 = mock.addr_make("admin2");
-        // delegate
-        mock.add_balance(&admin2, vec![coin(10000000000u128, "ubtsg")])?;
-        suite.delegate_to_val(mock.clone(), admin2.clone(), 10000000000u128)?;
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
 
-        mock.wait_seconds(200)?;
-        suite.mint_and_list(mock.clone(), token_id, &admin2)?;
+        let friend = mock.addr_make("friend");
+        let stranger = mock.addr_make("stranger");
+        let market = suite.market.address()?;
+        let full_price = super::super::BASE_PRICE;
+        for user in [&friend, &stranger] {
+            mock.add_balance(user, vec![coin(10000000000u128, "ubtsg")])?;
+            suite.delegate_to_val(mock.clone(), user.clone(), 10000000000u128)?;
+            mock.add_balance(user, coins(full_price * 2, "ubtsg"))?;
+            suite.nft.call_as(user).approve_all(market.clone(), None)?;
+        }
 
-        // run sudo msg
-        mock.app.borrow_mut().sudo(SudoMsg::Wasm(WasmSudo {
-            contract_addr: suite.market.address()?,
-            message: to_json_binary(&btsg_account::market::SudoMsg::UpdateParams {
-                trading_fee_bps: Some(1000u64),
-                min_price: Some(Uint128::from(1000u128)),
-                ask_interval: Some(1000),
-                cooldown_duration: Some(69),
-                cooldown_cancel_fee: Some(coin(69u128, "jerets")),
-            })?,
-        }))?;
+        // merkle tree of the sender and friend
+        let leaves = [&mock.sender, &friend].map(|a| merkle::leaf_hash(a.as_bytes()));
+        let root = HexBinary::from(merkle::hash_pair(leaves[0], leaves[1])).to_hex();
+        let friend_proof = vec![HexBinary::from(leaves[0]).to_hex()];
+
+        let now = mock.block_info()?.time;
+        let whitelist = Whitelist {
+            list: WhitelistType::Merkle { root },
+            start_time: now,
+            end_time: now.plus_seconds(100),
+            per_address_limit: 1,
+            discount: Some(Decimal::percent(50)),
+        };
+        suite
+            .minter
+            .call_as(&stranger)
+            .add_whitelist(whitelist.clone())
+            .unwrap_err();
+        suite.minter.add_whitelist(whitelist)?;
+        assert_eq!(suite.minter.whitelists()?.len(), 1);
+
+        let mint = |user: &Addr, account: &str, proof: Option<Vec<String>>, price: u128| {
+            suite.minter.call_as(user).execute(
+                &bs721_account_minter::msg::ExecuteMsg::MintAndList {
+                    account: account.to_string(),
+                    proof,
+                    referrer: None,
+                    voucher: None,
+                    owner: None,
+                    initial: None,
+                    associate: None,
+                },
+                &coins(price, "ubtsg"),
+            )
+        };
 
+        // the query reports whitelist eligibility before public minting
+        let res = suite
+            .minter
+            .can_mint("friendly", &friend, Some(friend_proof.clone()))?;
+        assert!(res.can_mint);
+        assert!(!res.started);
         assert_eq!(
-            mock.app
-                .borrow_mut()
-                .sudo(SudoMsg::Wasm(WasmSudo {
-                    contract_addr: suite.market.address()?,
-                    message: to_json_binary(&btsg_account::market::SudoMsg::UpdateParams {
-                        trading_fee_bps: Some(MAX_FEE_BPS * 2u64),
-                        min_price: Some(Uint128::from(1000u128)),
-                        ask_interval: Some(1000),
-                        cooldown_duration: Some(69),
-                        cooldown_cancel_fee: Some(coin(69u128, "jerets")),
-                    })?,
-                }))
-                .unwrap_err()
-                .root_cause()
-                .to_string(),
-            MarketContractError::InvalidTradingFeeBps(MAX_FEE_BPS * 2u64).to_string()
+            suite.minter.can_mint("friendly", &friend, None)?.errors,
+            vec![MintError::NotWhitelisted]
+        );
+        assert_eq!(
+            suite.minter.can_mint("strange", &stranger, None)?.errors,
+            vec![MintError::NotWhitelisted]
         );
 
-        // confirm updated params
-        let res = suite.market.params()?;
-        assert_eq!(res.trading_fee_percent, Decimal::percent(10));
-        assert_eq!(res.min_price, Uint128::from(1000u128));
-        assert_eq!(res.ask_interval, 1000);
-        assert_eq!(res.cooldown_duration, 69);
-        assert_eq!(res.cooldown_fee, coin(69u128, "jerets"));
+        // whitelisted at half price, once
+        mint(
+            &friend,
+            "friendly",
+            Some(friend_proof.clone()),
+            full_price / 2,
+        )?;
+        assert_eq!(suite.owner_of("friendly".to_string())?, friend.to_string());
+        assert_eq!(suite.minter.whitelist_mints(friend.to_string(), 1u32)?, 1);
+        assert_eq!(
+            suite
+                .minter
+                .can_mint("friendlier", &friend, Some(friend_proof.clone()))?
+                .errors,
+            vec![MintError::WhitelistLimitReached]
+        );
+        let err = mint(
+            &friend,
+            "friendlier",
+            Some(friend_proof.clone()),
+            full_price,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::WhitelistLimitReached {}.to_string()
+        );
+
+        // not in the tree, with or without someone else's proof
+        for proof in [None, Some(friend_proof)] {
+            let err = mint(&stranger, "strange", proof, full_price).unwrap_err();
+            assert_eq!(
+                err.root().to_string(),
+                MinterContractError::NotWhitelisted {}.to_string()
+            );
+        }
+
+        // phase over, public mint not started yet
+        mock.wait_seconds(100)?;
+        let err = mint(&stranger, "strange", None, full_price).unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::MintingNotStarted {}.to_string()
+        );
+        assert_eq!(
+            suite.minter.can_mint("strange", &stranger, None)?.errors,
+            vec![MintError::NotStarted]
+        );
+
+        suite.minter.remove_whitelist(1u32)?;
+        assert_eq!(suite.minter.whitelists()?, vec![]);
+
+        // public mint at full price
+        mock.wait_seconds(100)?;
+        mint(&stranger, "strange", None, full_price)?;
+        assert_eq!(suite.owner_of("strange".to_string())?, stranger.to_string());
 
-        let new = mock.addr_make("new-jawn");
-        let newnew = mock.addr_make("newer-jawn");
-        mock.app.borrow_mut().sudo(SudoMsg::Wasm(WasmSudo {
-            contract_addr: suite.market.address()?,
-            message: to_json_binary(&btsg_account::market::SudoMsg::UpdateAccountFactory {
-                factory: new.to_string(),
-            })?,
-        }))?;
-        assert_eq!(suite.market.config()?.minter, new);
-        mock.app.borrow_mut().sudo(SudoMsg::Wasm(WasmSudo {
-            contract_addr: suite.market.address()?,
-            message: to_json_binary(&btsg_account::market::SudoMsg::UpdateAccountCollection {
-                collection: newnew.to_string(),
-            })?,
-        }))?;
-        assert_eq!(suite.market.config()?.collection, newnew);
         Ok(())
     }
 
     #[test]
-    fn test_cooldown_period_operator_approve() -> anyhow::Result<()> {
+    fn test_reservations() -> anyhow::Result<()> {
         let mock = MockBech32::new("bitsong");
         let mut suite = BtsgAccountSuite::new(mock.clone());
         suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+
+        // artists hold no funds and have not delegated
+        let artist = mock.addr_make("artist");
+        let other_artist = mock.addr_make("other-artist");
+        let csv = format!(
+            "account,address\nbitsong,{}\nartist,{artist}\nother-artist,{other_artist}\n",
+            mock.sender
+        );
+        let tree = ReservationTree::from_csv(csv.as_bytes())?;
+        suite
+            .minter
+            .call_as(&artist)
+            .update_reservations(tree.accounts(), vec![], Some(tree.root.clone()))
+            .unwrap_err();
+        suite
+            .minter
+            .update_reservations(tree.accounts(), vec![], Some(tree.root.clone()))?;
+        assert_eq!(suite.minter.reservation_root()?, Some(tree.root.clone()));
+        assert_eq!(
+            suite.minter.reserved_accounts(None, None)?,
+            vec!["artist", "bitsong", "other-artist"]
+        );
+
+        // nobody can mint a reserved account publicly
         mock.wait_seconds(200)?;
-        let owner = mock.sender.clone();
-        mock.add_balance(&owner, vec![coin(10000000000u128, "ubtsg")])?;
-        let bidder = mock.addr_make("bidder");
-        let account = "account";
-        suite.mint_and_list(mock.clone(), account, &owner)?;
-        suite.bid_w_funds(mock.clone(), account, bidder.clone(), BID_AMOUNT)?;
-        suite.market.accept_bid(bidder.clone(), account.into())?;
-        suite.nft.revoke_all(suite.market.address()?)?;
-        mock.wait_seconds(60)?;
-        let res = suite.market.finalize_bid(account.to_string())?;
-        res.assert_event(&Event::new("wasm").add_attributes(vec![
-            Attribute {
-                key: "_contract_address".to_string(),
-                value: suite.nft.addr_str()?,
-            },
-            Attribute::new("action", "approve_all".to_string()),
-            Attribute::new("operator", suite.market.addr_str()?),
-        ]));
+        let err = suite
+            .mint_and_list(mock.clone(), "artist", &mock.sender)
+            .unwrap_err();
         assert_eq!(
-            suite.market.ask(account.to_string())?.unwrap().seller,
-            &bidder
+            err.root().to_string(),
+            MinterContractError::AccountReserved {}.to_string()
+        );
+
+        // a proof only works for its own claimant
+        let claim = tree.claim("artist").unwrap();
+        let err = suite
+            .minter
+            .call_as(&other_artist)
+            .claim_reserved("artist", claim.proof.clone())
+            .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::InvalidReservationProof {}.to_string()
+        );
+
+        let market = suite.market.address()?;
+        suite.nft.call_as(&artist).approve_all(market, None)?;
+        suite
+            .minter
+            .call_as(&artist)
+            .claim_reserved("artist", claim.proof.clone())?;
+        assert_eq!(suite.owner_of("artist".to_string())?, artist.to_string());
+        assert_eq!(
+            suite.minter.reserved_accounts(None, None)?,
+            vec!["bitsong", "other-artist"]
+        );
+
+        // claimed only once
+        let err = suite
+            .minter
+            .call_as(&artist)
+            .claim_reserved("artist", claim.proof.clone())
+            .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::NotReserved {}.to_string()
         );
+
+        // released accounts can be minted by anyone
+        suite
+            .minter
+            .update_reservations(vec![], vec!["other-artist".to_string()], None)?;
+        suite.mint_and_list(mock.clone(), "other-artist", &mock.sender)?;
+
         Ok(())
     }
 
     #[test]
-    fn test_cooldown_period() -> anyhow::Result<()> {
+    fn test_referrals_and_vouchers() -> anyhow::Result<()> {
         let mock = MockBech32::new("bitsong");
         let mut suite = BtsgAccountSuite::new(mock.clone());
         suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
         mock.wait_seconds(200)?;
 
-        let owner = mock.sender.clone();
-        mock.add_balance(&owner, vec![coin(10000000000u128, "ubtsg")])?;
-        let bidder = mock.addr_make("bidder");
-        let account = "account";
-        suite.mint_and_list(mock.clone(), account, &owner)?;
-        suite.bid_w_funds(mock.clone(), account, bidder.clone(), BID_AMOUNT)?;
-        let owner_balance_a = mock.query_balance(&owner, "ubtsg")?;
-        let bidder_balance_a = mock.query_balance(&bidder, "ubtsg")?;
-        suite.market.accept_bid(bidder.clone(), account.into())?;
-        // no funds are transfered before cooldown is over
-        let owner_balance_b = mock.query_balance(&owner, "ubtsg")?;
-        let bidder_balance_b = mock.query_balance(&bidder, "ubtsg")?;
-        mock.wait_seconds(10)?;
-        assert_eq!(owner_balance_a, owner_balance_b);
-        assert_eq!(bidder_balance_a, bidder_balance_b);
-        assert_eq!(
-            suite.market.cooldown(account.to_string())?,
-            Some(PendingBid {
-                ask: Ask {
-                    token_id: account.to_string(),
-                    id: 1,
-                    seller: owner.clone(),
+        let referrer = mock.sender.clone();
+        let user = mock.addr_make("user");
+        let full_price = super::super::BASE_PRICE;
+        mock.add_balance(&user, vec![coin(10000000000u128, "ubtsg")])?;
+        suite.delegate_to_val(mock.clone(), user.clone(), 10000000000u128)?;
+        mock.add_balance(&user, coins(full_price * 3, "ubtsg"))?;
+        suite
+            .nft
+            .call_as(&user)
+            .approve_all(suite.market.address()?, None)?;
+        suite.mint_and_list(mock.clone(), "referrer", &referrer)?;
+
+        let mint = |account: &str, referrer: Option<&str>, voucher: Option<&str>, price: u128| {
+            suite.minter.call_as(&user).execute(
+                &bs721_account_minter::msg::ExecuteMsg::MintAndList {
+                    account: account.to_string(),
+                    proof: None,
+                    referrer: referrer.map(str::to_string),
+                    voucher: voucher.map(str::to_string),
+                    owner: None,
+                    initial: None,
+                    associate: None,
                 },
-                new_owner: bidder.clone(),
-                amount: BID_AMOUNT.into(),
-                unlock_time: mock.block_info()?.time.plus_seconds(50)
-            })
+                &coins(price, "ubtsg"),
+            )
+        };
+
+        let err = mint("referred", Some("referrer"), None, full_price).unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::ReferralsDisabled {}.to_string()
         );
+        suite.minter.update_referral_config(ReferralConfig {
+            fee_share: Decimal::percent(20),
+            discount: Decimal::percent(10),
+        })?;
+
+        // referred by account name, the owner of the account gets 20% of the discounted fee
+        let before = mock.query_balance(&referrer, "ubtsg")?;
+        mint("referred", Some("referrer"), None, full_price * 9 / 10)?;
+        let reward = full_price * 9 / 10 / 5;
         assert_eq!(
-            suite.nft.burn(account).unwrap_err().root().to_string(),
-            bs721_account::ContractError::AccountCannotBeTransfered {
-                reason: "Account is in cooldown".to_string()
-            }
-            .to_string()
+            mock.query_balance(&referrer, "ubtsg")?,
+            before + Uint128::from(reward)
         );
         assert_eq!(
-            suite
-                .nft
-                .transfer_nft(bidder.clone(), account)
-                .unwrap_err()
-                .root()
-                .to_string(),
-            bs721_account::ContractError::AccountCannotBeTransfered {
-                reason: "Account is in cooldown".to_string()
+            suite.minter.referral(referrer.to_string())?,
+            ReferralStats {
+                mints: 1,
+                rewards: reward.into()
             }
-            .to_string()
         );
+
+        // can't refer yourself
+        let err = mint("selfref", Some(user.as_str()), None, full_price * 9 / 10).unwrap_err();
         assert_eq!(
-            suite
-                .nft
-                .send_nft(suite.minter.address()?, Binary::default(), account)
-                .unwrap_err()
-                .root()
-                .to_string(),
+            err.root().to_string(),
+            MinterContractError::InvalidReferrer {}.to_string()
+        );
+
+        // single use voucher, the largest discount applies
+        let code_hash = HexBinary::from(Sha256::digest(b"LAUNCH50").as_slice()).to_hex();
+        let err = suite
+            .minter
+            .add_voucher(code_hash.clone(), Decimal::percent(50), 0u32, None)
+            .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::InvalidVoucherUses {}.to_string()
+        );
+        suite.minter.add_voucher(
+            code_hash.clone(),
+            Decimal::percent(50),
+            1u32,
+            Some(user.to_string()),
+        )?;
+        let err = mint("vouched", None, Some("LAUNCH5O"), full_price / 2).unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::InvalidVoucher {}.to_string()
+        );
+
+        // a code copied from a pending mint can't be used by anyone else
+        let frontrunner = mock.addr_make("frontrunner");
+        mock.add_balance(&frontrunner, coins(full_price / 2, "ubtsg"))?;
+        let err = suite
+            .minter
+            .call_as(&frontrunner)
+            .execute(
+                &bs721_account_minter::msg::ExecuteMsg::MintAndList {
+                    account: "stolen".to_string(),
+                    proof: None,
+                    referrer: None,
+                    voucher: Some("LAUNCH50".to_string()),
+                    owner: None,
+                    initial: None,
+                    associate: None,
+                },
+                &coins(full_price / 2, "ubtsg"),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::VoucherNotRedeemer {}.to_string()
+        );
+        assert_eq!(suite.minter.voucher(code_hash.clone())?.unwrap().uses, 0);
+        mint(
+            "vouched",
+            Some(referrer.as_str()),
+            Some("LAUNCH50"),
+            full_price / 2,
+        )?;
+        assert_eq!(suite.minter.voucher(code_hash)?.unwrap().uses, 1);
+        assert_eq!(
+            suite.minter.referral(referrer.to_string())?,
+            ReferralStats {
+                mints: 2,
+                rewards: (reward + full_price / 2 / 5).into()
+            }
+        );
+        let err = mint("vouched2", None, Some("LAUNCH50"), full_price / 2).unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::VoucherExhausted {}.to_string()
+        );
+
+        // a voucher without redeemer needs a commitment to the code and sender, in an earlier block
+        let code_hash = HexBinary::from(Sha256::digest(b"OPEN25").as_slice()).to_hex();
+        suite
+            .minter
+            .add_voucher(code_hash.clone(), Decimal::percent(25), 2u32, None)?;
+        let err = mint("opened", None, Some("OPEN25"), full_price * 3 / 4).unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::VoucherNotCommitted {}.to_string()
+        );
+        suite
+            .minter
+            .call_as(&user)
+            .commit_voucher(voucher_commitment("OPEN25", user.as_str()))?;
+        let err = mint("opened", None, Some("OPEN25"), full_price * 3 / 4).unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::VoucherNotCommitted {}.to_string()
+        );
+
+        // someone else's commitment doesn't help a copier
+        mock.add_balance(&frontrunner, coins(full_price, "ubtsg"))?;
+        let err = suite
+            .minter
+            .call_as(&frontrunner)
+            .execute(
+                &bs721_account_minter::msg::ExecuteMsg::MintAndList {
+                    account: "stolen".to_string(),
+                    proof: None,
+                    referrer: None,
+                    voucher: Some("OPEN25".to_string()),
+                    owner: None,
+                    initial: None,
+                    associate: None,
+                },
+                &coins(full_price * 3 / 4, "ubtsg"),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::VoucherNotCommitted {}.to_string()
+        );
+
+        mock.wait_blocks(1)?;
+        mint("opened", None, Some("OPEN25"), full_price * 3 / 4)?;
+        assert_eq!(suite.minter.voucher(code_hash)?.unwrap().uses, 1);
+
+        // the commitment is used up with the mint
+        let err = mint("opened2", None, Some("OPEN25"), full_price * 3 / 4).unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::VoucherNotCommitted {}.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_idn_accounts() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        mock.wait_seconds(200)?;
+
+        let full_price = super::super::BASE_PRICE;
+        mock.add_balance(&mock.sender, coins(full_price * 200, "ubtsg"))?;
+        suite.mint_and_list(mock.clone(), "apple", &mock.sender)?;
+
+        let mint = |account: &str, price: u128| {
+            suite.minter.execute(
+                &bs721_account_minter::msg::ExecuteMsg::MintAndList {
+                    account: account.to_string(),
+                    proof: None,
+                    referrer: None,
+                    voucher: None,
+                    owner: None,
+                    initial: None,
+                    associate: None,
+                },
+                &coins(price, "ubtsg"),
+            )
+        };
+
+        // ASCII only until enabled
+        let err = mint("café", full_price * 10).unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::InvalidAccount {}.to_string()
+        );
+
+        let config = IdnConfig {
+            scripts: vec!["Latin".to_string(), "Cyrillic".to_string()],
+            allow_emoji: true,
+        };
+        let err = suite
+            .minter
+            .update_idn_config(Some(IdnConfig {
+                scripts: vec!["Klingon".to_string()],
+                ..config.clone()
+            }))
+            .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::InvalidScript {
+                script: "Klingon".to_string()
+            }
+            .to_string()
+        );
+        suite.minter.update_idn_config(Some(config.clone()))?;
+        assert_eq!(suite.minter.idn_config()?, Some(config));
+
+        // cyrillic "а" looks like latin "a"
+        let lookalike = "\u{430}pple";
+        assert_eq!(
+            suite.minter.confusable(lookalike)?,
+            Some("apple".to_string())
+        );
+        let err = mint(lookalike, full_price).unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::ConfusableAccount {
+                existing: "apple".to_string()
+            }
+            .to_string()
+        );
+
+        // minted normalized and priced by graphemes, 4 here
+        mint("CAFE\u{301}", full_price * 10)?;
+        assert_eq!(suite.owner_of("café".to_string())?, mock.sender.to_string());
+        mint("🎵🎸🎤", full_price * 100)?;
+        assert_eq!(
+            suite.owner_of("🎵🎸🎤".to_string())?,
+            mock.sender.to_string()
+        );
+
+        // look-alikes of accounts minted in IDN mode are recorded too
+        assert_eq!(
+            suite.minter.confusable("c\u{430}fé")?,
+            Some("café".to_string())
+        );
+
+        suite.minter.update_idn_config(None)?;
+        let err = mint("naïve", full_price).unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::InvalidAccount {}.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pricing_schedule() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        let base_price = super::super::BASE_PRICE;
+        let base_delegation = super::super::BASE_DELEGATION;
+        let minter = suite.minter.address()?;
+
+        // the default schedule keeps the length tiers
+        let price = suite.minter.mint_price("abc")?;
+        assert_eq!(price.price, coin(base_price * 100, "ubtsg"));
+        assert_eq!(price.delegation, Uint128::from(base_delegation * 5));
+
+        let pricing = PricingSchedule {
+            word_premium: Decimal::percent(500),
+            numeric_discount: Some(Decimal::percent(50)),
+            dutch_auction: Some(DutchAuction {
+                start_multiplier: Decimal::percent(300),
+                duration: 100,
+            }),
+            ..PricingSchedule::default()
+        };
+        let err = mock
+            .app
+            .borrow_mut()
+            .sudo(SudoMsg::Wasm(WasmSudo {
+                contract_addr: minter.clone(),
+                message: to_json_binary(&bs721_account_minter::msg::SudoMsg::UpdatePricing {
+                    pricing: PricingSchedule {
+                        numeric_discount: Some(Decimal::percent(150)),
+                        ..pricing.clone()
+                    },
+                })?,
+            }))
+            .unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            MinterContractError::InvalidPricing {}.to_string()
+        );
+        for message in [
+            to_json_binary(&bs721_account_minter::msg::SudoMsg::UpdatePricing {
+                pricing: pricing.clone(),
+            })?,
+            to_json_binary(&bs721_account_minter::msg::SudoMsg::UpdatePremiumWords {
+                add: vec!["music".to_string()],
+                remove: vec![],
+            })?,
+            // updating the other params keeps the schedule
+            to_json_binary(&bs721_account_minter::msg::SudoMsg::UpdateParams {
+                min_account_length: 3,
+                max_account_length: 128,
+                base_price: base_price.into(),
+                base_delegation: base_delegation.into(),
+            })?,
+        ] {
+            mock.app.borrow_mut().sudo(SudoMsg::Wasm(WasmSudo {
+                contract_addr: minter.clone(),
+                message,
+            }))?;
+        }
+        assert_eq!(suite.minter.params()?.pricing, pricing);
+
+        // no launch pricing before public minting
+        assert_eq!(
+            suite.minter.mint_price("music")?.price,
+            coin(base_price * 5, "ubtsg")
+        );
+        assert_eq!(
+            suite.minter.mint_price("12345")?.price,
+            coin(base_price / 2, "ubtsg")
+        );
+
+        // 3x at launch, 2x halfway through the auction
+        mock.wait_seconds(200)?;
+        assert_eq!(
+            suite.minter.mint_price("abcde")?.price,
+            coin(base_price * 3, "ubtsg")
+        );
+        mock.wait_seconds(50)?;
+        assert_eq!(
+            suite.minter.mint_price("abcde")?.price,
+            coin(base_price * 2, "ubtsg")
+        );
+
+        suite.nft.approve_all(suite.market.address()?, None)?;
+        mock.add_balance(&mock.sender, coins(base_price * 10, "ubtsg"))?;
+        let mint = |price: u128| {
+            suite.minter.execute(
+                &bs721_account_minter::msg::ExecuteMsg::MintAndList {
+                    account: "music".to_string(),
+                    proof: None,
+                    referrer: None,
+                    voucher: None,
+                    owner: None,
+                    initial: None,
+                    associate: None,
+                },
+                &coins(price, "ubtsg"),
+            )
+        };
+        let err = mint(base_price).unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::IncorrectPayment {
+                got: base_price,
+                expected: base_price * 10
+            }
+            .to_string()
+        );
+        mint(base_price * 10)?;
+        assert_eq!(
+            suite.owner_of("music".to_string())?,
+            mock.sender.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_delegation_policy() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        let sender = mock.sender.to_string();
+
+        let err = suite.minter.register_delegation().unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::HoldingPeriodDisabled {}.to_string()
+        );
+        let err = suite
+            .minter
+            .update_delegation_policy(Some(DelegationPolicy {
+                validators: Some(vec![]),
+                ..DelegationPolicy::default()
+            }))
+            .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::InvalidDelegationPolicy {}.to_string()
+        );
+
+        // only delegations to allowed validators count
+        suite
+            .minter
+            .update_delegation_policy(Some(DelegationPolicy {
+                validators: Some(vec!["val-2".to_string()]),
+                ..DelegationPolicy::default()
+            }))?;
+        mock.wait_seconds(200)?;
+        assert_eq!(
+            suite.minter.can_mint("abcde", &sender, None)?.errors,
+            vec![MintError::InsufficientDelegation]
+        );
+        let err = suite
+            .mint_and_list(mock.clone(), "abcde", &mock.sender)
+            .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::IncorrectDelegation {
+                got: 0,
+                expected: super::super::BASE_DELEGATION
+            }
+            .to_string()
+        );
+
+        // val-1 is in the active set
+        let policy = DelegationPolicy {
+            holding_period: Some(300),
+            validators: Some(vec!["val-1".to_string()]),
+            active_set_only: true,
+            delegator: Delegator::Sender,
+        };
+        suite
+            .minter
+            .update_delegation_policy(Some(policy.clone()))?;
+        assert_eq!(suite.minter.delegation_policy()?, Some(policy));
+
+        // the delegation must be registered and held for the holding period
+        let err = suite
+            .mint_and_list(mock.clone(), "abcde", &mock.sender)
+            .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::DelegationNotHeld {}.to_string()
+        );
+        suite.minter.register_delegation()?;
+        let snapshot = suite.minter.delegation_snapshot(&sender)?.unwrap();
+        assert_eq!(snapshot.amount, Uint128::from(10_500_000_000u128));
+        mock.wait_seconds(100)?;
+        assert_eq!(
+            suite.minter.can_mint("abcde", &sender, None)?.errors,
+            vec![MintError::DelegationNotHeld]
+        );
+        mock.wait_seconds(200)?;
+        assert!(suite.minter.can_mint("abcde", &sender, None)?.can_mint);
+        suite.mint_and_list(mock.clone(), "abcde", &mock.sender)?;
+
+        // a minted account is challenged against the policy it was minted with
+        let challenger = mock.addr_make("challenger");
+        let err = suite.minter.challenge("abcde").unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::ChallengeFailed {}.to_string()
+        );
+        suite
+            .minter
+            .update_delegation_policy(Some(DelegationPolicy {
+                validators: Some(vec!["val-2".to_string()]),
+                ..DelegationPolicy::default()
+            }))?;
+        let err = suite
+            .minter
+            .call_as(&challenger)
+            .challenge("abcde")
+            .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::ChallengeFailed {}.to_string()
+        );
+
+        // and can be challenged once that delegation drops
+        suite.undelegate_from_val(mock.clone(), mock.sender.clone(), 10_500_000_000)?;
+        suite.minter.call_as(&challenger).challenge("abcde")?;
+        let flagged = suite.minter.flagged("abcde")?.unwrap();
+        assert_eq!(flagged.delegator, mock.sender);
+        assert_eq!(
+            flagged.required,
+            Uint128::from(super::super::BASE_DELEGATION)
+        );
+        assert_eq!(flagged.delegation, Uint128::zero());
+        assert_eq!(suite.minter.delegation_snapshot(&sender)?, None);
+        assert!(suite.nft.locked("abcde".to_string())?);
+        let err = suite
+            .minter
+            .call_as(&challenger)
+            .challenge("abcde")
+            .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::AccountFlagged {}.to_string()
+        );
+
+        // a flagged account can neither be transferred nor sold
+        let buyer = mock.addr_make("buyer");
+        assert_eq!(
+            suite
+                .nft
+                .transfer_nft(buyer.clone(), "abcde")
+                .unwrap_err()
+                .root()
+                .to_string(),
             bs721_account::ContractError::AccountCannotBeTransfered {
-                reason: "Account is in cooldown".to_string()
+                reason: "Account is locked".to_string()
+            }
+            .to_string()
+        );
+        suite.bid_w_funds(
+            mock.clone(),
+            "abcde",
+            buyer.clone(),
+            super::super::BASE_PRICE,
+        )?;
+        assert_eq!(
+            suite
+                .market
+                .accept_bid(buyer.clone(), "abcde".to_string())
+                .unwrap_err()
+                .root()
+                .to_string(),
+            MarketContractError::AccountLocked {}.to_string()
+        );
+
+        // the flag stays until the delegation is restored
+        let err = suite.minter.clear_flag("abcde").unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::IncorrectDelegation {
+                got: 0,
+                expected: super::super::BASE_DELEGATION
+            }
+            .to_string()
+        );
+        suite.delegate_to_val(mock.clone(), mock.sender.clone(), 10_500_000_000)?;
+        suite.minter.call_as(&challenger).clear_flag("abcde")?;
+        assert_eq!(suite.minter.flagged("abcde")?, None);
+        assert!(!suite.nft.locked("abcde".to_string())?);
+
+        // once the account changed hands, the minter's delegation no longer matters
+        suite.nft.transfer_nft(buyer.clone(), "abcde")?;
+        suite.undelegate_from_val(mock.clone(), mock.sender.clone(), 10_500_000_000)?;
+        let err = suite
+            .minter
+            .call_as(&challenger)
+            .challenge("abcde")
+            .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::ChallengeFailed {}.to_string()
+        );
+
+        // without a policy, all delegations count as is
+        suite.minter.update_delegation_policy(None)?;
+        suite.delegate_to_val(mock.clone(), mock.sender.clone(), 10_500_000_000)?;
+        suite.mint_and_list(mock.clone(), "fghij", &mock.sender)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_batch_mint() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        let alice = mock.addr_make("alice");
+        let bob = mock.addr_make("bob");
+
+        let item = |account: &str, owner: &Addr| BatchMintItem {
+            account: account.to_string(),
+            owner: owner.to_string(),
+            metadata: Metadata::default(),
+        };
+        let err = suite
+            .minter
+            .call_as(&alice)
+            .batch_mint(vec![item("alice", &alice)])
+            .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::OwnershipError(cw_ownable::OwnershipError::NotOwner).to_string()
+        );
+        for items in [vec![], vec![item("alice", &alice); MAX_BATCH_MINT + 1]] {
+            let err = suite.minter.batch_mint(items).unwrap_err();
+            assert_eq!(
+                err.root().to_string(),
+                MinterContractError::InvalidBatchSize {
+                    max: MAX_BATCH_MINT
+                }
+                .to_string()
+            );
+        }
+
+        // an invalid account fails its whole chunk, and the import resumes from the checkpoint
+        let metadata = r#"{"account_ownership":false,"image_nft":null,"records":[{"account":"twitter","value":"alice","verified":null}]}"#
+            .replace('"', "\"\"");
+        let csv = format!(
+            "account,owner,metadata\n\
+             alice,{alice},\"{metadata}\"\n\
+             bob,{bob},\n\
+             abc,{bob},\n\
+             bad_name,{bob},\n\
+             music,{alice},\n"
+        );
+        let mut items = batch_mint::read_items(csv.as_bytes())?;
+        let checkpoint =
+            std::env::temp_dir().join(format!("batch_mint_{}.checkpoint", std::process::id()));
+        std::fs::remove_file(&checkpoint).ok();
+        batch_mint::submit(&suite.minter, &items, 2, &checkpoint).unwrap_err();
+        assert_eq!(batch_mint::load_checkpoint(&checkpoint)?, 2);
+        assert_eq!(suite.owner_of("bob".to_string())?, bob.to_string());
+        assert!(suite.owner_of("abc".to_string()).is_err());
+
+        items[3].account = "good-name".to_string();
+        assert_eq!(
+            batch_mint::submit(&suite.minter, &items, 2, &checkpoint)?,
+            3
+        );
+        assert_eq!(batch_mint::load_checkpoint(&checkpoint)?, 5);
+        std::fs::remove_file(&checkpoint)?;
+
+        // minted with their metadata, and listed on the marketplace
+        for (account, owner) in [
+            ("alice", &alice),
+            ("bob", &bob),
+            ("abc", &bob),
+            ("good-name", &bob),
+            ("music", &alice),
+        ] {
+            assert_eq!(suite.owner_of(account.to_string())?, owner.to_string());
+            assert_eq!(
+                suite.market.ask(account.to_string())?.unwrap().seller,
+                *owner
+            );
+        }
+        let records = suite.nft.nft_info("alice".to_string())?.extension.records;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].value, "alice");
+
+        // blocked names and patterns can't be batch minted either
+        suite.minter.update_blocklist(
+            vec![
+                BlockRule::Name("kraken".to_string()),
+                BlockRule::Pattern("*binance*".to_string()),
+            ],
+            vec![],
+        )?;
+        for account in ["kraken", "mybinance"] {
+            let err = suite
+                .minter
+                .batch_mint(vec![item("carol", &bob), item(account, &bob)])
+                .unwrap_err();
+            assert_eq!(
+                err.root().to_string(),
+                MinterContractError::AccountBlocked {}.to_string()
+            );
+        }
+        assert!(suite.owner_of("carol".to_string()).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_blocklist() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        let minter = suite.minter.address()?;
+        mock.wait_seconds(200)?;
+
+        let err = suite
+            .minter
+            .update_blocklist(vec![BlockRule::Pattern("**".to_string())], vec![])
+            .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::InvalidBlockRule {}.to_string()
+        );
+        suite.minter.update_blocklist(
+            vec![
+                BlockRule::Name("kraken".to_string()),
+                BlockRule::Pattern("*binance*".to_string()),
+            ],
+            vec![],
+        )?;
+        // governance can manage the blocklist too
+        mock.app.borrow_mut().sudo(SudoMsg::Wasm(WasmSudo {
+            contract_addr: minter.clone(),
+            message: to_json_binary(&bs721_account_minter::msg::SudoMsg::UpdateBlocklist {
+                add: vec![BlockRule::Name("coinbase".to_string())],
+                remove: vec![],
+            })?,
+        }))?;
+
+        assert_eq!(
+            suite.minter.blocklist(None, None)?,
+            vec![
+                BlockRule::Name("coinbase".to_string()),
+                BlockRule::Name("kraken".to_string()),
+                BlockRule::Pattern("*binance*".to_string()),
+            ]
+        );
+        assert_eq!(
+            suite
+                .minter
+                .blocklist(Some(1), Some(BlockRule::Name("coinbase".to_string())))?,
+            vec![BlockRule::Name("kraken".to_string())]
+        );
+        assert_eq!(
+            suite
+                .minter
+                .blocklist(None, Some(BlockRule::Name("kraken".to_string())))?,
+            vec![BlockRule::Pattern("*binance*".to_string())]
+        );
+
+        assert_eq!(
+            suite.minter.is_blocked("binance-support")?,
+            Some(BlockRule::Pattern("*binance*".to_string()))
+        );
+        assert_eq!(suite.minter.is_blocked("krakens")?, None);
+        assert_eq!(
+            suite
+                .minter
+                .can_mint("kraken", mock.sender.to_string(), None)?
+                .errors,
+            vec![MintError::Blocked]
+        );
+        for account in ["kraken", "binance-support"] {
+            let err = suite
+                .mint_and_list(mock.clone(), account, &mock.sender)
+                .unwrap_err();
+            assert_eq!(
+                err.root().to_string(),
+                MinterContractError::AccountBlocked {}.to_string()
+            );
+        }
+
+        // look-alikes of blocked names are blocked in IDN mode
+        suite.minter.update_idn_config(Some(IdnConfig {
+            scripts: vec!["Latin".to_string(), "Cyrillic".to_string()],
+            allow_emoji: false,
+        }))?;
+        assert_eq!(
+            suite.minter.is_blocked("krаken")?,
+            Some(BlockRule::Name("kraken".to_string()))
+        );
+
+        suite
+            .minter
+            .update_blocklist(vec![], vec![BlockRule::Name("kraken".to_string())])?;
+        suite.mint_and_list(mock.clone(), "kraken", &mock.sender)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_gift_mint() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        let bob = mock.addr_make("bob");
+        mock.wait_seconds(200)?;
+        mock.add_balance(&mock.sender, coins(super::super::BASE_PRICE * 3, "ubtsg"))?;
+
+        let minter = suite.minter.clone();
+        let gift = |account: &str, initial: Option<Metadata>| {
+            minter.execute(
+                &bs721_account_minter::msg::ExecuteMsg::MintAndList {
+                    account: account.to_string(),
+                    proof: None,
+                    referrer: None,
+                    voucher: None,
+                    owner: Some(bob.to_string()),
+                    initial,
+                    associate: None,
+                },
+                &coins(super::super::BASE_PRICE, "ubtsg"),
+            )
+        };
+
+        // initial records get the same checks as records added later
+        let err = gift(
+            "gift",
+            Some(Metadata {
+                records: vec![TextRecord {
+                    account: "twitter".to_string(),
+                    value: "bob".to_string(),
+                    verified: Some(true),
+                }],
+                ..Metadata::default()
+            }),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            bs721_account::ContractError::UnauthorizedVerification {}.to_string()
+        );
+
+        // the sender pays and delegates, bob owns the account and its listing
+        gift(
+            "gift",
+            Some(Metadata {
+                records: vec![TextRecord::new("twitter", "bob")],
+                ..Metadata::default()
+            }),
+        )?;
+        assert_eq!(suite.owner_of("gift".to_string())?, bob.to_string());
+        assert_eq!(suite.market.ask("gift".to_string())?.unwrap().seller, bob);
+        let records = suite.nft.nft_info("gift".to_string())?.extension.records;
+        assert_eq!(records, vec![TextRecord::new("twitter", "bob")]);
+
+        // the marketplace is approved for the gifted account only, not for all of bob's accounts
+        let market = suite.market.addr_str()?;
+        assert!(suite
+            .nft
+            .all_operators(bob.to_string(), None, None, None)?
+            .operators
+            .is_empty());
+        suite.nft.approval(market, "gift".to_string(), None)?;
+        let buyer = mock.addr_make("buyer");
+        suite.bid_w_funds(mock.clone(), "gift", buyer.clone(), BID_AMOUNT)?;
+        suite
+            .market
+            .call_as(&bob)
+            .accept_bid(buyer.clone(), "gift".to_string())?;
+
+        // the admin can check the owner's delegation instead
+        suite
+            .minter
+            .update_delegation_policy(Some(DelegationPolicy {
+                delegator: Delegator::Owner,
+                ..DelegationPolicy::default()
+            }))?;
+        let err = gift("second-gift", None).unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::IncorrectDelegation {
+                got: 0,
+                expected: super::super::BASE_DELEGATION
+            }
+            .to_string()
+        );
+        suite.delegate_to_val(mock.clone(), bob.clone(), super::super::BASE_DELEGATION)?;
+        gift("second-gift", None)?;
+        assert_eq!(suite.owner_of("second-gift".to_string())?, bob.to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mint_with_profile() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        let bob = mock.addr_make("bob");
+        mock.wait_seconds(200)?;
+        mock.add_balance(&mock.sender, coins(super::super::BASE_PRICE * 4, "ubtsg"))?;
+        suite.nft.approve_all(suite.market.address()?, None)?;
+
+        let mint = |account: &str, owner: Option<&Addr>, associate: &Addr| {
+            suite.minter.execute(
+                &bs721_account_minter::msg::ExecuteMsg::MintAndList {
+                    account: account.to_string(),
+                    proof: None,
+                    referrer: None,
+                    voucher: None,
+                    owner: owner.map(Addr::to_string),
+                    initial: Some(Metadata {
+                        records: vec![TextRecord::new("twitter", "sender")],
+                        ..Metadata::default()
+                    }),
+                    associate: Some(associate.to_string()),
+                },
+                &coins(super::super::BASE_PRICE, "ubtsg"),
+            )
+        };
+
+        let err = mint("profile", Some(&bob), &bob).unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::InvalidAssociation {}.to_string()
+        );
+        // only addresses the owner controls can be associated
+        mint("profile", None, &bob).unwrap_err();
+
+        // minted with its records and associated in one message
+        mint("profile", None, &mock.sender)?;
+        assert_eq!(
+            suite.nft.nft_info("profile".to_string())?.token_uri,
+            Some(mock.sender.to_string())
+        );
+        assert_eq!(
+            suite.nft.associated_address("profile")?,
+            mock.sender.to_string()
+        );
+        assert_eq!(suite.nft.account(mock.sender.to_string())?, "profile");
+        assert_eq!(
+            suite.nft.nft_info("profile".to_string())?.extension.records,
+            vec![TextRecord::new("twitter", "sender")]
+        );
+
+        // the address moves to the new account
+        mint("profile-two", None, &mock.sender)?;
+        assert_eq!(suite.nft.account(mock.sender.to_string())?, "profile-two");
+        assert_eq!(suite.nft.nft_info("profile".to_string())?.token_uri, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_can_mint() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        let sender = mock.sender.to_string();
+        let bob = mock.addr_make("bob").to_string();
+
+        let res = suite.minter.can_mint("  abc ", &sender, None)?;
+        assert_eq!(res.account, "abc");
+        assert_eq!(res.price, coin(super::super::BASE_PRICE * 100, "ubtsg"));
+        assert_eq!(
+            res.required_delegation,
+            Uint128::from(super::super::BASE_DELEGATION * 5)
+        );
+        assert!(!res.started);
+        assert!(!res.can_mint);
+        assert_eq!(res.errors, vec![MintError::NotStarted]);
+
+        // every failed check is reported
+        suite.minter.pause(true)?;
+        let res = suite.minter.can_mint("a!", &bob, None)?;
+        assert!(res.paused);
+        assert_eq!(res.delegation, Uint128::zero());
+        assert_eq!(
+            res.errors,
+            vec![
+                MintError::Paused,
+                MintError::NotStarted,
+                MintError::TooShort,
+                MintError::InsufficientDelegation,
+            ]
+        );
+        assert_eq!(
+            suite.minter.can_mint("bad_name", &sender, None)?.errors,
+            vec![
+                MintError::Paused,
+                MintError::NotStarted,
+                MintError::InvalidCharacters
+            ]
+        );
+        suite.minter.pause(false)?;
+
+        mock.wait_seconds(200)?;
+        let res = suite.minter.can_mint("abcde", &sender, None)?;
+        assert!(res.can_mint);
+        assert!(res.errors.is_empty());
+        assert!(res.delegation >= res.required_delegation);
+
+        suite.mint_and_list(mock.clone(), "abcde", &mock.sender)?;
+        let res = suite.minter.can_mint("abcde", &sender, None)?;
+        assert!(res.taken);
+        assert_eq!(res.errors, vec![MintError::Taken]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_mkt_sudo() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, None)?;
+        let token_id = "bandura";
+        let admin2 = mock.addr_make("admin2");
+        // delegate
+        mock.add_balance(&admin2, vec![coin(10000000000u128, "ubtsg")])?;
+        suite.delegate_to_val(mock.clone(), admin2.clone(), 10000000000u128)?;
+
+        mock.wait_seconds(200)?;
+        suite.mint_and_list(mock.clone(), token_id, &admin2)?;
+
+        // run sudo msg
+        mock.app.borrow_mut().sudo(SudoMsg::Wasm(WasmSudo {
+            contract_addr: suite.market.address()?,
+            message: to_json_binary(&btsg_account::market::SudoMsg::UpdateParams {
+                trading_fee_bps: Some(1000u64),
+                min_price: Some(Uint128::from(1000u128)),
+                ask_interval: Some(1000),
+                cooldown_duration: Some(69),
+                cooldown_cancel_fee: Some(coin(69u128, "jerets")),
+                cooldown_tiers: None,
+            })?,
+        }))?;
+
+        assert_eq!(
+            mock.app
+                .borrow_mut()
+                .sudo(SudoMsg::Wasm(WasmSudo {
+                    contract_addr: suite.market.address()?,
+                    message: to_json_binary(&btsg_account::market::SudoMsg::UpdateParams {
+                        trading_fee_bps: Some(MAX_FEE_BPS * 2u64),
+                        min_price: Some(Uint128::from(1000u128)),
+                        ask_interval: Some(1000),
+                        cooldown_duration: Some(69),
+                        cooldown_cancel_fee: Some(coin(69u128, "jerets")),
+                        cooldown_tiers: None,
+                    })?,
+                }))
+                .unwrap_err()
+                .root_cause()
+                .to_string(),
+            MarketContractError::InvalidTradingFeeBps(MAX_FEE_BPS * 2u64).to_string()
+        );
+
+        // confirm updated params
+        let res = suite.market.params()?;
+        assert_eq!(res.trading_fee_percent, Decimal::percent(10));
+        assert_eq!(res.min_price, Uint128::from(1000u128));
+        assert_eq!(res.ask_interval, 1000);
+        assert_eq!(res.cooldown_duration, 69);
+        assert_eq!(res.cooldown_fee, coin(69u128, "jerets"));
+
+        let new = mock.addr_make("new-jawn");
+        let newnew = mock.addr_make("newer-jawn");
+        mock.app.borrow_mut().sudo(SudoMsg::Wasm(WasmSudo {
+            contract_addr: suite.market.address()?,
+            message: to_json_binary(&btsg_account::market::SudoMsg::UpdateAccountFactory {
+                factory: new.to_string(),
+            })?,
+        }))?;
+        assert_eq!(suite.market.config()?.minter, new);
+        mock.app.borrow_mut().sudo(SudoMsg::Wasm(WasmSudo {
+            contract_addr: suite.market.address()?,
+            message: to_json_binary(&btsg_account::market::SudoMsg::UpdateAccountCollection {
+                collection: newnew.to_string(),
+            })?,
+        }))?;
+        assert_eq!(suite.market.config()?.collection, newnew);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cooldown_period_operator_approve() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        mock.wait_seconds(200)?;
+        let owner = mock.sender.clone();
+        mock.add_balance(&owner, vec![coin(10000000000u128, "ubtsg")])?;
+        let bidder = mock.addr_make("bidder");
+        let account = "account";
+        suite.mint_and_list(mock.clone(), account, &owner)?;
+        suite.bid_w_funds(mock.clone(), account, bidder.clone(), BID_AMOUNT)?;
+        suite.market.accept_bid(bidder.clone(), account.into())?;
+        suite.nft.revoke_all(suite.market.address()?)?;
+        mock.wait_seconds(60)?;
+        let res = suite.market.finalize_bid(account.to_string())?;
+        res.assert_event(&Event::new("wasm").add_attributes(vec![
+            Attribute {
+                key: "_contract_address".to_string(),
+                value: suite.nft.addr_str()?,
+            },
+            Attribute::new("action", "approve_all".to_string()),
+            Attribute::new("operator", suite.market.addr_str()?),
+        ]));
+        assert_eq!(
+            suite.market.ask(account.to_string())?.unwrap().seller,
+            &bidder
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_cooldown_period() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        mock.wait_seconds(200)?;
+
+        let owner = mock.sender.clone();
+        mock.add_balance(&owner, vec![coin(10000000000u128, "ubtsg")])?;
+        let bidder = mock.addr_make("bidder");
+        let account = "account";
+        suite.mint_and_list(mock.clone(), account, &owner)?;
+        suite.bid_w_funds(mock.clone(), account, bidder.clone(), BID_AMOUNT)?;
+        let owner_balance_a = mock.query_balance(&owner, "ubtsg")?;
+        let bidder_balance_a = mock.query_balance(&bidder, "ubtsg")?;
+        suite.market.accept_bid(bidder.clone(), account.into())?;
+        // no funds are transfered before cooldown is over
+        let owner_balance_b = mock.query_balance(&owner, "ubtsg")?;
+        let bidder_balance_b = mock.query_balance(&bidder, "ubtsg")?;
+        mock.wait_seconds(10)?;
+        assert_eq!(owner_balance_a, owner_balance_b);
+        assert_eq!(bidder_balance_a, bidder_balance_b);
+        assert_eq!(
+            suite.market.cooldown(account.to_string())?,
+            Some(PendingBid {
+                ask: Ask {
+                    token_id: account.to_string(),
+                    id: 1,
+                    seller: owner.clone(),
+                },
+                new_owner: bidder.clone(),
+                amount: BID_AMOUNT.into(),
+                unlock_time: mock.block_info()?.time.plus_seconds(50),
+                cooldown: 60,
+                cancel_fee: Some(coin(500_000_000, "ubtsg")),
+            })
+        );
+        assert_eq!(
+            suite.nft.burn(account).unwrap_err().root().to_string(),
+            bs721_account::ContractError::AccountCannotBeTransfered {
+                reason: "Account is in cooldown".to_string()
+            }
+            .to_string()
+        );
+        assert_eq!(
+            suite
+                .nft
+                .transfer_nft(bidder.clone(), account)
+                .unwrap_err()
+                .root()
+                .to_string(),
+            bs721_account::ContractError::AccountCannotBeTransfered {
+                reason: "Account is in cooldown".to_string()
+            }
+            .to_string()
+        );
+        assert_eq!(
+            suite
+                .nft
+                .send_nft(suite.minter.address()?, Binary::default(), account)
+                .unwrap_err()
+                .root()
+                .to_string(),
+            bs721_account::ContractError::AccountCannotBeTransfered {
+                reason: "Account is in cooldown".to_string()
+            }
+            .to_string()
+        );
+
+        // cannot finalize until cooldown period is over
+
+        assert!(mock.block_info()?.time.seconds().lt(&suite
+            .market
+            .cooldown(account.to_string())?
+            .unwrap()
+            .unlock_time
+            .seconds()));
+        assert_eq!(
+            suite
+                .market
+                .finalize_bid(account.to_string())
+                .unwrap_err()
+                .root()
+                .to_string(),
+            MarketContractError::InvalidDuration {}.to_string()
+        );
+
+        mock.wait_seconds(50)?;
+        // cannot cancel after cooldown completes
+        assert_eq!(
+            suite
+                .market
+                .execute(
+                    &ExecuteMsg::CancelCooldown {
+                        token_id: account.to_string(),
+                    },
+                    &vec![coin(500_000_000, "ubtsg")],
+                )
+                .unwrap_err()
+                .root()
+                .to_string(),
+            MarketContractError::InvalidDuration {}.to_string()
+        );
+        // token id doesnt exists
+        assert_eq!(
+            suite
+                .market
+                .execute(
+                    &ExecuteMsg::CancelCooldown {
+                        token_id: "babber".to_string(),
+                    },
+                    &vec![coin(500_000_000, "ubtsg")],
+                )
+                .unwrap_err()
+                .root()
+                .to_string(),
+            MarketContractError::AskNotFound {}.to_string()
+        );
+        // token id doesnt exists
+        assert_eq!(
+            suite
+                .market
+                .execute(
+                    &ExecuteMsg::FinalizeBid {
+                        token_id: "babber".to_string(),
+                    },
+                    &vec![coin(500_000_000, "ubtsg")],
+                )
+                .unwrap_err()
+                .root()
+                .to_string(),
+            MarketContractError::AskNotFound {}.to_string()
+        );
+
+        suite
+            .market
+            .call_as(&owner)
+            .finalize_bid(account.to_string())?;
+        assert_eq!(suite.market.cooldown(account.to_string())?, None,);
+        let owner_balance_c = mock.query_balance(&owner, "ubtsg")?;
+        let bidder_balance_c = mock.query_balance(&bidder, "ubtsg")?;
+        assert_eq!(owner_balance_c.u128(), owner_balance_b.u128() + BID_AMOUNT);
+        assert_eq!(bidder_balance_b, bidder_balance_c);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cancel_cooldown_period() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        mock.wait_seconds(200)?;
+        let owner = mock.sender.clone();
+        mock.add_balance(&owner, vec![coin(10000000000u128, "ubtsg")])?;
+        let bidder = mock.addr_make("bidder");
+        mock.add_balance(&owner, vec![coin(10000000000u128, "uthiol")])?;
+        let account = "account";
+        suite.mint_and_list(mock.clone(), account, &owner)?;
+        suite.bid_w_funds(mock.clone(), account, bidder.clone(), BID_AMOUNT)?;
+        let owner_balance_a = mock.query_balance(&owner, "ubtsg")?;
+        let bidder_balance_a = mock.query_balance(&bidder, "ubtsg")?;
+        suite.market.accept_bid(bidder.clone(), account.into())?;
+        // no funds are transfered before cooldown is over
+        let owner_balance_b = mock.query_balance(&owner, "ubtsg")?;
+        let bidder_balance_b = mock.query_balance(&bidder, "ubtsg")?;
+        mock.wait_seconds(10)?;
+        assert_eq!(owner_balance_a, owner_balance_b);
+        assert_eq!(bidder_balance_a, bidder_balance_b);
+        assert_eq!(
+            suite.market.cooldown(account.to_string())?,
+            Some(PendingBid {
+                ask: Ask {
+                    token_id: account.to_string(),
+                    id: 1,
+                    seller: owner.clone(),
+                },
+                new_owner: bidder.clone(),
+                amount: BID_AMOUNT.into(),
+                unlock_time: mock.block_info()?.time.plus_seconds(50),
+                cooldown: 60,
+                cancel_fee: Some(coin(500_000_000, "ubtsg")),
+            })
+        );
+        mock.wait_seconds(49)?;
+        assert_eq!(
+            suite
+                .market
+                .call_as(&bidder)
+                .cancel_cooldown(account.to_string())
+                .unwrap_err()
+                .root()
+                .to_string(),
+            MinterContractError::Unauthorized {}.to_string()
+        );
+        assert_eq!(
+            suite
+                .market
+                .execute(
+                    &ExecuteMsg::CancelCooldown {
+                        token_id: account.to_string()
+                    },
+                    &vec![]
+                )
+                .unwrap_err()
+                .root()
+                .to_string(),
+            "No funds sent"
+        );
+        assert_eq!(
+            suite
+                .market
+                .execute(
+                    &ExecuteMsg::CancelCooldown {
+                        token_id: account.to_string()
+                    },
+                    &vec![coin(499_000_000, "ubtsg")]
+                )
+                .unwrap_err()
+                .root()
+                .to_string(),
+            MinterContractError::IncorrectPayment {
+                got: 499_000_000u128,
+                expected: 500_000_000u128
+            }
+            .to_string()
+        );
+        assert_eq!(
+            suite
+                .market
+                .execute(
+                    &ExecuteMsg::CancelCooldown {
+                        token_id: account.to_string()
+                    },
+                    &vec![coin(499_000_000, "uthiol")]
+                )
+                .unwrap_err()
+                .root()
+                .to_string(),
+            "Must send reserve token 'ubtsg'"
+        );
+        suite.market.execute(
+            &ExecuteMsg::CancelCooldown {
+                token_id: account.to_string(),
+            },
+            &vec![coin(500_000_000, "ubtsg")],
+        )?;
+        let dd_balance = mock.query_balance(&Addr::unchecked(DEPLOYMENT_DAO), "ubtsg")?;
+        let bidder_balance_c = mock.query_balance(&bidder, "ubtsg")?;
+        assert_eq!(bidder_balance_c.u128(), BID_AMOUNT + 250_000_000u128);
+        assert_eq!(dd_balance.u128(), 250_000_000u128);
+        Ok(())
+        // assert_eq!(suite.market.cooldown(account.to_string())?, None,);
+
+        // assert_eq!(owner_balance_b, bidder_balance_c);
+        // assert_ne!(bidder_balance_b, owner_balance_c);
+    }
+
+    #[test]
+    fn test_waive_cooldown() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        mock.wait_seconds(200)?;
+
+        let owner = mock.sender.clone();
+        let bidder = mock.addr_make("bidder");
+        let bidder2 = mock.addr_make("bidder2");
+        let account = "account";
+        suite.mint_and_list(mock.clone(), account, &owner)?;
+        suite.bid_w_funds(mock.clone(), account, bidder2.clone(), BID_AMOUNT)?;
+
+        // bidder opts in to skipping the cooldown
+        mock.add_balance(&bidder, coins(BID_AMOUNT, "ubtsg"))?;
+        suite.market.call_as(&bidder).execute(
+            &ExecuteMsg::SetBid {
+                token_id: account.to_string(),
+                waive_cooldown: true,
+            },
+            &coins(BID_AMOUNT, "ubtsg"),
+        )?;
+        assert!(
+            suite
+                .market
+                .bid(bidder.to_string(), account.to_string())?
+                .unwrap()
+                .waive_cooldown
+        );
+
+        let owner_balance = mock.query_balance(&owner, "ubtsg")?;
+        suite
+            .market
+            .accept_bid(bidder.clone(), account.to_string())?;
+
+        // sale settles immediately, without a cooldown period
+        assert_eq!(suite.market.cooldown(account.to_string())?, None);
+        assert_eq!(suite.owner_of(account.to_string())?, bidder.to_string());
+        assert_eq!(
+            suite.market.ask(account.to_string())?.unwrap().seller,
+            bidder
+        );
+        assert_eq!(
+            mock.query_balance(&owner, "ubtsg")?.u128(),
+            owner_balance.u128() + BID_AMOUNT
+        );
+        assert_eq!(mock.query_balance(&bidder2, "ubtsg")?.u128(), BID_AMOUNT);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sale_history() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        mock.wait_seconds(200)?;
+
+        let owner = mock.sender.clone();
+        let bidder = mock.addr_make("bidder");
+        let accounts = ["abc", "xyz", "longer"];
+        for account in accounts {
+            suite.mint_and_list(mock.clone(), account, &owner)?;
+        }
+        assert_eq!(suite.market.recent_sales(None, None)?, vec![]);
+        assert_eq!(suite.market.floor_price(3u32)?, None);
+
+        // sell each account at a different price, settling immediately
+        let prices = [BID_AMOUNT * 3, BID_AMOUNT * 2, BID_AMOUNT];
+        mock.add_balance(&bidder, coins(BID_AMOUNT * 6, "ubtsg"))?;
+        for (account, price) in accounts.iter().zip(prices) {
+            suite.market.call_as(&bidder).execute(
+                &ExecuteMsg::SetBid {
+                    token_id: account.to_string(),
+                    waive_cooldown: true,
+                },
+                &coins(price, "ubtsg"),
+            )?;
+            suite
+                .market
+                .accept_bid(bidder.clone(), account.to_string())?;
+            mock.wait_seconds(10)?;
+        }
+
+        let sales = suite.market.sales_by_token("abc".to_string(), None, None)?;
+        assert_eq!(sales.len(), 1);
+        assert_eq!(sales[0].seller, owner);
+        assert_eq!(sales[0].buyer, bidder);
+        assert_eq!(sales[0].price, Uint128::from(BID_AMOUNT * 3));
+        assert_eq!(sales[0].denom, "ubtsg");
+
+        // most recent first, paginated by sale id
+        let recent = suite.market.recent_sales(None, None)?;
+        assert_eq!(
+            recent
+                .iter()
+                .map(|s| s.token_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["longer", "xyz", "abc"]
+        );
+        let older = suite.market.recent_sales(Some(1u32), Some(recent[0].id))?;
+        assert_eq!(older.len(), 1);
+        assert_eq!(older[0].token_id, "xyz");
+
+        // floor only considers accounts of the requested length
+        assert_eq!(
+            suite.market.floor_price(3u32)?,
+            Some(coin(BID_AMOUNT * 2, "ubtsg"))
+        );
+        assert_eq!(
+            suite.market.floor_price(6u32)?,
+            Some(coin(BID_AMOUNT, "ubtsg"))
+        );
+        assert_eq!(suite.market.floor_price(4u32)?, None);
+        assert_eq!(
+            suite.market.last_sale_price("xyz".to_string())?,
+            Some(coin(BID_AMOUNT * 2, "ubtsg"))
+        );
+        assert_eq!(suite.market.last_sale_price("none".to_string())?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_failed_hooks() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        mock.wait_seconds(200)?;
+
+        let owner = mock.sender.clone();
+        let account = "account";
+        suite.mint_and_list(mock.clone(), account, &owner)?;
+
+        // an address without a contract rejects every hook call
+        let broken = mock.addr_make("brokenhook");
+        suite
+            .market
+            .manage_hooks(ManageHooksAction::AddBidHook(broken.to_string()))?;
+
+        // bids still succeed, and each failed call is recorded
+        for i in 0..2 {
+            let bidder = mock.addr_make(format!("bidder{}", i));
+            suite.bid_w_funds(mock.clone(), account, bidder, BID_AMOUNT)?;
+        }
+        let failed = suite.market.failed_hooks(None, None)?;
+        assert_eq!(failed.len(), 2);
+        assert_eq!(failed[0].id, 1);
+        assert_eq!(failed[0].hook, broken);
+        assert_eq!(failed[0].kind, HookKind::Bid);
+        assert_eq!(suite.market.bid_hooks()?.hooks, vec![broken.to_string()]);
+
+        // only the hooks admin can retry
+        let bidder = mock.addr_make("bidder2");
+        assert!(suite.market.call_as(&bidder).retry_hook(1u64).is_err());
+
+        // the retry fails again and reaches the limit, disabling the hook
+        suite.market.retry_hook(1u64)?;
+        let failed = suite.market.failed_hooks(None, None)?;
+        assert_eq!(failed.iter().map(|f| f.id).collect::<Vec<_>>(), vec![2, 3]);
+        assert!(suite.market.bid_hooks()?.hooks.is_empty());
+
+        // later bids no longer call the hook
+        suite.bid_w_funds(mock.clone(), account, bidder, BID_AMOUNT)?;
+        assert_eq!(suite.market.failed_hooks(None, None)?.len(), 2);
+        assert_eq!(suite.market.bids(account.to_string(), None, None)?.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_hooks_timelock() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+
+        mock.app.borrow_mut().sudo(SudoMsg::Wasm(WasmSudo {
+            contract_addr: suite.market.address()?,
+            message: to_json_binary(&btsg_account::market::SudoMsg::UpdateAdminDelay {
+                delay: 100,
+            })?,
+        }))?;
+        assert_eq!(suite.market.admin_delay()?, 100);
+
+        let hook = mock.addr_make("hook");
+        let err = suite
+            .market
+            .manage_hooks(ManageHooksAction::AddSaleHook(hook.to_string()))
+            .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MarketContractError::TimelockRequired {}.to_string()
+        );
+
+        let user = mock.addr_make("user");
+        assert!(suite
+            .market
+            .call_as(&user)
+            .propose_manage_hooks(ManageHooksAction::AddSaleHook(hook.to_string()))
+            .is_err());
+        suite
+            .market
+            .propose_manage_hooks(ManageHooksAction::AddSaleHook(hook.to_string()))?;
+        suite
+            .market
+            .propose_manage_hooks(ManageHooksAction::AddBidHook(hook.to_string()))?;
+        let now = mock.block_info()?.time;
+        let queued = suite.market.queued_hook_actions(None, None)?;
+        assert_eq!(queued.len(), 2);
+        assert_eq!(
+            queued[0].action,
+            ManageHooksAction::AddSaleHook(hook.to_string())
+        );
+        assert_eq!(queued[0].executable_at, now.plus_seconds(100));
+
+        let err = suite.market.execute_manage_hooks(1u64).unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MarketContractError::ActionNotReady {
+                executable_at: now.plus_seconds(100)
+            }
+            .to_string()
+        );
+
+        suite.market.cancel_manage_hooks(2u64)?;
+        mock.wait_seconds(100)?;
+        suite.market.execute_manage_hooks(1u64)?;
+        assert_eq!(suite.market.sale_hooks()?.hooks, vec![hook.to_string()]);
+        assert!(suite.market.bid_hooks()?.hooks.is_empty());
+        assert!(suite.market.queued_hook_actions(None, None)?.is_empty());
+        Ok(())
+    }
+
+    fn noop_execute(
+        _deps: cosmwasm_std::DepsMut,
+        _env: cosmwasm_std::Env,
+        _info: cosmwasm_std::MessageInfo,
+        _msg: cosmwasm_std::Empty,
+    ) -> cosmwasm_std::StdResult<cosmwasm_std::Response> {
+        Ok(cosmwasm_std::Response::new())
+    }
+
+    /// Denies bids on "blocked", and accepting bids below twice the minimum bid
+    fn validation_hook_query(
+        _deps: cosmwasm_std::Deps,
+        _env: cosmwasm_std::Env,
+        msg: ValidationHookQueryMsg,
+    ) -> cosmwasm_std::StdResult<Binary> {
+        let ValidationHookQueryMsg::ValidateBid(bid) = msg;
+        let res = match bid.action {
+            ValidationAction::SetBid if bid.token_id == "blocked" => ValidationResponse::Deny {
+                code: 1,
+                reason: "blocked account".to_string(),
+            },
+            ValidationAction::AcceptBid if bid.amount < Uint128::from(BID_AMOUNT * 2) => {
+                ValidationResponse::Deny {
+                    code: 2,
+                    reason: "below reserve".to_string(),
+                }
+            }
+            _ => ValidationResponse::Allow {},
+        };
+        to_json_binary(&res)
+    }
+
+    #[test]
+    fn test_validation_hooks() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        mock.wait_seconds(200)?;
+
+        let owner = mock.sender.clone();
+        let bidder = mock.addr_make("bidder");
+        suite.mint_and_list(mock.clone(), "blocked", &owner)?;
+        suite.mint_and_list(mock.clone(), "account", &owner)?;
+
+        let code_id = mock
+            .app
+            .borrow_mut()
+            .store_code(Box::new(ContractWrapper::new(
+                noop_execute,
+                noop_execute,
+                validation_hook_query,
+            )));
+        let hook = mock.app.borrow_mut().instantiate_contract(
+            code_id,
+            owner.clone(),
+            &cosmwasm_std::Empty {},
+            &[],
+            "validation-hook",
+            None,
+        )?;
+        suite
+            .market
+            .manage_hooks(ManageHooksAction::AddValidationHook(hook.to_string()))?;
+        assert_eq!(
+            suite.market.validation_hooks()?.hooks,
+            vec![hook.to_string()]
+        );
+
+        // UIs can pre-check bids, with hook and marketplace reasons
+        assert_eq!(
+            suite.market.can_bid(
+                Uint128::from(BID_AMOUNT),
+                bidder.to_string(),
+                "blocked".to_string()
+            )?,
+            ValidationResponse::Deny {
+                code: 1,
+                reason: "blocked account".to_string()
+            }
+        );
+        assert_eq!(
+            suite.market.can_bid(
+                Uint128::from(BID_AMOUNT),
+                bidder.to_string(),
+                "account".to_string()
+            )?,
+            ValidationResponse::Allow {}
+        );
+        match suite
+            .market
+            .can_bid(Uint128::one(), bidder.to_string(), "account".to_string())?
+        {
+            ValidationResponse::Deny { code, .. } => assert_eq!(code, 0),
+            res => panic!("expected a denial, got {:?}", res),
+        }
+
+        // the hook rejects the bid with its reason
+        mock.add_balance(&bidder, coins(BID_AMOUNT, "ubtsg"))?;
+        let err = suite
+            .market
+            .call_as(&bidder)
+            .execute(
+                &ExecuteMsg::SetBid {
+                    token_id: "blocked".to_string(),
+                    waive_cooldown: false,
+                },
+                &coins(BID_AMOUNT, "ubtsg"),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MarketContractError::BidRejected {
+                hook: hook.to_string(),
+                code: 1,
+                reason: "blocked account".to_string()
+            }
+            .to_string()
+        );
+
+        // and can veto accepting a bid
+        suite.bid_w_funds(mock.clone(), "account", bidder.clone(), BID_AMOUNT)?;
+        let err = suite
+            .market
+            .accept_bid(bidder.clone(), "account".to_string())
+            .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MarketContractError::BidRejected {
+                hook: hook.to_string(),
+                code: 2,
+                reason: "below reserve".to_string()
+            }
+            .to_string()
+        );
+
+        // counter offers and private sales are vetoed like accepted bids
+        let below_reserve = MarketContractError::BidRejected {
+            hook: hook.to_string(),
+            code: 2,
+            reason: "below reserve".to_string(),
+        }
+        .to_string();
+        suite.market.counter_offer(
+            bidder.clone(),
+            mock.block_info()?.time.plus_seconds(100),
+            Uint128::from(BID_AMOUNT + 1),
+            "account".to_string(),
+        )?;
+        mock.add_balance(&bidder, coins(1, "ubtsg"))?;
+        let err = suite
+            .market
+            .call_as(&bidder)
+            .execute(
+                &ExecuteMsg::AcceptCounterOffer {
+                    token_id: "account".to_string(),
+                },
+                &coins(1, "ubtsg"),
+            )
+            .unwrap_err();
+        assert_eq!(err.root().to_string(), below_reserve);
+
+        let buyer = mock.addr_make("buyer");
+        suite.market.private_sale(
+            buyer.clone(),
+            Uint128::from(BID_AMOUNT),
+            "account".to_string(),
+        )?;
+        mock.add_balance(&buyer, coins(BID_AMOUNT, "ubtsg"))?;
+        let err = suite
+            .market
+            .call_as(&buyer)
+            .execute(
+                &ExecuteMsg::AcceptPrivateSale {
+                    token_id: "account".to_string(),
+                },
+                &coins(BID_AMOUNT, "ubtsg"),
+            )
+            .unwrap_err();
+        assert_eq!(err.root().to_string(), below_reserve);
+
+        let bidder2 = mock.addr_make("bidder2");
+        suite.bid_w_funds(mock.clone(), "account", bidder2.clone(), BID_AMOUNT * 2)?;
+        suite
+            .market
+            .accept_bid(bidder2.clone(), "account".to_string())?;
+        assert!(suite.market.cooldown("account".to_string())?.is_some());
+        Ok(())
+    }
+
+    fn failing_hook_query(
+        _deps: cosmwasm_std::Deps,
+        _env: cosmwasm_std::Env,
+        _msg: ValidationHookQueryMsg,
+    ) -> cosmwasm_std::StdResult<Binary> {
+        Err(cosmwasm_std::StdError::generic_err("hook unavailable"))
+    }
+
+    #[test]
+    fn test_failing_validation_hook() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        mock.wait_seconds(200)?;
+
+        let owner = mock.sender.clone();
+        suite.mint_and_list(mock.clone(), "account", &owner)?;
+
+        let code_id = mock
+            .app
+            .borrow_mut()
+            .store_code(Box::new(ContractWrapper::new(
+                noop_execute,
+                noop_execute,
+                failing_hook_query,
+            )));
+        let hook = mock.app.borrow_mut().instantiate_contract(
+            code_id,
+            owner.clone(),
+            &cosmwasm_std::Empty {},
+            &[],
+            "failing-hook",
+            None,
+        )?;
+        suite
+            .market
+            .manage_hooks(ManageHooksAction::AddValidationHook(hook.to_string()))?;
+
+        // a hook whose query errors rejects every bid, however often it's called
+        let bidder = mock.addr_make("bidder");
+        match suite.market.can_bid(
+            Uint128::from(BID_AMOUNT),
+            bidder.to_string(),
+            "account".to_string(),
+        )? {
+            ValidationResponse::Deny { code, .. } => assert_eq!(code, 0),
+            res => panic!("expected a denial, got {:?}", res),
+        }
+        mock.add_balance(&bidder, coins(BID_AMOUNT, "ubtsg"))?;
+        for _ in 0..4 {
+            let err = suite
+                .market
+                .call_as(&bidder)
+                .execute(
+                    &ExecuteMsg::SetBid {
+                        token_id: "account".to_string(),
+                        waive_cooldown: false,
+                    },
+                    &coins(BID_AMOUNT, "ubtsg"),
+                )
+                .unwrap_err();
+            assert!(err.root().to_string().starts_with(&format!(
+                "ValidationHookFailed: {hook} could not be queried"
+            )));
+        }
+        assert_eq!(
+            suite.market.validation_hooks()?.hooks,
+            vec![hook.to_string()]
+        );
+
+        // only the hooks admin removes it
+        suite
+            .market
+            .manage_hooks(ManageHooksAction::RemoveValidationHook(hook.to_string()))?;
+        suite.bid_w_funds(mock.clone(), "account", bidder.clone(), BID_AMOUNT)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_cooldown_tiers() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        mock.wait_seconds(200)?;
+
+        let owner = mock.sender.clone();
+        let bidder = mock.addr_make("bidder");
+        let update_tiers = |tiers: Vec<CooldownTier>| {
+            mock.app.borrow_mut().sudo(SudoMsg::Wasm(WasmSudo {
+                contract_addr: suite.market.address().unwrap(),
+                message: to_json_binary(&btsg_account::market::SudoMsg::UpdateParams {
+                    trading_fee_bps: None,
+                    min_price: None,
+                    ask_interval: None,
+                    cooldown_duration: None,
+                    cooldown_cancel_fee: None,
+                    cooldown_tiers: Some(tiers),
+                })
+                .unwrap(),
+            }))
+        };
+        let large_sale = CooldownTier {
+            min_price: Uint128::from(BID_AMOUNT * 2),
+            duration: 600,
+            cancel_fee: coin(1_000_000_000, "ubtsg"),
+        };
+
+        // tiers must be sorted by price
+        assert_eq!(
+            update_tiers(vec![large_sale.clone(), large_sale.clone()])
+                .unwrap_err()
+                .root_cause()
+                .to_string(),
+            MarketContractError::InvalidCooldownTiers {}.to_string()
+        );
+        update_tiers(vec![large_sale.clone()])?;
+        assert_eq!(suite.market.params()?.cooldown_tiers, vec![large_sale]);
+
+        // sales below the lowest tier use the default cooldown
+        suite.mint_and_list(mock.clone(), "small-sale", &owner)?;
+        suite.bid_w_funds(mock.clone(), "small-sale", bidder.clone(), BID_AMOUNT)?;
+        suite
+            .market
+            .accept_bid(bidder.clone(), "small-sale".to_string())?;
+        assert_eq!(
+            suite
+                .market
+                .cooldown("small-sale".to_string())?
+                .unwrap()
+                .unlock_time,
+            mock.block_info()?.time.plus_seconds(60)
+        );
+
+        suite.mint_and_list(mock.clone(), "large-sale", &owner)?;
+        suite.bid_w_funds(mock.clone(), "large-sale", bidder.clone(), BID_AMOUNT * 2)?;
+        suite
+            .market
+            .accept_bid(bidder.clone(), "large-sale".to_string())?;
+        assert_eq!(
+            suite
+                .market
+                .cooldown("large-sale".to_string())?
+                .unwrap()
+                .unlock_time,
+            mock.block_info()?.time.plus_seconds(600)
+        );
+
+        // cancelling a large sale requires the tier's fee
+        mock.add_balance(&owner, coins(1_000_000_000, "ubtsg"))?;
+        assert_eq!(
+            suite
+                .market
+                .execute(
+                    &ExecuteMsg::CancelCooldown {
+                        token_id: "large-sale".to_string(),
+                    },
+                    &coins(500_000_000, "ubtsg"),
+                )
+                .unwrap_err()
+                .root()
+                .to_string(),
+            MarketContractError::IncorrectPayment {
+                got: 500_000_000,
+                expected: 1_000_000_000,
             }
             .to_string()
         );
+        suite.market.execute(
+            &ExecuteMsg::CancelCooldown {
+                token_id: "large-sale".to_string(),
+            },
+            &coins(1_000_000_000, "ubtsg"),
+        )?;
+        assert_eq!(
+            mock.query_balance(&bidder, "ubtsg")?.u128(),
+            BID_AMOUNT * 2 + 500_000_000
+        );
 
-        // cannot finalize until cooldown period is over
+        // a sale keeps the cooldown and fee it was accepted with, even once the tiers change
+        update_tiers(vec![CooldownTier {
+            min_price: Uint128::from(BID_AMOUNT),
+            duration: 0,
+            cancel_fee: coin(1, "ubtsg"),
+        }])?;
+        let pending = suite.market.cooldown("small-sale".to_string())?.unwrap();
+        assert_eq!(pending.cooldown, 60);
+        assert_eq!(pending.cancel_fee, Some(coin(500_000_000, "ubtsg")));
+        mock.add_balance(&owner, coins(500_000_000, "ubtsg"))?;
+        suite.market.execute(
+            &ExecuteMsg::CancelCooldown {
+                token_id: "small-sale".to_string(),
+            },
+            &coins(500_000_000, "ubtsg"),
+        )?;
 
-        assert!(mock.block_info()?.time.seconds().lt(&suite
+        // a sale without cooldown can't be cancelled
+        suite.mint_and_list(mock.clone(), "no-cooldown", &owner)?;
+        suite.bid_w_funds(mock.clone(), "no-cooldown", bidder.clone(), BID_AMOUNT)?;
+        suite
             .market
-            .cooldown(account.to_string())?
-            .unwrap()
-            .unlock_time
-            .seconds()));
+            .accept_bid(bidder.clone(), "no-cooldown".to_string())?;
         assert_eq!(
             suite
                 .market
-                .finalize_bid(account.to_string())
+                .execute(
+                    &ExecuteMsg::CancelCooldown {
+                        token_id: "no-cooldown".to_string(),
+                    },
+                    &coins(1, "ubtsg"),
+                )
                 .unwrap_err()
                 .root()
                 .to_string(),
             MarketContractError::InvalidDuration {}.to_string()
         );
+        Ok(())
+    }
 
-        mock.wait_seconds(50)?;
-        // cannot cancel after cooldown completes
+    #[test]
+    fn test_counter_offer() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        mock.wait_seconds(200)?;
+
+        let owner = mock.sender.clone();
+        let bidder = mock.addr_make("bidder");
+        let bidder2 = mock.addr_make("bidder2");
+        let account = "account";
+        suite.mint_and_list(mock.clone(), account, &owner)?;
+        suite.bid_w_funds(mock.clone(), account, bidder.clone(), BID_AMOUNT)?;
+        suite.bid_w_funds(mock.clone(), account, bidder2.clone(), BID_AMOUNT)?;
+        let expires = mock.block_info()?.time.plus_seconds(100);
+
+        // counter offer must be higher than the existing bid
+        assert_eq!(
+            suite
+                .market
+                .counter_offer(
+                    bidder.clone(),
+                    expires,
+                    Uint128::from(BID_AMOUNT),
+                    account.to_string()
+                )
+                .unwrap_err()
+                .root()
+                .to_string(),
+            MarketContractError::PriceTooSmall(BID_AMOUNT.into()).to_string()
+        );
+        // only the token owner can counter
+        assert_eq!(
+            suite
+                .market
+                .call_as(&bidder2)
+                .counter_offer(
+                    bidder.clone(),
+                    expires,
+                    Uint128::from(BID_AMOUNT * 2),
+                    account.to_string()
+                )
+                .unwrap_err()
+                .root()
+                .to_string(),
+            MarketContractError::UnauthorizedOwner {}.to_string()
+        );
+
+        suite.market.counter_offer(
+            bidder.clone(),
+            expires,
+            Uint128::from(BID_AMOUNT * 2),
+            account.to_string(),
+        )?;
+        suite.market.counter_offer(
+            bidder2.clone(),
+            mock.block_info()?.time.plus_seconds(10),
+            Uint128::from(BID_AMOUNT * 2),
+            account.to_string(),
+        )?;
+        assert_eq!(
+            suite
+                .market
+                .counter_offers(account.to_string(), None, None)?
+                .len(),
+            2
+        );
+
+        // expired counter offers cannot be accepted
+        mock.wait_seconds(20)?;
+        mock.add_balance(&bidder2, coins(BID_AMOUNT, "ubtsg"))?;
         assert_eq!(
             suite
                 .market
+                .call_as(&bidder2)
                 .execute(
-                    &ExecuteMsg::CancelCooldown {
+                    &ExecuteMsg::AcceptCounterOffer {
                         token_id: account.to_string(),
                     },
-                    &vec![coin(500_000_000, "ubtsg")],
+                    &coins(BID_AMOUNT, "ubtsg"),
+                )
+                .unwrap_err()
+                .root()
+                .to_string(),
+            MarketContractError::CounterOfferExpired {}.to_string()
+        );
+
+        // bidder must top up the difference to their existing bid
+        mock.add_balance(&bidder, coins(BID_AMOUNT, "ubtsg"))?;
+        assert_eq!(
+            suite
+                .market
+                .call_as(&bidder)
+                .execute(
+                    &ExecuteMsg::AcceptCounterOffer {
+                        token_id: account.to_string(),
+                    },
+                    &coins(BID_AMOUNT * 2, "ubtsg"),
+                )
+                .unwrap_err()
+                .root()
+                .to_string(),
+            MarketContractError::IncorrectPayment {
+                got: BID_AMOUNT * 2,
+                expected: BID_AMOUNT,
+            }
+            .to_string()
+        );
+        suite.market.call_as(&bidder).execute(
+            &ExecuteMsg::AcceptCounterOffer {
+                token_id: account.to_string(),
+            },
+            &coins(BID_AMOUNT, "ubtsg"),
+        )?;
+
+        // remaining bids and counter offers are refunded, sale enters cooldown
+        assert_eq!(
+            mock.query_balance(&bidder2, "ubtsg")?.u128(),
+            BID_AMOUNT * 2
+        );
+        assert!(suite
+            .market
+            .bids(account.to_string(), None, None)?
+            .is_empty());
+        assert!(suite
+            .market
+            .counter_offers(account.to_string(), None, None)?
+            .is_empty());
+        let pending = suite.market.cooldown(account.to_string())?.unwrap();
+        assert_eq!(pending.new_owner, bidder);
+        assert_eq!(pending.amount, Uint128::from(BID_AMOUNT * 2));
+
+        mock.wait_seconds(60)?;
+        suite.market.finalize_bid(account.to_string())?;
+        assert_eq!(suite.owner_of(account.to_string())?, bidder.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn test_private_sale() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        mock.wait_seconds(200)?;
+
+        let owner = mock.sender.clone();
+        let buyer = mock.addr_make("buyer");
+        let bidder = mock.addr_make("bidder");
+        let account = "account";
+        let price = BID_AMOUNT * 3;
+        suite.mint_and_list(mock.clone(), account, &owner)?;
+        suite.bid_w_funds(mock.clone(), account, buyer.clone(), BID_AMOUNT)?;
+        suite.bid_w_funds(mock.clone(), account, bidder.clone(), BID_AMOUNT)?;
+
+        // seller can withdraw a private sale
+        suite
+            .market
+            .private_sale(bidder.clone(), Uint128::from(price), account.to_string())?;
+        assert_eq!(
+            suite
+                .market
+                .call_as(&bidder)
+                .cancel_private_sale(account.to_string())
+                .unwrap_err()
+                .root()
+                .to_string(),
+            MarketContractError::Unauthorized {}.to_string()
+        );
+        suite.market.cancel_private_sale(account.to_string())?;
+        assert_eq!(suite.market.private_sale_offer(account.to_string())?, None);
+
+        suite
+            .market
+            .private_sale(buyer.clone(), Uint128::from(price), account.to_string())?;
+        assert_eq!(
+            suite.market.private_sale_offer(account.to_string())?,
+            Some(PrivateSale {
+                token_id: account.to_string(),
+                seller: owner.clone(),
+                buyer: buyer.clone(),
+                price: price.into(),
+            })
+        );
+
+        // only the designated buyer may accept
+        mock.add_balance(&bidder, coins(price, "ubtsg"))?;
+        assert_eq!(
+            suite
+                .market
+                .call_as(&bidder)
+                .execute(
+                    &ExecuteMsg::AcceptPrivateSale {
+                        token_id: account.to_string(),
+                    },
+                    &coins(price, "ubtsg"),
                 )
                 .unwrap_err()
                 .root()
                 .to_string(),
-            MarketContractError::InvalidDuration {}.to_string()
+            MarketContractError::Unauthorized {}.to_string()
+        );
+
+        mock.add_balance(&buyer, coins(price, "ubtsg"))?;
+        suite.market.call_as(&buyer).execute(
+            &ExecuteMsg::AcceptPrivateSale {
+                token_id: account.to_string(),
+            },
+            &coins(price, "ubtsg"),
+        )?;
+
+        // existing bids, including the buyers, are refunded
+        assert_eq!(mock.query_balance(&buyer, "ubtsg")?.u128(), BID_AMOUNT);
+        assert!(suite
+            .market
+            .bids(account.to_string(), None, None)?
+            .is_empty());
+        assert_eq!(suite.market.private_sale_offer(account.to_string())?, None);
+        let pending = suite.market.cooldown(account.to_string())?.unwrap();
+        assert_eq!(pending.new_owner, buyer);
+        assert_eq!(pending.amount, Uint128::from(price));
+
+        // a second sale cannot begin while the first is in escrow
+        suite.bid_w_funds(mock.clone(), account, bidder.clone(), BID_AMOUNT)?;
+        assert_eq!(
+            suite
+                .market
+                .accept_bid(bidder.clone(), account.to_string())
+                .unwrap_err()
+                .root()
+                .to_string(),
+            MarketContractError::CooldownInProgress {}.to_string()
+        );
+
+        mock.wait_seconds(60)?;
+        suite.market.finalize_bid(account.to_string())?;
+        assert_eq!(suite.owner_of(account.to_string())?, buyer.to_string());
+
+        // an offer is dropped once the token changes hands, so it can't come back at an old price
+        suite.market.call_as(&buyer).private_sale(
+            bidder.clone(),
+            Uint128::from(price),
+            account.to_string(),
+        )?;
+        suite
+            .nft
+            .call_as(&buyer)
+            .transfer_nft(owner.clone(), account)?;
+        assert_eq!(suite.market.private_sale_offer(account.to_string())?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bundle_bid() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        mock.wait_seconds(200)?;
+
+        let owner = mock.sender.clone();
+        let other = mock.addr_make("other");
+        let buyer = mock.addr_make("buyer");
+        let bidder = mock.addr_make("bidder");
+        let accounts = ["jimi-music", "jimitour", "jimi-live"];
+        for account in accounts {
+            suite.mint_and_list(mock.clone(), account, &owner)?;
+        }
+        suite.mint_and_list(mock.clone(), "jimi-other", &owner)?;
+        suite.nft.transfer_nft(other.clone(), "jimi-other")?;
+        suite.bid_w_funds(mock.clone(), accounts[0], bidder.clone(), BID_AMOUNT)?;
+
+        let amount = BID_AMOUNT * 3 + 1;
+        mock.add_balance(&buyer, coins(amount, "ubtsg"))?;
+        let set_bundle_bid = |token_ids: Vec<&str>| {
+            suite.market.call_as(&buyer).execute(
+                &ExecuteMsg::SetBundleBid {
+                    token_ids: token_ids.into_iter().map(String::from).collect(),
+                    amount: amount.into(),
+                },
+                &coins(amount, "ubtsg"),
+            )
+        };
+
+        // bundles need several accounts, all from the same seller
+        assert_eq!(
+            set_bundle_bid(vec![accounts[0]])
+                .unwrap_err()
+                .root()
+                .to_string(),
+            MarketContractError::InvalidBundle(
+                "must contain between 2 and 10 accounts".to_string()
+            )
+            .to_string()
+        );
+        assert_eq!(
+            set_bundle_bid(vec![accounts[0], accounts[0]])
+                .unwrap_err()
+                .root()
+                .to_string(),
+            MarketContractError::InvalidBundle("duplicate account jimi-music".to_string())
+                .to_string()
+        );
+        assert_eq!(
+            set_bundle_bid(vec![accounts[0], "jimi-other"])
+                .unwrap_err()
+                .root()
+                .to_string(),
+            MarketContractError::InvalidBundle(
+                "accounts must be listed by the same seller".to_string()
+            )
+            .to_string()
+        );
+
+        set_bundle_bid(accounts.to_vec())?;
+        let bundles = suite
+            .market
+            .bundle_bids_by_token(accounts[1].to_string(), None, None)?;
+        assert_eq!(bundles.len(), 1);
+        assert_eq!(bundles[0].token_ids, accounts.map(String::from).to_vec());
+        assert_eq!(bundles[0].seller, owner);
+        let id = bundles[0].id;
+
+        assert_eq!(
+            suite
+                .market
+                .call_as(&other)
+                .accept_bundle_bid(id)
+                .unwrap_err()
+                .root()
+                .to_string(),
+            MarketContractError::UnauthorizedOwner {}.to_string()
         );
-        // token id doesnt exists
+        suite.market.accept_bundle_bid(id)?;
+
+        // existing bids are refunded, and every account is locked in the same cooldown
+        assert_eq!(mock.query_balance(&bidder, "ubtsg")?.u128(), BID_AMOUNT);
+        assert_eq!(suite.market.bundle_bid(id)?, None);
+        assert!(suite
+            .market
+            .bundle_bids_by_token(accounts[1].to_string(), None, None)?
+            .is_empty());
+        let unlock_time = suite.market.bundle_cooldown(id)?.unwrap().unlock_time;
+        for account in accounts {
+            let pending = suite.market.cooldown(account.to_string())?.unwrap();
+            assert_eq!(pending.new_owner, buyer);
+            assert_eq!(pending.unlock_time, unlock_time);
+        }
         assert_eq!(
             suite
-                .market
-                .execute(
-                    &ExecuteMsg::CancelCooldown {
-                        token_id: "babber".to_string(),
-                    },
-                    &vec![coin(500_000_000, "ubtsg")],
-                )
+                .nft
+                .transfer_nft(other.clone(), accounts[2])
                 .unwrap_err()
                 .root()
                 .to_string(),
-            MarketContractError::AskNotFound {}.to_string()
+            bs721_account::ContractError::AccountCannotBeTransfered {
+                reason: "Account is in cooldown".to_string()
+            }
+            .to_string()
         );
-        // token id doesnt exists
         assert_eq!(
             suite
                 .market
-                .execute(
-                    &ExecuteMsg::FinalizeBid {
-                        token_id: "babber".to_string(),
-                    },
-                    &vec![coin(500_000_000, "ubtsg")],
-                )
+                .finalize_bundle_bid(id)
                 .unwrap_err()
                 .root()
                 .to_string(),
-            MarketContractError::AskNotFound {}.to_string()
+            MarketContractError::InvalidDuration {}.to_string()
         );
 
-        suite
-            .market
-            .call_as(&owner)
-            .finalize_bid(account.to_string())?;
-        assert_eq!(suite.market.cooldown(account.to_string())?, None,);
-        let owner_balance_c = mock.query_balance(&owner, "ubtsg")?;
-        let bidder_balance_c = mock.query_balance(&bidder, "ubtsg")?;
-        assert_eq!(owner_balance_c.u128(), owner_balance_b.u128() + BID_AMOUNT);
-        assert_eq!(bidder_balance_b, bidder_balance_c);
+        let owner_balance = mock.query_balance(&owner, "ubtsg")?;
+        mock.wait_seconds(60)?;
+        suite.market.call_as(&buyer).finalize_bundle_bid(id)?;
+        for account in accounts {
+            assert_eq!(suite.owner_of(account.to_string())?, buyer.to_string());
+            assert_eq!(suite.market.cooldown(account.to_string())?, None);
+        }
+        assert_eq!(suite.market.bundle_cooldown(id)?, None);
+        assert_eq!(
+            mock.query_balance(&owner, "ubtsg")?.u128(),
+            owner_balance.u128() + amount
+        );
         Ok(())
     }
 
     #[test]
-    fn test_cancel_cooldown_period() -> anyhow::Result<()> {
+    fn test_market_pause_and_emergency_refund() -> anyhow::Result<()> {
         let mock = MockBech32::new("bitsong");
         let mut suite = BtsgAccountSuite::new(mock.clone());
         suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
         mock.wait_seconds(200)?;
+
         let owner = mock.sender.clone();
-        mock.add_balance(&owner, vec![coin(10000000000u128, "ubtsg")])?;
-        let bidder = mock.addr_make("bidder");
-        mock.add_balance(&owner, vec![coin(10000000000u128, "uthiol")])?;
-        let account = "account";
-        suite.mint_and_list(mock.clone(), account, &owner)?;
-        suite.bid_w_funds(mock.clone(), account, bidder.clone(), BID_AMOUNT)?;
-        let owner_balance_a = mock.query_balance(&owner, "ubtsg")?;
-        let bidder_balance_a = mock.query_balance(&bidder, "ubtsg")?;
-        suite.market.accept_bid(bidder.clone(), account.into())?;
-        // no funds are transfered before cooldown is over
-        let owner_balance_b = mock.query_balance(&owner, "ubtsg")?;
-        let bidder_balance_b = mock.query_balance(&bidder, "ubtsg")?;
-        mock.wait_seconds(10)?;
-        assert_eq!(owner_balance_a, owner_balance_b);
-        assert_eq!(bidder_balance_a, bidder_balance_b);
-        assert_eq!(
-            suite.market.cooldown(account.to_string())?,
-            Some(PendingBid {
-                ask: Ask {
-                    token_id: account.to_string(),
-                    id: 1,
-                    seller: owner.clone(),
-                },
-                new_owner: bidder.clone(),
-                amount: BID_AMOUNT.into(),
-                unlock_time: mock.block_info()?.time.plus_seconds(50)
-            })
-        );
-        mock.wait_seconds(49)?;
+        let bidder1 = mock.addr_make("bidder1");
+        let bidder2 = mock.addr_make("bidder2");
+        let bidder3 = mock.addr_make("bidder3");
+        suite.mint_and_list(mock.clone(), "account1", &owner)?;
+        suite.mint_and_list(mock.clone(), "account2", &owner)?;
+        suite.bid_w_funds(mock.clone(), "account1", bidder1.clone(), BID_AMOUNT)?;
+        suite.bid_w_funds(mock.clone(), "account1", bidder3.clone(), BID_AMOUNT)?;
+        suite.bid_w_funds(mock.clone(), "account2", bidder2.clone(), BID_AMOUNT)?;
+        suite
+            .market
+            .accept_bid(bidder2.clone(), "account2".to_string())?;
+
+        // only the hooks admin or governance may pause
         assert_eq!(
             suite
                 .market
-                .call_as(&bidder)
-                .cancel_cooldown(account.to_string())
+                .call_as(&bidder1)
+                .update_status(MarketStatus::Paused)
                 .unwrap_err()
                 .root()
                 .to_string(),
-            MinterContractError::Unauthorized {}.to_string()
+            MarketContractError::Unauthorized {}.to_string()
         );
+        suite.market.update_status(MarketStatus::Paused)?;
+        assert_eq!(suite.market.status()?, MarketStatus::Paused);
+
+        // new bids and sales are halted
+        mock.add_balance(&bidder1, coins(BID_AMOUNT, "ubtsg"))?;
         assert_eq!(
             suite
                 .market
+                .call_as(&bidder1)
                 .execute(
-                    &ExecuteMsg::CancelCooldown {
-                        token_id: account.to_string()
+                    &ExecuteMsg::SetBid {
+                        token_id: "account2".to_string(),
+                        waive_cooldown: false,
                     },
-                    &vec![]
+                    &coins(BID_AMOUNT, "ubtsg"),
                 )
                 .unwrap_err()
                 .root()
                 .to_string(),
-            "No funds sent"
+            MarketContractError::MarketPaused {}.to_string()
         );
         assert_eq!(
             suite
                 .market
-                .execute(
-                    &ExecuteMsg::CancelCooldown {
-                        token_id: account.to_string()
-                    },
-                    &vec![coin(499_000_000, "ubtsg")]
-                )
+                .accept_bid(bidder3.clone(), "account1".to_string())
                 .unwrap_err()
                 .root()
                 .to_string(),
-            MinterContractError::IncorrectPayment {
-                got: 499_000_000u128,
-                expected: 500_000_000u128
-            }
-            .to_string()
+            MarketContractError::MarketPaused {}.to_string()
         );
+        mock.wait_seconds(60)?;
         assert_eq!(
             suite
                 .market
-                .execute(
-                    &ExecuteMsg::CancelCooldown {
-                        token_id: account.to_string()
-                    },
-                    &vec![coin(499_000_000, "uthiol")]
-                )
+                .finalize_bid("account2".to_string())
                 .unwrap_err()
                 .root()
                 .to_string(),
-            "Must send reserve token 'ubtsg'"
+            MarketContractError::MarketPaused {}.to_string()
         );
-        suite.market.execute(
-            &ExecuteMsg::CancelCooldown {
-                token_id: account.to_string(),
-            },
-            &vec![coin(500_000_000, "ubtsg")],
-        )?;
-        let dd_balance = mock.query_balance(&Addr::unchecked(DEPLOYMENT_DAO), "ubtsg")?;
-        let bidder_balance_c = mock.query_balance(&bidder, "ubtsg")?;
-        assert_eq!(bidder_balance_c.u128(), BID_AMOUNT + 250_000_000u128);
-        assert_eq!(dd_balance.u128(), 250_000_000u128);
-        Ok(())
-        // assert_eq!(suite.market.cooldown(account.to_string())?, None,);
 
-        // assert_eq!(owner_balance_b, bidder_balance_c);
-        // assert_ne!(bidder_balance_b, owner_balance_c);
+        // bidders can still reclaim their escrow
+        suite
+            .market
+            .call_as(&bidder1)
+            .remove_bid("account1".to_string())?;
+        assert_eq!(
+            mock.query_balance(&bidder1, "ubtsg")?.u128(),
+            BID_AMOUNT * 2
+        );
+        assert_eq!(
+            suite
+                .market
+                .refund_escrow(None)
+                .unwrap_err()
+                .root()
+                .to_string(),
+            MarketContractError::NotInEmergency {}.to_string()
+        );
+
+        // emergency mode releases all remaining escrow, in pages
+        mock.app.borrow_mut().sudo(SudoMsg::Wasm(WasmSudo {
+            contract_addr: suite.market.address()?,
+            message: to_json_binary(&btsg_account::market::SudoMsg::UpdateStatus {
+                status: MarketStatus::Emergency,
+            })?,
+        }))?;
+        suite.market.call_as(&bidder1).refund_escrow(Some(1))?;
+        assert_eq!(mock.query_balance(&bidder3, "ubtsg")?.u128(), BID_AMOUNT);
+        assert_eq!(mock.query_balance(&bidder2, "ubtsg")?.u128(), 0);
+        suite.market.call_as(&bidder1).refund_escrow(None)?;
+        assert_eq!(mock.query_balance(&bidder2, "ubtsg")?.u128(), BID_AMOUNT);
+        assert_eq!(suite.market.cooldown("account2".to_string())?, None);
+        assert!(suite
+            .market
+            .bids("account1".to_string(), None, None)?
+            .is_empty());
+        assert_eq!(suite.owner_of("account2".to_string())?, owner.to_string());
+
+        suite.market.update_status(MarketStatus::Active)?;
+        assert_eq!(suite.market.status()?, MarketStatus::Active);
+        Ok(())
     }
 
     #[test]
@@ -1307,6 +3760,53 @@ mod query {
         Ok(())
     }
     #[test]
+    fn test_query_highest_bid_skips_invalid_bids() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        let admin = mock.sender.clone();
+        let new_owner = mock.addr_make("new-owner");
+        let token_id = "bandura";
+
+        mock.wait_seconds(200)?;
+        suite.mint_and_list(mock.clone(), token_id, &admin)?;
+
+        // place more bids than can be refunded at once when the ask is updated
+        let bid_count = MAX_REMOVE_BID_LIMIT as u128 + 2;
+        for i in 0..bid_count {
+            let bidder = mock.addr_make(format!("bidder{}", i));
+            suite.bid_w_funds(mock.clone(), token_id, bidder, BID_AMOUNT + i)?;
+        }
+
+        // the highest bids are queued for refund, and no longer have valid escrow
+        suite.nft.transfer_nft(new_owner.clone(), token_id)?;
+        assert_eq!(
+            suite.market.bids(token_id.to_string(), None, None)?.len(),
+            2
+        );
+        assert_eq!(suite.market.highest_bid(token_id.to_string())?, None);
+
+        let bidder = mock.addr_make("fresh-bidder");
+        suite.bid_w_funds(mock.clone(), token_id, bidder.clone(), BID_AMOUNT)?;
+        assert_eq!(
+            suite
+                .market
+                .highest_bid(token_id.to_string())?
+                .unwrap()
+                .bidder,
+            bidder
+        );
+
+        // processing the queued refunds leaves only the fresh bid
+        suite.market.checked_remove_bids(token_id.to_string())?;
+        assert_eq!(
+            suite.market.bids(token_id.to_string(), None, None)?.len(),
+            1
+        );
+
+        Ok(())
+    }
+    #[test]
     fn test_query_account() -> anyhow::Result<()> {
         let mock = MockBech32::new("bitsong");
         let mut suite = BtsgAccountSuite::new(mock.clone());
@@ -1606,7 +4106,7 @@ mod collection {
 }
 mod public_start_time {
 
-    use btsg_account::minter::Config;
+    use btsg_account::minter::{AdminAction, Config};
     use cosmwasm_std::coin;
 
     use super::*;
@@ -1658,6 +4158,174 @@ mod public_start_time {
 
         Ok(())
     }
+
+    #[test]
+    fn test_admin_timelock() -> anyhow::Result<()> {
+        let mock = MockBech32::new("bitsong");
+        let mut suite = BtsgAccountSuite::new(mock.clone());
+        suite.default_setup(mock.clone(), None, Some(mock.sender.clone()))?;
+        let minter = suite.minter.address()?;
+
+        mock.app.borrow_mut().sudo(SudoMsg::Wasm(WasmSudo {
+            contract_addr: minter.clone(),
+            message: to_json_binary(&bs721_account_minter::msg::SudoMsg::UpdateAdminDelay {
+                delay: 100,
+            })?,
+        }))?;
+        assert_eq!(suite.minter.admin_delay()?, 100);
+
+        // config changes and unpausing must be proposed, pausing stays immediate
+        let now = mock.block_info()?.time;
+        let config = Config {
+            public_mint_start_time: now.plus_seconds(300),
+        };
+        let err = suite.minter.update_config(config.clone()).unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::TimelockRequired {}.to_string()
+        );
+        suite.minter.pause(true)?;
+        let err = suite.minter.pause(false).unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::TimelockRequired {}.to_string()
+        );
+
+        // so must every other change to who can mint which accounts, and when
+        let whitelist = Whitelist {
+            list: WhitelistType::Contract {
+                address: mock.addr_make("whitelist").to_string(),
+            },
+            start_time: now,
+            end_time: now.plus_seconds(1000),
+            per_address_limit: 1,
+            discount: None,
+        };
+        let err = suite.minter.add_whitelist(whitelist.clone()).unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::TimelockRequired {}.to_string()
+        );
+        let err = suite
+            .minter
+            .update_blocklist(vec![], vec![BlockRule::Name("kraken".to_string())])
+            .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::TimelockRequired {}.to_string()
+        );
+        let err = suite
+            .minter
+            .batch_mint(vec![BatchMintItem {
+                account: "partner".to_string(),
+                owner: mock.sender.to_string(),
+                metadata: Metadata::default(),
+            }])
+            .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::TimelockRequired {}.to_string()
+        );
+
+        // a start time passed by the time the change can be executed is rejected
+        let err = suite
+            .minter
+            .propose_admin_action(AdminAction::UpdateConfig {
+                config: Config {
+                    public_mint_start_time: now.plus_seconds(50),
+                },
+            })
+            .unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::InvalidTradingStartTime(
+                now.plus_seconds(100),
+                now.plus_seconds(50)
+            )
+            .to_string()
+        );
+
+        let user = mock.addr_make("user");
+        assert!(suite
+            .minter
+            .call_as(&user)
+            .propose_admin_action(AdminAction::Pause { pause: false })
+            .is_err());
+        suite
+            .minter
+            .propose_admin_action(AdminAction::UpdateConfig {
+                config: config.clone(),
+            })?;
+        suite
+            .minter
+            .propose_admin_action(AdminAction::Pause { pause: false })?;
+        let queued = suite.minter.admin_actions(None, None)?;
+        assert_eq!(queued.len(), 2);
+        assert_eq!(queued[0].id, 1);
+        assert_eq!(queued[0].executable_at, now.plus_seconds(100));
+        assert_eq!(
+            suite.minter.admin_actions(None, Some(1))?[0].action,
+            AdminAction::Pause { pause: false }
+        );
+
+        let err = suite.minter.execute_admin_action(1u64).unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::ActionNotReady {
+                executable_at: now.plus_seconds(100)
+            }
+            .to_string()
+        );
+
+        suite.minter.cancel_admin_action(2u64)?;
+        assert_eq!(suite.minter.admin_actions(None, None)?.len(), 1);
+        assert!(suite.minter.execute_admin_action(2u64).is_err());
+
+        // raising the delay postpones actions already queued
+        mock.app.borrow_mut().sudo(SudoMsg::Wasm(WasmSudo {
+            contract_addr: minter.clone(),
+            message: to_json_binary(&bs721_account_minter::msg::SudoMsg::UpdateAdminDelay {
+                delay: 150,
+            })?,
+        }))?;
+        assert_eq!(
+            suite.minter.admin_actions(None, None)?[0].executable_at,
+            now.plus_seconds(150)
+        );
+        mock.wait_seconds(100)?;
+        let err = suite.minter.execute_admin_action(1u64).unwrap_err();
+        assert_eq!(
+            err.root().to_string(),
+            MinterContractError::ActionNotReady {
+                executable_at: now.plus_seconds(150)
+            }
+            .to_string()
+        );
+
+        mock.wait_seconds(50)?;
+        assert!(suite
+            .minter
+            .call_as(&user)
+            .execute_admin_action(1u64)
+            .is_err());
+        suite.minter.execute_admin_action(1u64)?;
+        assert_eq!(suite.minter.config()?, config);
+        assert!(suite.minter.admin_actions(None, None)?.is_empty());
+        // executed actions can't be replayed
+        assert!(suite.minter.execute_admin_action(1u64).is_err());
+        assert!(suite.minter.can_mint("bandura", &user, None)?.paused);
+
+        // a queued whitelist phase only opens once the delay has passed
+        suite
+            .minter
+            .propose_admin_action(AdminAction::AddWhitelist { whitelist })?;
+        assert!(suite.minter.whitelists()?.is_empty());
+        mock.wait_seconds(150)?;
+        suite.minter.execute_admin_action(3u64)?;
+        assert_eq!(suite.minter.whitelists()?.len(), 1);
+
+        Ok(())
+    }
 }
 
 mod associate_address {
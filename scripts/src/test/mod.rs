@@ -1,5 +1,7 @@
 pub mod account;
 pub mod market;
+pub mod middleware;
+pub mod migrate;
 pub mod minter;
 pub mod smart_accounts;
 
@@ -77,7 +79,7 @@ impl BtsgAccountSuite<MockBech32> {
                 cooldown_cancel_fee: coin(500_000_000, "ubtsg"),
                 hooks_admin: None,
             },
-            None,
+            Some(&sender_addr),
             &[],
         )?;
         // Account Minter
@@ -97,8 +99,9 @@ impl BtsgAccountSuite<MockBech32> {
                     base_price: BASE_PRICE.into(),
                     base_delegation: BASE_DELEGATION.into(),
                     mint_start_delay: Some(200u64),
+                    pricing: None,
                 },
-                None,
+                Some(&sender_addr),
                 &[],
             )?
             .event_attr_value("wasm", "bs721_account_address")?;
@@ -259,6 +262,30 @@ impl BtsgAccountSuite<MockBech32> {
         Ok(())
     }
 
+    /// undelegate tokens from val one.
+    pub fn undelegate_from_val(
+        &mut self,
+        mock: MockBech32,
+        delegator: Addr,
+        amount: u128,
+    ) -> anyhow::Result<()> {
+        let block_info = mock.block_info()?;
+        mock.app.borrow_mut().init_modules(|router, api, storage| {
+            router.staking.execute(
+                api,
+                storage,
+                router,
+                &block_info,
+                delegator,
+                StakingMsg::Undelegate {
+                    validator: VALIDATOR_1.into(),
+                    amount: coin(amount, "ubtsg"),
+                },
+            )
+        })?;
+        Ok(())
+    }
+
     pub fn mint_and_list(
         &mut self,
         mock: MockBech32,
@@ -286,6 +313,12 @@ impl BtsgAccountSuite<MockBech32> {
         let res = self.minter.call_as(user).execute(
             &bs721_account_minter::msg::ExecuteMsg::MintAndList {
                 account: account.to_string(),
+                proof: None,
+                referrer: None,
+                voucher: None,
+                owner: None,
+                initial: None,
+                associate: None,
             },
             &name_fee,
         )?;
@@ -311,6 +344,7 @@ impl BtsgAccountSuite<MockBech32> {
         self.market.call_as(&bidder).execute(
             &btsg_account::market::ExecuteMsg::SetBid {
                 token_id: account.into(),
+                waive_cooldown: false,
             },
             &bid_amnt,
         )?;